@@ -0,0 +1,37 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::service::user_service::UserService;
+
+/// The caller identified by a `Bearer` token with an active session.
+///
+/// Rejects with `401` when the header is missing, the token is invalid or
+/// the session was revoked.
+#[derive(Debug, Clone, Copy)]
+pub struct AuthUser {
+    pub user_id: Uuid,
+    pub session_id: Uuid,
+}
+
+impl FromRequestParts<PgPool> for AuthUser {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, pool: &PgPool) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+
+        let (user_id, session_id) = UserService::authenticate(pool, token)
+            .await
+            .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+        Ok(Self { user_id, session_id })
+    }
+}
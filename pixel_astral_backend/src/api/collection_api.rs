@@ -0,0 +1,473 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get, patch, post, put},
+    Extension, Router,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::config::Config;
+use crate::error;
+use crate::mailer::Mailer;
+use crate::service::collection_service::CollectionService;
+use crate::service::game_service::GameService;
+use crate::type::collection_type::{
+    CollectionInvitation, CollectionMember, CreateCollectionRequest, InviteMemberRequest, SetMemberRoleRequest,
+    SharedCollection, UpdateCollectionRequest,
+};
+use crate::type::game_type::{CreateGameRequest, Game};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/", get(get_collections))
+        .route("/", post(create_collection))
+        .route("/invitations", get(get_received_invitations))
+        .route("/invitations/:invitation_id/accept", post(accept_invitation))
+        .route("/invitations/:invitation_id", delete(decline_invitation))
+        .route("/:collection_id", get(get_collection))
+        .route("/:collection_id", patch(update_collection))
+        .route("/:collection_id", delete(delete_collection))
+        .route("/:collection_id/members", get(get_members))
+        .route("/:collection_id/members/:user_id", put(set_member_role))
+        .route("/:collection_id/members/:user_id", delete(remove_member))
+        .route("/:collection_id/invitations", get(get_invitations))
+        .route("/:collection_id/invitations", post(invite_member))
+        .route("/:collection_id/invitations/:invitation_id", delete(revoke_invitation))
+        .route("/:collection_id/games", get(get_games))
+        .route("/:collection_id/games", post(create_game))
+        .route("/:collection_id/games/trash", get(get_trash))
+}
+
+#[utoipa::path(
+    "/api/collections",
+    get,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Shared collections the caller is a member of", body = [SharedCollection]),
+        (status = 401, description = "Missing or invalid token")
+    )
+)]
+async fn get_collections(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<SharedCollection>>, StatusCode> {
+    CollectionService::get_all_by_user(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/collections",
+    post,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    request_body = CreateCollectionRequest,
+    responses(
+        (status = 201, description = "Collection created with the caller as owner", body = SharedCollection),
+        (status = 400, description = "Invalid name"),
+        (status = 401, description = "Missing or invalid token")
+    )
+)]
+async fn create_collection(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<CreateCollectionRequest>,
+) -> Result<(StatusCode, Json<SharedCollection>), StatusCode> {
+    CollectionService::create(&pool, auth.user_id, request)
+        .await
+        .map(|collection| (StatusCode::CREATED, Json(collection)))
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/collections/invitations",
+    get,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Invitations to the caller's handle or verified email", body = [CollectionInvitation]),
+        (status = 401, description = "Missing or invalid token")
+    )
+)]
+async fn get_received_invitations(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<CollectionInvitation>>, StatusCode> {
+    CollectionService::get_received_invitations(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/collections/invitations/{invitation_id}/accept",
+    post,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(("invitation_id" = Uuid, Path, description = "Invitation ID")),
+    responses(
+        (status = 200, description = "Joined the collection", body = SharedCollection),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "No such invitation for the caller")
+    )
+)]
+async fn accept_invitation(
+    auth: AuthUser,
+    Path(invitation_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<SharedCollection>, StatusCode> {
+    CollectionService::accept_invitation(&pool, auth.user_id, invitation_id)
+        .await
+        .map_err(|err| error::status_code(&err))?
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[utoipa::path(
+    "/api/collections/invitations/{invitation_id}",
+    delete,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(("invitation_id" = Uuid, Path, description = "Invitation ID")),
+    responses(
+        (status = 204, description = "Invitation declined"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "No such invitation for the caller")
+    )
+)]
+async fn decline_invitation(
+    auth: AuthUser,
+    Path(invitation_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    CollectionService::decline_invitation(&pool, auth.user_id, invitation_id)
+        .await
+        .map(|declined| if declined { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/collections/{collection_id}",
+    get,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(("collection_id" = Uuid, Path, description = "Collection ID")),
+    responses(
+        (status = 200, description = "The collection, with the caller's role", body = SharedCollection),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "Collection not found, or the caller is not a member")
+    )
+)]
+async fn get_collection(
+    auth: AuthUser,
+    Path(collection_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<SharedCollection>, StatusCode> {
+    CollectionService::get_by_id(&pool, collection_id, auth.user_id)
+        .await
+        .map_err(|err| error::status_code(&err))?
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[utoipa::path(
+    "/api/collections/{collection_id}",
+    patch,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(("collection_id" = Uuid, Path, description = "Collection ID")),
+    request_body = UpdateCollectionRequest,
+    responses(
+        (status = 200, description = "Collection renamed", body = SharedCollection),
+        (status = 400, description = "Invalid name"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Only owners can rename the collection"),
+        (status = 404, description = "Collection not found, or the caller is not a member")
+    )
+)]
+async fn update_collection(
+    auth: AuthUser,
+    Path(collection_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<UpdateCollectionRequest>,
+) -> Result<Json<SharedCollection>, StatusCode> {
+    CollectionService::update(&pool, collection_id, auth.user_id, request)
+        .await
+        .map_err(|err| error::status_code(&err))?
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[utoipa::path(
+    "/api/collections/{collection_id}",
+    delete,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(("collection_id" = Uuid, Path, description = "Collection ID")),
+    responses(
+        (status = 204, description = "Collection and all its games deleted"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Only owners can delete the collection"),
+        (status = 404, description = "Collection not found, or the caller is not a member")
+    )
+)]
+async fn delete_collection(
+    auth: AuthUser,
+    Path(collection_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    CollectionService::delete(&pool, collection_id, auth.user_id)
+        .await
+        .map(|deleted| if deleted { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/collections/{collection_id}/members",
+    get,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(("collection_id" = Uuid, Path, description = "Collection ID")),
+    responses(
+        (status = 200, description = "Members, owners first", body = [CollectionMember]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "Collection not found, or the caller is not a member")
+    )
+)]
+async fn get_members(
+    auth: AuthUser,
+    Path(collection_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<CollectionMember>>, StatusCode> {
+    CollectionService::get_members(&pool, collection_id, auth.user_id)
+        .await
+        .map_err(|err| error::status_code(&err))?
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[utoipa::path(
+    "/api/collections/{collection_id}/members/{user_id}",
+    put,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(
+        ("collection_id" = Uuid, Path, description = "Collection ID"),
+        ("user_id" = Uuid, Path, description = "Member's user ID")
+    ),
+    request_body = SetMemberRoleRequest,
+    responses(
+        (status = 204, description = "Role changed"),
+        (status = 400, description = "Unknown role"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Only owners can change roles"),
+        (status = 404, description = "Collection or member not found"),
+        (status = 409, description = "The last owner cannot step down")
+    )
+)]
+async fn set_member_role(
+    auth: AuthUser,
+    Path((collection_id, user_id)): Path<(Uuid, Uuid)>,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<SetMemberRoleRequest>,
+) -> Result<StatusCode, StatusCode> {
+    CollectionService::set_member_role(&pool, collection_id, auth.user_id, user_id, request)
+        .await
+        .map(|updated| if updated { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/collections/{collection_id}/members/{user_id}",
+    delete,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(
+        ("collection_id" = Uuid, Path, description = "Collection ID"),
+        ("user_id" = Uuid, Path, description = "Member's user ID; the caller's own to leave")
+    ),
+    responses(
+        (status = 204, description = "Member removed, or the caller left"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Only owners can remove other members"),
+        (status = 404, description = "Collection or member not found"),
+        (status = 409, description = "The last owner cannot leave")
+    )
+)]
+async fn remove_member(
+    auth: AuthUser,
+    Path((collection_id, user_id)): Path<(Uuid, Uuid)>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    CollectionService::remove_member(&pool, collection_id, auth.user_id, user_id)
+        .await
+        .map(|removed| if removed { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/collections/{collection_id}/invitations",
+    get,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(("collection_id" = Uuid, Path, description = "Collection ID")),
+    responses(
+        (status = 200, description = "Open invitations, newest first", body = [CollectionInvitation]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Only owners can see invitations"),
+        (status = 404, description = "Collection not found, or the caller is not a member")
+    )
+)]
+async fn get_invitations(
+    auth: AuthUser,
+    Path(collection_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<CollectionInvitation>>, StatusCode> {
+    CollectionService::get_invitations(&pool, collection_id, auth.user_id)
+        .await
+        .map_err(|err| error::status_code(&err))?
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[utoipa::path(
+    "/api/collections/{collection_id}/invitations",
+    post,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(("collection_id" = Uuid, Path, description = "Collection ID")),
+    request_body = InviteMemberRequest,
+    responses(
+        (status = 201, description = "Invitation created; email invitations are also mailed", body = CollectionInvitation),
+        (status = 400, description = "Neither or both of email and handle, invalid email or unknown role"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Only owners can invite"),
+        (status = 404, description = "Collection or handle not found"),
+        (status = 409, description = "Already a member or already invited")
+    )
+)]
+async fn invite_member(
+    auth: AuthUser,
+    Path(collection_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+    Extension(config): Extension<Config>,
+    Extension(mailer): Extension<Arc<dyn Mailer>>,
+    Json(request): Json<InviteMemberRequest>,
+) -> Result<(StatusCode, Json<CollectionInvitation>), StatusCode> {
+    CollectionService::invite(&pool, &config, mailer, collection_id, auth.user_id, request)
+        .await
+        .map_err(|err| error::status_code(&err))?
+        .map(|invitation| (StatusCode::CREATED, Json(invitation)))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[utoipa::path(
+    "/api/collections/{collection_id}/invitations/{invitation_id}",
+    delete,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(
+        ("collection_id" = Uuid, Path, description = "Collection ID"),
+        ("invitation_id" = Uuid, Path, description = "Invitation ID")
+    ),
+    responses(
+        (status = 204, description = "Invitation withdrawn"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Only owners can withdraw invitations"),
+        (status = 404, description = "Collection or invitation not found")
+    )
+)]
+async fn revoke_invitation(
+    auth: AuthUser,
+    Path((collection_id, invitation_id)): Path<(Uuid, Uuid)>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    CollectionService::revoke_invitation(&pool, collection_id, auth.user_id, invitation_id)
+        .await
+        .map(|revoked| if revoked { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|err| error::status_code(&err))
+}
+
+/// Single shared games are read and changed through the usual
+/// `/api/users/{user_id}/games/{id}` routes with the member's own user ID;
+/// the role is checked there.
+#[utoipa::path(
+    "/api/collections/{collection_id}/games",
+    get,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(("collection_id" = Uuid, Path, description = "Collection ID")),
+    responses(
+        (status = 200, description = "Games in the collection, newest first", body = [Game]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "Collection not found, or the caller is not a member")
+    )
+)]
+async fn get_games(
+    auth: AuthUser,
+    Path(collection_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Game>>, StatusCode> {
+    GameService::get_all_by_collection(&pool, collection_id, auth.user_id)
+        .await
+        .map_err(|err| error::status_code(&err))?
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[utoipa::path(
+    "/api/collections/{collection_id}/games",
+    post,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(("collection_id" = Uuid, Path, description = "Collection ID")),
+    request_body = CreateGameRequest,
+    responses(
+        (status = 200, description = "Game added, with the caller as `user_id`", body = Game),
+        (status = 400, description = "Invalid game"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Viewers cannot add games"),
+        (status = 404, description = "Collection not found, or the caller is not a member")
+    )
+)]
+async fn create_game(
+    auth: AuthUser,
+    Path(collection_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<CreateGameRequest>,
+) -> Result<Json<Game>, StatusCode> {
+    GameService::create_shared(&pool, collection_id, auth.user_id, request)
+        .await
+        .map_err(|err| error::status_code(&err))?
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[utoipa::path(
+    "/api/collections/{collection_id}/games/trash",
+    get,
+    tag = "collections",
+    security(("bearer_auth" = [])),
+    params(("collection_id" = Uuid, Path, description = "Collection ID")),
+    responses(
+        (status = 200, description = "Games in the collection's trash, most recently deleted first", body = [Game]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "Collection not found, or the caller is not a member")
+    )
+)]
+async fn get_trash(
+    auth: AuthUser,
+    Path(collection_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Game>>, StatusCode> {
+    GameService::get_collection_trash(&pool, collection_id, auth.user_id)
+        .await
+        .map_err(|err| error::status_code(&err))?
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
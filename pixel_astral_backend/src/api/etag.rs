@@ -0,0 +1,69 @@
+use axum::{
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Strong validator for one item, from its `version` column.
+pub fn for_version(version: i32) -> String {
+    format!("\"{}\"", version)
+}
+
+/// Validator for a list of items; changes when any item is added, removed,
+/// reordered or written.
+pub fn for_list(items: impl IntoIterator<Item = (Uuid, i32)>) -> String {
+    let mut hasher = Sha256::new();
+
+    for (id, version) in items {
+        hasher.update(id.as_bytes());
+        hasher.update(version.to_be_bytes());
+    }
+
+    format!("\"{}\"", &hex::encode(hasher.finalize())[..32])
+}
+
+/// Validator for a response without versions, from its serialized body.
+pub fn for_body(body: &[u8]) -> String {
+    format!("\"{}\"", &hex::encode(Sha256::digest(body))[..32])
+}
+
+/// Versions listed in `If-Match`, for a conditional write. `None` when the
+/// header is absent or `*`; tags that are weak or not one of ours are
+/// dropped, so they can never match.
+pub fn if_match_versions(headers: &HeaderMap) -> Option<Vec<i32>> {
+    let value = headers.get(header::IF_MATCH)?;
+    let Ok(value) = value.to_str() else {
+        return Some(Vec::new());
+    };
+
+    if value.trim() == "*" {
+        return None;
+    }
+
+    let versions = value
+        .split(',')
+        .map(str::trim)
+        .filter_map(|tag| tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok())
+        .collect();
+
+    Some(versions)
+}
+
+/// Answers `304 Not Modified` if `If-None-Match` lists `etag` (weak
+/// comparison, `*` matches anything), and `body` with the `ETag` otherwise.
+pub fn respond(headers: &HeaderMap, etag: String, body: impl IntoResponse) -> Response {
+    let not_modified = headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag);
+
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+    }
+
+    ([(header::ETAG, etag)], body).into_response()
+}
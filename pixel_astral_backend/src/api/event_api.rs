@@ -0,0 +1,69 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+    routing::get,
+    Extension, Router,
+};
+use futures::stream::{Stream, StreamExt};
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::error;
+use crate::event_hub::EventHub;
+use crate::service::event_service::EventService;
+use crate::type::event_type::{ItemNotification, StreamEvent, EVENT_HEARTBEAT_INTERVAL};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new().route("/", get(stream_events))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/events",
+    get,
+    tag = "events",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("Last-Event-ID" = Option<Uuid>, Header, description = "Resume after this event")
+    ),
+    responses(
+        (status = 200, description = "Server-sent `created`, `updated` and `deleted` events, each carrying an item event. A `reset` event means the client should resync.", body = ItemNotification, content_type = "text/event-stream"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn stream_events(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(hub): Extension<EventHub>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<Uuid>().ok());
+
+    let events = EventService::subscribe(&pool, &hub, user_id, last_event_id)
+        .await
+        .map_err(|err| error::status_code(&err))?;
+
+    let stream = events.map(|event| match event {
+        StreamEvent::Item(item) => Event::default()
+            .id(item.id.to_string())
+            .event(item.event_name())
+            .json_data(&item),
+        StreamEvent::Reset => Ok(Event::default().event("reset").data("{}")),
+    });
+
+    Ok(Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(EVENT_HEARTBEAT_INTERVAL)
+            .text("heartbeat"),
+    ))
+}
@@ -0,0 +1,68 @@
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::error;
+use crate::service::export_service::ExportService;
+use crate::type::export_type::ExportQuery;
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/", get(export_collection))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/export",
+    get,
+    tag = "export",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ExportQuery
+    ),
+    responses(
+        (status = 200, description = "Versioned JSON envelope (profile, tags, games, manga, novels) or CSV with one row per item, streamed as a download", content_type = ["application/json", "text/csv"]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "User not found")
+    )
+)]
+async fn export_collection(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    Query(query): Query<ExportQuery>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Response, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let format = query.format.unwrap_or_default();
+
+    let stream = ExportService::export(&pool, auth.user_id, format)
+        .await
+        .map_err(|err| error::status_code(&err))?;
+
+    let disposition = format!(
+        "attachment; filename=\"pixelastral-export-{}.{}\"",
+        Utc::now().format("%Y%m%d"),
+        format.extension()
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response())
+}
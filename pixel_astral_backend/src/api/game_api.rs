@@ -1,76 +1,613 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::Json,
-    routing::{delete, get, post, put},
-    Router,
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, patch, post, put},
+    Extension, Router,
 };
+use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::api::auth::AuthUser;
+use crate::api::{etag, upload};
+use crate::blob_store::BlobStore;
+use crate::error;
+use crate::service::cover_service::CoverService;
 use crate::service::game_service::GameService;
-use crate::type::game_type::{CreateGameRequest, Game, UpdateGameRequest};
+use crate::service::tag_service::TagService;
+use crate::type::cover_type::{Cover, MAX_COVER_BYTES};
+use crate::type::history_type::ItemEvent;
+use crate::type::item_type::ItemKind;
+use crate::type::game_type::{
+    BulkGameRequest, BulkGameResponse, CreateGameRequest, Game, GameFilter, UpdateGameRequest,
+};
+use crate::type::tag_type::{SetItemTagsRequest, Tag};
 
 pub fn routes() -> Router<sqlx::PgPool> {
     Router::new()
         .route("/", get(get_games))
         .route("/", post(create_game))
+        .route("/bulk", post(bulk_games))
         .route("/:id", get(get_game))
-        .route("/:id", put(update_game))
+        .route("/:id", put(replace_game))
+        .route("/:id", patch(update_game))
+        .route("/trash", get(get_trash))
+        .route("/trash", delete(empty_trash))
+        .route("/trash/:id", delete(purge))
+        .route("/:id/restore", post(restore))
         .route("/:id", delete(delete_game))
+        .route("/:id/cover", post(upload_cover).layer(DefaultBodyLimit::max(MAX_COVER_BYTES + 64 * 1024)))
+        .route("/:id/cover", delete(delete_cover))
+        .route("/:id/tags", get(get_tags))
+        .route("/:id/tags", put(set_tags))
+        .route("/:id/history", get(get_history))
+        .route("/:id/history/:event_id/revert", post(revert))
 }
 
+#[utoipa::path(
+    "/api/users/{user_id}/games",
+    get,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        GameFilter
+    ),
+    responses(
+        (status = 200, description = "The user's games, with an `ETag` over the whole list", body = [Game]),
+        (status = 304, description = "`If-None-Match` matches the current list"),
+        (status = 400, description = "Invalid tag ID"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
 async fn get_games(
     Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    Query(filter): Query<GameFilter>,
     State(pool): State<sqlx::PgPool>,
-) -> Result<Json<Vec<Game>>, StatusCode> {
-    GameService::get_all_by_user(&pool, user_id)
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let games = GameService::get_all_by_user(&pool, auth.user_id, filter)
         .await
-        .map(|games| Json(games))
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+        .map_err(|err| error::status_code(&err))?;
+
+    let tag = etag::for_list(games.iter().map(|game| (game.id, game.version)));
+    Ok(etag::respond(&headers, tag, Json(games)))
 }
 
 async fn create_game(
     Path(user_id): Path<Uuid>,
+    auth: AuthUser,
     State(pool): State<sqlx::PgPool>,
     Json(request): Json<CreateGameRequest>,
 ) -> Result<Json<Game>, StatusCode> {
-    GameService::create(&pool, user_id, request)
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    GameService::create(&pool, auth.user_id, request)
         .await
         .map(|game| Json(game))
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+        .map_err(|err| error::status_code(&err))
 }
 
+#[utoipa::path(
+    "/api/users/{user_id}/games/bulk",
+    post,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    request_body = BulkGameRequest,
+    responses(
+        (status = 200, description = "Operations applied; see per-item results", body = BulkGameResponse),
+        (status = 400, description = "No operations, or more than the allowed number"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 422, description = "An operation failed and `all_or_nothing` rolled everything back", body = BulkGameResponse)
+    )
+)]
+async fn bulk_games(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<BulkGameRequest>,
+) -> Result<(StatusCode, Json<BulkGameResponse>), StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    GameService::bulk(&pool, auth.user_id, request)
+        .await
+        .map(|response| {
+            let status = if response.committed { StatusCode::OK } else { StatusCode::UNPROCESSABLE_ENTITY };
+            (status, Json(response))
+        })
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/games/{id}",
+    get,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Game ID")
+    ),
+    responses(
+        (status = 200, description = "The game, with its version as `ETag`", body = Game),
+        (status = 304, description = "`If-None-Match` matches the current version"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Game not found")
+    )
+)]
 async fn get_game(
     Path((user_id, game_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
     State(pool): State<sqlx::PgPool>,
-) -> Result<Json<Game>, StatusCode> {
-    GameService::get_by_id(&pool, game_id, user_id)
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let game = GameService::get_by_id(&pool, game_id, auth.user_id)
         .await
-        .map(|game_opt| {
-            game_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
-        })
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(etag::respond(&headers, etag::for_version(game.version), Json(game)))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/games/{id}",
+    put,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Game ID")
+    ),
+    request_body = CreateGameRequest,
+    responses(
+        (status = 200, description = "Game replaced; optional fields left out are cleared", body = Game),
+        (status = 400, description = "Invalid game"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user, or the game is shared and the caller is a viewer"),
+        (status = 404, description = "Game not found"),
+        (status = 412, description = "`If-Match` does not match the current version")
+    )
+)]
+async fn replace_game(
+    Path((user_id, game_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    headers: HeaderMap,
+    Json(request): Json<CreateGameRequest>,
+) -> Result<Response, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let if_match = etag::if_match_versions(&headers);
+    let game = GameService::replace(&pool, game_id, auth.user_id, request, if_match.as_deref())
+        .await
+        .map_err(|err| error::status_code(&err))?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(([(header::ETAG, etag::for_version(game.version))], Json(game)).into_response())
 }
 
+/// Accepts `application/json` and `application/merge-patch+json` (RFC 7396);
+/// both mean the same here since games have no nested objects.
+#[utoipa::path(
+    "/api/users/{user_id}/games/{id}",
+    patch,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Game ID")
+    ),
+    request_body(
+        content((UpdateGameRequest = "application/merge-patch+json"), (UpdateGameRequest = "application/json")),
+        description = "Omitted fields are kept, `null` clears a field"
+    ),
+    responses(
+        (status = 200, description = "Game updated", body = Game),
+        (status = 400, description = "Invalid change, e.g. clearing `title`"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user, or the game is shared and the caller is a viewer"),
+        (status = 404, description = "Game not found"),
+        (status = 412, description = "`If-Match` does not match the current version"),
+        (status = 415, description = "Body is not JSON")
+    )
+)]
 async fn update_game(
     Path((user_id, game_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
     State(pool): State<sqlx::PgPool>,
+    headers: HeaderMap,
     Json(request): Json<UpdateGameRequest>,
-) -> Result<Json<Game>, StatusCode> {
-    GameService::update(&pool, game_id, user_id, request)
+) -> Result<Response, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let if_match = etag::if_match_versions(&headers);
+    let game = GameService::update(&pool, game_id, auth.user_id, request, if_match.as_deref())
         .await
-        .map(|game_opt| {
-            game_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
-        })
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map_err(|err| error::status_code(&err))?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(([(header::ETAG, etag::for_version(game.version))], Json(game)).into_response())
 }
 
+#[utoipa::path(
+    "/api/users/{user_id}/games/{id}",
+    delete,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Game ID")
+    ),
+    responses(
+        (status = 204, description = "Game moved to the trash"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user, or the game is shared and the caller is a viewer"),
+        (status = 404, description = "Game not found"),
+        (status = 412, description = "`If-Match` does not match the current version")
+    )
+)]
 async fn delete_game(
     Path((user_id, game_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
     State(pool): State<sqlx::PgPool>,
+    headers: HeaderMap,
 ) -> Result<StatusCode, StatusCode> {
-    GameService::delete(&pool, game_id, user_id)
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let if_match = etag::if_match_versions(&headers);
+
+    GameService::delete(&pool, game_id, auth.user_id, if_match.as_deref())
         .await
         .map(|deleted| if deleted { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/games/{id}/cover",
+    post,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Game ID")
+    ),
+    request_body(content_type = "multipart/form-data", description = "Image in a `file` field (JPEG, PNG, WebP or GIF, up to 5 MB)"),
+    responses(
+        (status = 200, description = "Cover stored with thumbnails", body = Cover),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user, or the game is shared and the caller is a viewer"),
+        (status = 404, description = "Game not found"),
+        (status = 413, description = "Image is too large"),
+        (status = 415, description = "File is not a supported image")
+    )
+)]
+async fn upload_cover(
+    Path((user_id, game_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+    multipart: Multipart,
+) -> Result<Json<Cover>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let (bytes, content_type) = upload::read_file_field(multipart).await?;
+
+    CoverService::upload(&pool, store.as_ref(), ItemKind::Game, game_id, auth.user_id, bytes, content_type.as_deref())
+        .await
+        .map(|cover_opt| {
+            cover_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/games/{id}/cover",
+    delete,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Game ID")
+    ),
+    responses(
+        (status = 204, description = "Cover removed"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user, or the game is shared and the caller is a viewer"),
+        (status = 404, description = "Game not found")
+    )
+)]
+async fn delete_cover(
+    Path((user_id, game_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    CoverService::delete(&pool, store.as_ref(), ItemKind::Game, game_id, auth.user_id)
+        .await
+        .map(|deleted| if deleted { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/games/{id}/tags",
+    get,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Game ID")
+    ),
+    responses(
+        (status = 200, description = "Tags on the game", body = [Tag]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Game not found")
+    )
+)]
+async fn get_tags(
+    Path((user_id, game_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Tag>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    TagService::get_item_tags(&pool, auth.user_id, ItemKind::Game, game_id)
+        .await
+        .map(|tags_opt| {
+            tags_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/games/{id}/tags",
+    put,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Game ID")
+    ),
+    request_body = SetItemTagsRequest,
+    responses(
+        (status = 200, description = "Tags replaced", body = [Tag]),
+        (status = 400, description = "Unknown tag"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user, or the game is shared and the caller is a viewer"),
+        (status = 404, description = "Game not found")
+    )
+)]
+async fn set_tags(
+    Path((user_id, game_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<SetItemTagsRequest>,
+) -> Result<Json<Vec<Tag>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    TagService::set_item_tags(&pool, auth.user_id, ItemKind::Game, game_id, request.tag_ids)
+        .await
+        .map(|tags_opt| {
+            tags_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/games/trash",
+    get,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Games in the trash, most recently deleted first", body = [Game]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn get_trash(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Game>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    GameService::get_trash(&pool, auth.user_id)
+        .await
+        .map(Json)
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
 }
+
+#[utoipa::path(
+    "/api/users/{user_id}/games/{id}/restore",
+    post,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Game ID")
+    ),
+    responses(
+        (status = 200, description = "Game restored from the trash", body = Game),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user, or the game is shared and the caller is a viewer"),
+        (status = 404, description = "Game not in the trash")
+    )
+)]
+async fn restore(
+    Path((user_id, game_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Game>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    GameService::restore(&pool, game_id, auth.user_id)
+        .await
+        .map(|item_opt| {
+            item_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/games/trash/{id}",
+    delete,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Game ID")
+    ),
+    responses(
+        (status = 204, description = "Game permanently deleted"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user, or the game is shared and the caller is not an owner"),
+        (status = 404, description = "Game not in the trash")
+    )
+)]
+async fn purge(
+    Path((user_id, game_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    GameService::purge(&pool, store.as_ref(), game_id, auth.user_id)
+        .await
+        .map(|purged| if purged { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/games/trash",
+    delete,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 204, description = "Trash emptied"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn empty_trash(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    GameService::empty_trash(&pool, store.as_ref(), auth.user_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/games/{id}/history",
+    get,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Game ID")
+    ),
+    responses(
+        (status = 200, description = "Changes to the game, newest first", body = Vec<ItemEvent>),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Game not found")
+    )
+)]
+async fn get_history(
+    Path((user_id, game_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<ItemEvent>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    GameService::get_history(&pool, game_id, auth.user_id)
+        .await
+        .map(|events_opt| {
+            events_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/games/{id}/history/{event_id}/revert",
+    post,
+    tag = "games",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Game ID"),
+        ("event_id" = Uuid, Path, description = "History event whose version to restore")
+    ),
+    responses(
+        (status = 200, description = "Game reverted; the revert is recorded as a new event", body = Game),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user, or the game is shared and the caller is a viewer"),
+        (status = 404, description = "Game or event not found")
+    )
+)]
+async fn revert(
+    Path((user_id, game_id, event_id)): Path<(Uuid, Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Game>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    GameService::revert(&pool, game_id, auth.user_id, event_id)
+        .await
+        .map(|game_opt| {
+            game_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
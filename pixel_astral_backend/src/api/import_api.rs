@@ -0,0 +1,73 @@
+use axum::{
+    extract::{DefaultBodyLimit, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::Json,
+    routing::post,
+    Router,
+};
+use bytes::Bytes;
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::error;
+use crate::service::import_service::ImportService;
+use crate::type::export_type::ExportFormat;
+use crate::type::import_type::{ImportQuery, ImportResponse, MAX_IMPORT_BYTES};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/", post(import_collection).layer(DefaultBodyLimit::max(MAX_IMPORT_BYTES)))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/import",
+    post,
+    tag = "import",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ImportQuery
+    ),
+    request_body(content_type = ["text/csv", "application/json", "application/xml"], description = "For the generic source: CSV with a header row, a PixelAstral JSON export, or a JSON array of objects. Otherwise the tracker's own export file (up to 10 MB)"),
+    responses(
+        (status = 200, description = "Preview (dry run) or applied import with a result per row", body = ImportResponse),
+        (status = 400, description = "Unreadable file or invalid mapping"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 413, description = "File is too large"),
+        (status = 422, description = "Some rows are invalid; nothing was imported", body = ImportResponse)
+    )
+)]
+async fn import_collection(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    Query(query): Query<ImportQuery>,
+    State(pool): State<sqlx::PgPool>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, Json<ImportResponse>), StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let format = query.format.unwrap_or_else(|| {
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        if content_type.contains("csv") { ExportFormat::Csv } else { ExportFormat::Json }
+    });
+
+    ImportService::import(&pool, auth.user_id, format, &body, &query)
+        .await
+        .map(|response| {
+            let status = if !response.dry_run && !response.applied {
+                StatusCode::UNPROCESSABLE_ENTITY
+            } else {
+                StatusCode::OK
+            };
+            (status, Json(response))
+        })
+        .map_err(|err| error::status_code(&err))
+}
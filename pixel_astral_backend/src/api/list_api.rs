@@ -0,0 +1,394 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get, patch, post, put},
+    Router,
+};
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::error;
+use crate::service::list_service::ListService;
+use crate::service::share_service::ShareService;
+use crate::type::item_type::ItemKind;
+use crate::type::list_type::{
+    AddListItemRequest, CreateListRequest, CustomList, ListItem, ListWithItems, MoveListItemRequest,
+    UpdateListRequest,
+};
+use crate::type::public_type::{CreatedShareLink, ShareLink};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/", get(get_lists))
+        .route("/", post(create_list))
+        .route("/:id", get(get_list))
+        .route("/:id", patch(update_list))
+        .route("/:id", delete(delete_list))
+        .route("/:id/items", post(add_list_item))
+        .route("/:id/items/:item_type/:item_id", delete(remove_list_item))
+        .route("/:id/items/:item_type/:item_id/position", put(move_list_item))
+        .route("/:id/shares", get(get_share_links))
+        .route("/:id/shares", post(create_share_link))
+        .route("/:id/shares/:share_id", delete(revoke_share_link))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/lists",
+    get,
+    tag = "lists",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "The user's lists, without items", body = [CustomList]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn get_lists(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<CustomList>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    ListService::get_all_by_user(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/lists",
+    post,
+    tag = "lists",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    request_body = CreateListRequest,
+    responses(
+        (status = 200, description = "List created", body = CustomList),
+        (status = 400, description = "Invalid name"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn create_list(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<CreateListRequest>,
+) -> Result<Json<CustomList>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    ListService::create(&pool, auth.user_id, request)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/lists/{id}",
+    get,
+    tag = "lists",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "List ID")
+    ),
+    responses(
+        (status = 200, description = "The list with its items in order", body = ListWithItems),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "List not found")
+    )
+)]
+async fn get_list(
+    Path((user_id, list_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<ListWithItems>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    ListService::get_by_id(&pool, list_id, auth.user_id)
+        .await
+        .map(|list_opt| {
+            list_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/lists/{id}",
+    patch,
+    tag = "lists",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "List ID")
+    ),
+    request_body = UpdateListRequest,
+    responses(
+        (status = 200, description = "List updated", body = CustomList),
+        (status = 400, description = "Invalid name"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "List not found")
+    )
+)]
+async fn update_list(
+    Path((user_id, list_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<UpdateListRequest>,
+) -> Result<Json<CustomList>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    ListService::update(&pool, list_id, auth.user_id, request)
+        .await
+        .map(|list_opt| {
+            list_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/lists/{id}",
+    delete,
+    tag = "lists",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "List ID")
+    ),
+    responses(
+        (status = 204, description = "List deleted; the items themselves are kept"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "List not found")
+    )
+)]
+async fn delete_list(
+    Path((user_id, list_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    ListService::delete(&pool, list_id, auth.user_id)
+        .await
+        .map(|deleted| if deleted { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/lists/{id}/items",
+    post,
+    tag = "lists",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "List ID")
+    ),
+    request_body = AddListItemRequest,
+    responses(
+        (status = 200, description = "Item added at the requested position", body = ListItem),
+        (status = 400, description = "Invalid position"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "List or item not found"),
+        (status = 409, description = "Item is already in the list")
+    )
+)]
+async fn add_list_item(
+    Path((user_id, list_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<AddListItemRequest>,
+) -> Result<Json<ListItem>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    ListService::add_item(&pool, list_id, auth.user_id, request)
+        .await
+        .map(|item_opt| {
+            item_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/lists/{id}/items/{item_type}/{item_id}/position",
+    put,
+    tag = "lists",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "List ID"),
+        ("item_type" = ItemKind, Path, description = "game, manga or novel"),
+        ("item_id" = Uuid, Path, description = "Item ID")
+    ),
+    request_body = MoveListItemRequest,
+    responses(
+        (status = 200, description = "Item moved; items in between shifted by one", body = ListItem),
+        (status = 400, description = "Invalid position"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "List or item not found")
+    )
+)]
+async fn move_list_item(
+    Path((user_id, list_id, item_type, item_id)): Path<(Uuid, Uuid, ItemKind, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<MoveListItemRequest>,
+) -> Result<Json<ListItem>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    ListService::move_item(&pool, list_id, auth.user_id, item_type, item_id, request.position)
+        .await
+        .map(|item_opt| {
+            item_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/lists/{id}/items/{item_type}/{item_id}",
+    delete,
+    tag = "lists",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "List ID"),
+        ("item_type" = ItemKind, Path, description = "game, manga or novel"),
+        ("item_id" = Uuid, Path, description = "Item ID")
+    ),
+    responses(
+        (status = 204, description = "Item removed from the list"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "List or item not found")
+    )
+)]
+async fn remove_list_item(
+    Path((user_id, list_id, item_type, item_id)): Path<(Uuid, Uuid, ItemKind, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    ListService::remove_item(&pool, list_id, auth.user_id, item_type, item_id)
+        .await
+        .map(|removed| if removed { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/lists/{id}/shares",
+    get,
+    tag = "lists",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "List ID")
+    ),
+    responses(
+        (status = 200, description = "Share links of the list, including revoked ones", body = [ShareLink]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "List not found")
+    )
+)]
+async fn get_share_links(
+    Path((user_id, list_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<ShareLink>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    ShareService::get_all(&pool, list_id, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/lists/{id}/shares",
+    post,
+    tag = "lists",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "List ID")
+    ),
+    responses(
+        (status = 200, description = "Share link created; the token is only shown here", body = CreatedShareLink),
+        (status = 400, description = "Profile is private"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "List not found")
+    )
+)]
+async fn create_share_link(
+    Path((user_id, list_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<CreatedShareLink>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    ShareService::create(&pool, list_id, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/lists/{id}/shares/{share_id}",
+    delete,
+    tag = "lists",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "List ID"),
+        ("share_id" = Uuid, Path, description = "Share link ID")
+    ),
+    responses(
+        (status = 200, description = "Share link revoked", body = ShareLink),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Share link not found")
+    )
+)]
+async fn revoke_share_link(
+    Path((user_id, list_id, share_id)): Path<(Uuid, Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<ShareLink>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    ShareService::revoke(&pool, share_id, list_id, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
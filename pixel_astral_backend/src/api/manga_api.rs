@@ -0,0 +1,563 @@
+use axum::{
+    extract::{DefaultBodyLimit, Multipart, Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get, post, put},
+    Extension, Router,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::api::upload;
+use crate::blob_store::BlobStore;
+use crate::error;
+use crate::service::cover_service::CoverService;
+use crate::service::manga_service::MangaService;
+use crate::service::tag_service::TagService;
+use crate::type::cover_type::{Cover, MAX_COVER_BYTES};
+use crate::type::history_type::ItemEvent;
+use crate::type::item_type::ItemKind;
+use crate::type::manga_type::{CreateMangaRequest, Manga, MangaProgressRequest, UpdateMangaRequest};
+use crate::type::tag_type::{SetItemTagsRequest, Tag};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/", get(get_manga_list))
+        .route("/", post(create_manga))
+        .route("/:id", get(get_manga))
+        .route("/:id", put(update_manga))
+        .route("/trash", get(get_trash))
+        .route("/trash", delete(empty_trash))
+        .route("/trash/:id", delete(purge))
+        .route("/:id/restore", post(restore))
+        .route("/:id", delete(delete_manga))
+        .route("/:id/cover", post(upload_cover).layer(DefaultBodyLimit::max(MAX_COVER_BYTES + 64 * 1024)))
+        .route("/:id/cover", delete(delete_cover))
+        .route("/:id/tags", get(get_tags))
+        .route("/:id/tags", put(set_tags))
+        .route("/:id/history", get(get_history))
+        .route("/:id/history/:event_id/revert", post(revert))
+        .route("/:id/progress", post(advance_progress))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga",
+    get,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "The user's manga", body = [Manga]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn get_manga_list(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Manga>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MangaService::get_all_by_user(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga",
+    post,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    request_body = CreateMangaRequest,
+    responses(
+        (status = 200, description = "Manga created", body = Manga),
+        (status = 400, description = "Invalid field"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn create_manga(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<CreateMangaRequest>,
+) -> Result<Json<Manga>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MangaService::create(&pool, auth.user_id, request)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/{id}",
+    get,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Manga ID")
+    ),
+    responses(
+        (status = 200, description = "Manga found", body = Manga),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Manga not found")
+    )
+)]
+async fn get_manga(
+    Path((user_id, manga_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Manga>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MangaService::get_by_id(&pool, manga_id, auth.user_id)
+        .await
+        .map(|manga_opt| {
+            manga_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/{id}",
+    put,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Manga ID")
+    ),
+    request_body = UpdateMangaRequest,
+    responses(
+        (status = 200, description = "Manga updated", body = Manga),
+        (status = 400, description = "Invalid field"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Manga not found")
+    )
+)]
+async fn update_manga(
+    Path((user_id, manga_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<UpdateMangaRequest>,
+) -> Result<Json<Manga>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MangaService::update(&pool, manga_id, auth.user_id, request)
+        .await
+        .map(|manga_opt| {
+            manga_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/{id}/progress",
+    post,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Manga ID")
+    ),
+    request_body = MangaProgressRequest,
+    responses(
+        (status = 200, description = "Progress recorded", body = Manga),
+        (status = 400, description = "No progress given"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Manga not found")
+    )
+)]
+async fn advance_progress(
+    Path((user_id, manga_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<MangaProgressRequest>,
+) -> Result<Json<Manga>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MangaService::advance_progress(&pool, manga_id, auth.user_id, request)
+        .await
+        .map(|manga_opt| {
+            manga_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/{id}",
+    delete,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Manga ID")
+    ),
+    responses(
+        (status = 204, description = "Manga moved to the trash"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Manga not found")
+    )
+)]
+async fn delete_manga(
+    Path((user_id, manga_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MangaService::delete(&pool, manga_id, auth.user_id)
+        .await
+        .map(|deleted| if deleted { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/{id}/cover",
+    post,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Manga ID")
+    ),
+    request_body(content_type = "multipart/form-data", description = "Image in a `file` field (JPEG, PNG, WebP or GIF, up to 5 MB)"),
+    responses(
+        (status = 200, description = "Cover stored with thumbnails", body = Cover),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Manga not found"),
+        (status = 413, description = "Image is too large"),
+        (status = 415, description = "File is not a supported image")
+    )
+)]
+async fn upload_cover(
+    Path((user_id, manga_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+    multipart: Multipart,
+) -> Result<Json<Cover>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let (bytes, content_type) = upload::read_file_field(multipart).await?;
+
+    CoverService::upload(&pool, store.as_ref(), ItemKind::Manga, manga_id, auth.user_id, bytes, content_type.as_deref())
+        .await
+        .map(|cover_opt| {
+            cover_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/{id}/cover",
+    delete,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Manga ID")
+    ),
+    responses(
+        (status = 204, description = "Cover removed"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Manga not found")
+    )
+)]
+async fn delete_cover(
+    Path((user_id, manga_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    CoverService::delete(&pool, store.as_ref(), ItemKind::Manga, manga_id, auth.user_id)
+        .await
+        .map(|deleted| if deleted { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/{id}/tags",
+    get,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Manga ID")
+    ),
+    responses(
+        (status = 200, description = "Tags on the manga", body = [Tag]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Manga not found")
+    )
+)]
+async fn get_tags(
+    Path((user_id, manga_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Tag>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    TagService::get_item_tags(&pool, auth.user_id, ItemKind::Manga, manga_id)
+        .await
+        .map(|tags_opt| {
+            tags_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/{id}/tags",
+    put,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Manga ID")
+    ),
+    request_body = SetItemTagsRequest,
+    responses(
+        (status = 200, description = "Tags replaced", body = [Tag]),
+        (status = 400, description = "Unknown tag"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Manga not found")
+    )
+)]
+async fn set_tags(
+    Path((user_id, manga_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<SetItemTagsRequest>,
+) -> Result<Json<Vec<Tag>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    TagService::set_item_tags(&pool, auth.user_id, ItemKind::Manga, manga_id, request.tag_ids)
+        .await
+        .map(|tags_opt| {
+            tags_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/trash",
+    get,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Manga in the trash, most recently deleted first", body = [Manga]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn get_trash(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Manga>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MangaService::get_trash(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/{id}/restore",
+    post,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Manga ID")
+    ),
+    responses(
+        (status = 200, description = "Manga restored from the trash", body = Manga),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Manga not in the trash")
+    )
+)]
+async fn restore(
+    Path((user_id, manga_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Manga>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MangaService::restore(&pool, manga_id, auth.user_id)
+        .await
+        .map(|item_opt| {
+            item_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/trash/{id}",
+    delete,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Manga ID")
+    ),
+    responses(
+        (status = 204, description = "Manga permanently deleted"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Manga not in the trash")
+    )
+)]
+async fn purge(
+    Path((user_id, manga_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MangaService::purge(&pool, store.as_ref(), manga_id, auth.user_id)
+        .await
+        .map(|purged| if purged { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/trash",
+    delete,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 204, description = "Trash emptied"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn empty_trash(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MangaService::empty_trash(&pool, store.as_ref(), auth.user_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/{id}/history",
+    get,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Manga ID")
+    ),
+    responses(
+        (status = 200, description = "Changes to the manga, newest first", body = Vec<ItemEvent>),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Manga not found")
+    )
+)]
+async fn get_history(
+    Path((user_id, manga_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<ItemEvent>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MangaService::get_history(&pool, manga_id, auth.user_id)
+        .await
+        .map(|events_opt| {
+            events_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/manga/{id}/history/{event_id}/revert",
+    post,
+    tag = "manga",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Manga ID"),
+        ("event_id" = Uuid, Path, description = "History event whose version to restore")
+    ),
+    responses(
+        (status = 200, description = "Manga reverted; the revert is recorded as a new event", body = Manga),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Manga or event not found")
+    )
+)]
+async fn revert(
+    Path((user_id, manga_id, event_id)): Path<(Uuid, Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Manga>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MangaService::revert(&pool, manga_id, auth.user_id, event_id)
+        .await
+        .map(|manga_opt| {
+            manga_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
@@ -1,2 +1,21 @@
+pub mod auth;
+pub mod collection_api;
+pub mod etag;
+pub mod event_api;
+pub mod export_api;
 pub mod game_api;
+pub mod import_api;
+pub mod list_api;
+pub mod manga_api;
+pub mod movie_api;
+pub mod novel_api;
+pub mod public_api;
+pub mod recommendation_api;
+pub mod review_api;
+pub mod social_api;
+pub mod stats_api;
+pub mod sync_api;
+pub mod tag_api;
+pub mod upload;
 pub mod user_api;
+pub mod visibility_api;
@@ -0,0 +1,78 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::error;
+use crate::service::movie_service::MovieService;
+use crate::type::movie_type::Movie;
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/", get(get_movies))
+        .route("/:id", get(get_movie))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/movies",
+    get,
+    tag = "movies",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "The user's movies", body = [Movie]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn get_movies(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Movie>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MovieService::get_all_by_user(&pool, user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/movies/{id}",
+    get,
+    tag = "movies",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Movie ID")
+    ),
+    responses(
+        (status = 200, description = "Movie found", body = Movie),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Movie not found")
+    )
+)]
+async fn get_movie(
+    Path((user_id, movie_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Movie>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    MovieService::get_by_id(&pool, movie_id, user_id)
+        .await
+        .map_err(|err| error::status_code(&err))?
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
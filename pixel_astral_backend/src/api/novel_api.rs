@@ -0,0 +1,563 @@
+use axum::{
+    extract::{DefaultBodyLimit, Multipart, Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get, post, put},
+    Extension, Router,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::api::upload;
+use crate::blob_store::BlobStore;
+use crate::error;
+use crate::service::cover_service::CoverService;
+use crate::service::novel_service::NovelService;
+use crate::service::tag_service::TagService;
+use crate::type::cover_type::{Cover, MAX_COVER_BYTES};
+use crate::type::history_type::ItemEvent;
+use crate::type::item_type::ItemKind;
+use crate::type::novel_type::{CreateNovelRequest, Novel, NovelProgressRequest, UpdateNovelRequest};
+use crate::type::tag_type::{SetItemTagsRequest, Tag};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/", get(get_novels))
+        .route("/", post(create_novel))
+        .route("/:id", get(get_novel))
+        .route("/:id", put(update_novel))
+        .route("/trash", get(get_trash))
+        .route("/trash", delete(empty_trash))
+        .route("/trash/:id", delete(purge))
+        .route("/:id/restore", post(restore))
+        .route("/:id", delete(delete_novel))
+        .route("/:id/cover", post(upload_cover).layer(DefaultBodyLimit::max(MAX_COVER_BYTES + 64 * 1024)))
+        .route("/:id/cover", delete(delete_cover))
+        .route("/:id/tags", get(get_tags))
+        .route("/:id/tags", put(set_tags))
+        .route("/:id/history", get(get_history))
+        .route("/:id/history/:event_id/revert", post(revert))
+        .route("/:id/progress", post(advance_progress))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels",
+    get,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "The user's novels", body = [Novel]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn get_novels(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Novel>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    NovelService::get_all_by_user(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels",
+    post,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    request_body = CreateNovelRequest,
+    responses(
+        (status = 200, description = "Novel created", body = Novel),
+        (status = 400, description = "Invalid field"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn create_novel(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<CreateNovelRequest>,
+) -> Result<Json<Novel>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    NovelService::create(&pool, auth.user_id, request)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/{id}",
+    get,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Novel ID")
+    ),
+    responses(
+        (status = 200, description = "Novel found", body = Novel),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Novel not found")
+    )
+)]
+async fn get_novel(
+    Path((user_id, novel_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Novel>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    NovelService::get_by_id(&pool, novel_id, auth.user_id)
+        .await
+        .map(|novel_opt| {
+            novel_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/{id}",
+    put,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Novel ID")
+    ),
+    request_body = UpdateNovelRequest,
+    responses(
+        (status = 200, description = "Novel updated", body = Novel),
+        (status = 400, description = "Invalid field"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Novel not found")
+    )
+)]
+async fn update_novel(
+    Path((user_id, novel_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<UpdateNovelRequest>,
+) -> Result<Json<Novel>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    NovelService::update(&pool, novel_id, auth.user_id, request)
+        .await
+        .map(|novel_opt| {
+            novel_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/{id}/progress",
+    post,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Novel ID")
+    ),
+    request_body = NovelProgressRequest,
+    responses(
+        (status = 200, description = "Progress recorded", body = Novel),
+        (status = 400, description = "No progress given"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Novel not found")
+    )
+)]
+async fn advance_progress(
+    Path((user_id, novel_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<NovelProgressRequest>,
+) -> Result<Json<Novel>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    NovelService::advance_progress(&pool, novel_id, auth.user_id, request)
+        .await
+        .map(|novel_opt| {
+            novel_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/{id}",
+    delete,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Novel ID")
+    ),
+    responses(
+        (status = 204, description = "Novel moved to the trash"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Novel not found")
+    )
+)]
+async fn delete_novel(
+    Path((user_id, novel_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    NovelService::delete(&pool, novel_id, auth.user_id)
+        .await
+        .map(|deleted| if deleted { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/{id}/cover",
+    post,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Novel ID")
+    ),
+    request_body(content_type = "multipart/form-data", description = "Image in a `file` field (JPEG, PNG, WebP or GIF, up to 5 MB)"),
+    responses(
+        (status = 200, description = "Cover stored with thumbnails", body = Cover),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Novel not found"),
+        (status = 413, description = "Image is too large"),
+        (status = 415, description = "File is not a supported image")
+    )
+)]
+async fn upload_cover(
+    Path((user_id, novel_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+    multipart: Multipart,
+) -> Result<Json<Cover>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let (bytes, content_type) = upload::read_file_field(multipart).await?;
+
+    CoverService::upload(&pool, store.as_ref(), ItemKind::Novel, novel_id, auth.user_id, bytes, content_type.as_deref())
+        .await
+        .map(|cover_opt| {
+            cover_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/{id}/cover",
+    delete,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Novel ID")
+    ),
+    responses(
+        (status = 204, description = "Cover removed"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Novel not found")
+    )
+)]
+async fn delete_cover(
+    Path((user_id, novel_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    CoverService::delete(&pool, store.as_ref(), ItemKind::Novel, novel_id, auth.user_id)
+        .await
+        .map(|deleted| if deleted { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/{id}/tags",
+    get,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Novel ID")
+    ),
+    responses(
+        (status = 200, description = "Tags on the novel", body = [Tag]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Novel not found")
+    )
+)]
+async fn get_tags(
+    Path((user_id, novel_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Tag>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    TagService::get_item_tags(&pool, auth.user_id, ItemKind::Novel, novel_id)
+        .await
+        .map(|tags_opt| {
+            tags_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/{id}/tags",
+    put,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Novel ID")
+    ),
+    request_body = SetItemTagsRequest,
+    responses(
+        (status = 200, description = "Tags replaced", body = [Tag]),
+        (status = 400, description = "Unknown tag"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Novel not found")
+    )
+)]
+async fn set_tags(
+    Path((user_id, novel_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<SetItemTagsRequest>,
+) -> Result<Json<Vec<Tag>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    TagService::set_item_tags(&pool, auth.user_id, ItemKind::Novel, novel_id, request.tag_ids)
+        .await
+        .map(|tags_opt| {
+            tags_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/trash",
+    get,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Novels in the trash, most recently deleted first", body = [Novel]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn get_trash(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Novel>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    NovelService::get_trash(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/{id}/restore",
+    post,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Novel ID")
+    ),
+    responses(
+        (status = 200, description = "Novel restored from the trash", body = Novel),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Novel not in the trash")
+    )
+)]
+async fn restore(
+    Path((user_id, novel_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Novel>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    NovelService::restore(&pool, novel_id, auth.user_id)
+        .await
+        .map(|item_opt| {
+            item_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/trash/{id}",
+    delete,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Novel ID")
+    ),
+    responses(
+        (status = 204, description = "Novel permanently deleted"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Novel not in the trash")
+    )
+)]
+async fn purge(
+    Path((user_id, novel_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    NovelService::purge(&pool, store.as_ref(), novel_id, auth.user_id)
+        .await
+        .map(|purged| if purged { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/trash",
+    delete,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 204, description = "Trash emptied"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn empty_trash(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    NovelService::empty_trash(&pool, store.as_ref(), auth.user_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/{id}/history",
+    get,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Novel ID")
+    ),
+    responses(
+        (status = 200, description = "Changes to the novel, newest first", body = Vec<ItemEvent>),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Novel not found")
+    )
+)]
+async fn get_history(
+    Path((user_id, novel_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<ItemEvent>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    NovelService::get_history(&pool, novel_id, auth.user_id)
+        .await
+        .map(|events_opt| {
+            events_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/novels/{id}/history/{event_id}/revert",
+    post,
+    tag = "novels",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Novel ID"),
+        ("event_id" = Uuid, Path, description = "History event whose version to restore")
+    ),
+    responses(
+        (status = 200, description = "Novel reverted; the revert is recorded as a new event", body = Novel),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Novel or event not found")
+    )
+)]
+async fn revert(
+    Path((user_id, novel_id, event_id)): Path<(Uuid, Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Novel>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    NovelService::revert(&pool, novel_id, auth.user_id, event_id)
+        .await
+        .map(|novel_opt| {
+            novel_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
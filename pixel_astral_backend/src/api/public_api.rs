@@ -0,0 +1,84 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::Response,
+    routing::get,
+    Router,
+};
+use serde::Serialize;
+
+use crate::api::etag;
+use crate::error;
+use crate::service::public_service::PublicService;
+use crate::service::share_service::ShareService;
+use crate::type::public_type::{PublicCollection, SharedList, PUBLIC_CACHE_CONTROL};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/shares/:token", get(get_shared_list))
+        .route("/:handle/games", get(get_public_games))
+}
+
+#[utoipa::path(
+    "/api/public/{handle}/games",
+    get,
+    tag = "public",
+    params(("handle" = String, Path, description = "Profile handle")),
+    responses(
+        (status = 200, description = "The profile and its public games, without notes. Cacheable by shared caches", body = PublicCollection),
+        (status = 304, description = "Not modified (If-None-Match)"),
+        (status = 404, description = "No public profile with this handle")
+    )
+)]
+async fn get_public_games(
+    Path(handle): Path<String>,
+    State(pool): State<sqlx::PgPool>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let collection = PublicService::get_public_games(&pool, &handle)
+        .await
+        .map_err(|err| error::status_code(&err))?;
+
+    cached(&headers, &collection)
+}
+
+#[utoipa::path(
+    "/api/public/shares/{token}",
+    get,
+    tag = "public",
+    params(("token" = String, Path, description = "Share token")),
+    responses(
+        (status = 200, description = "The shared list with its visible items, in order. Cacheable by shared caches", body = SharedList),
+        (status = 304, description = "Not modified (If-None-Match)"),
+        (status = 404, description = "Unknown or revoked token, or the owner's profile is private")
+    )
+)]
+async fn get_shared_list(
+    Path(token): Path<String>,
+    State(pool): State<sqlx::PgPool>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let list = ShareService::get_shared_list(&pool, &token)
+        .await
+        .map_err(|err| error::status_code(&err))?;
+
+    cached(&headers, &list)
+}
+
+/// JSON body with an `ETag` over its bytes and `Cache-Control` for shared
+/// caches, on `304` responses too.
+fn cached(headers: &HeaderMap, body: &impl Serialize) -> Result<Response, StatusCode> {
+    let bytes = serde_json::to_vec(body).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let etag = etag::for_body(&bytes);
+
+    let mut response = etag::respond(
+        headers,
+        etag,
+        ([(header::CONTENT_TYPE, "application/json")], bytes),
+    );
+    response
+        .headers_mut()
+        .insert(header::CACHE_CONTROL, HeaderValue::from_static(PUBLIC_CACHE_CONTROL));
+
+    Ok(response)
+}
@@ -0,0 +1,49 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::error;
+use crate::service::recommendation_service::RecommendationService;
+use crate::type::recommendation_type::{Recommendation, RecommendationQuery};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new().route("/", get(get_recommendations))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/recommendations",
+    get,
+    tag = "recommendations",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        RecommendationQuery
+    ),
+    responses(
+        (status = 200, description = "Plan to Play games, best match first; scores are refreshed periodically and new games are unscored until then", body = [Recommendation]),
+        (status = 400, description = "Invalid limit"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn get_recommendations(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    Query(query): Query<RecommendationQuery>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Recommendation>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    RecommendationService::get_recommendations(&pool, auth.user_id, query)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
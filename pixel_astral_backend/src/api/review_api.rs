@@ -0,0 +1,65 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
+};
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::error;
+use crate::service::review_service::ReviewService;
+use crate::type::review_type::{ReviewFormat, ReviewQuery, YearInReview};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/:year", get(get_review))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/review/{year}",
+    get,
+    tag = "review",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("year" = i32, Path, description = "Calendar year (UTC)"),
+        ReviewQuery
+    ),
+    responses(
+        (status = 200, description = "Items started and finished, top rated, genres, longest backlog item and busiest month, as JSON or as a pixel-art SVG card", body = YearInReview, content_type = ["application/json", "image/svg+xml"]),
+        (status = 400, description = "Year out of range"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn get_review(
+    Path((user_id, year)): Path<(Uuid, i32)>,
+    auth: AuthUser,
+    Query(query): Query<ReviewQuery>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Response, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let review = ReviewService::get_review(&pool, auth.user_id, year)
+        .await
+        .map_err(|err| error::status_code(&err))?;
+
+    match query.format.unwrap_or_default() {
+        ReviewFormat::Json => Ok(Json(review).into_response()),
+        ReviewFormat::Svg => Ok((
+            [
+                (header::CONTENT_TYPE, "image/svg+xml; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("inline; filename=\"pixelastral-{}.svg\"", year),
+                ),
+            ],
+            ReviewService::render_card(&review),
+        )
+            .into_response()),
+    }
+}
@@ -0,0 +1,251 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get, post, put},
+    Router,
+};
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::error;
+use crate::service::activity_service::ActivityService;
+use crate::service::social_service::SocialService;
+use crate::type::social_type::{BlockedUser, FeedPage, FeedQuery, Follow, FollowUser};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/feed", get(get_feed))
+        .route("/following", get(get_following))
+        .route("/following/:user_id", put(follow_user))
+        .route("/following/:user_id", delete(unfollow_user))
+        .route("/followers", get(get_followers))
+        .route("/followers/:user_id/approve", post(approve_follower))
+        .route("/followers/:user_id", delete(remove_follower))
+        .route("/blocks", get(get_blocks))
+        .route("/blocks/:user_id", put(block_user))
+        .route("/blocks/:user_id", delete(unblock_user))
+}
+
+#[utoipa::path(
+    "/api/social/feed",
+    get,
+    tag = "social",
+    security(("bearer_auth" = [])),
+    params(FeedQuery),
+    responses(
+        (status = 200, description = "Activity of followed users, newest first", body = FeedPage),
+        (status = 400, description = "Invalid cursor or limit"),
+        (status = 401, description = "Missing or invalid token")
+    )
+)]
+async fn get_feed(
+    auth: AuthUser,
+    Query(query): Query<FeedQuery>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<FeedPage>, StatusCode> {
+    ActivityService::get_feed(&pool, auth.user_id, query)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/social/following",
+    get,
+    tag = "social",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Users the caller follows or asked to follow", body = [FollowUser]),
+        (status = 401, description = "Missing or invalid token")
+    )
+)]
+async fn get_following(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<FollowUser>>, StatusCode> {
+    SocialService::get_following(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/social/following/{user_id}",
+    put,
+    tag = "social",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User to follow")),
+    responses(
+        (status = 200, description = "Followed, or `pending` until a private profile approves", body = Follow),
+        (status = 400, description = "Cannot follow yourself"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "User not found")
+    )
+)]
+async fn follow_user(
+    auth: AuthUser,
+    Path(user_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Follow>, StatusCode> {
+    SocialService::follow(&pool, auth.user_id, user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/social/following/{user_id}",
+    delete,
+    tag = "social",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "Other user's ID")),
+    responses(
+        (status = 204, description = "Unfollowed, or request withdrawn"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "Not following this user")
+    )
+)]
+async fn unfollow_user(
+    auth: AuthUser,
+    Path(user_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    SocialService::unfollow(&pool, auth.user_id, user_id)
+        .await
+        .map(|removed| if removed { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/social/followers",
+    get,
+    tag = "social",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Users following the caller, including pending requests", body = [FollowUser]),
+        (status = 401, description = "Missing or invalid token")
+    )
+)]
+async fn get_followers(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<FollowUser>>, StatusCode> {
+    SocialService::get_followers(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/social/followers/{user_id}/approve",
+    post,
+    tag = "social",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "Follower to approve")),
+    responses(
+        (status = 200, description = "Follow request approved", body = Follow),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "No pending request from this user")
+    )
+)]
+async fn approve_follower(
+    auth: AuthUser,
+    Path(user_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Follow>, StatusCode> {
+    SocialService::approve(&pool, auth.user_id, user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/social/followers/{user_id}",
+    delete,
+    tag = "social",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "Other user's ID")),
+    responses(
+        (status = 204, description = "Follower removed, or request declined"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "Not a follower")
+    )
+)]
+async fn remove_follower(
+    auth: AuthUser,
+    Path(user_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    SocialService::remove_follower(&pool, auth.user_id, user_id)
+        .await
+        .map(|removed| if removed { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/social/blocks",
+    get,
+    tag = "social",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Users the caller has blocked", body = [BlockedUser]),
+        (status = 401, description = "Missing or invalid token")
+    )
+)]
+async fn get_blocks(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<BlockedUser>>, StatusCode> {
+    SocialService::get_blocks(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/social/blocks/{user_id}",
+    put,
+    tag = "social",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User to block")),
+    responses(
+        (status = 204, description = "Blocked; follows between the two users are removed"),
+        (status = 400, description = "Cannot block yourself"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "User not found")
+    )
+)]
+async fn block_user(
+    auth: AuthUser,
+    Path(user_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    SocialService::block(&pool, auth.user_id, user_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/social/blocks/{user_id}",
+    delete,
+    tag = "social",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "Other user's ID")),
+    responses(
+        (status = 204, description = "Unblocked"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "User is not blocked")
+    )
+)]
+async fn unblock_user(
+    auth: AuthUser,
+    Path(user_id): Path<Uuid>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    SocialService::unblock(&pool, auth.user_id, user_id)
+        .await
+        .map(|removed| if removed { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|err| error::status_code(&err))
+}
@@ -0,0 +1,49 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::error;
+use crate::service::stats_service::StatsService;
+use crate::type::item_type::ItemKind;
+use crate::type::stats_type::{CollectionStats, StatsQuery};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new().route("/", get(get_stats))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/stats",
+    get,
+    tag = "stats",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        StatsQuery
+    ),
+    responses(
+        (status = 200, description = "Counts by status, genre and rating, completions over time, average time to complete and activity streaks", body = CollectionStats),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn get_stats(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    Query(query): Query<StatsQuery>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<CollectionStats>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    StatsService::get_stats(&pool, auth.user_id, query.item_type.unwrap_or(ItemKind::Game))
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::error;
+use crate::service::sync_service::SyncService;
+use crate::type::sync_type::{SyncPushRequest, SyncPushResponse, SyncQuery, SyncResponse};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/", get(pull_changes))
+        .route("/", post(push_changes))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/sync",
+    get,
+    tag = "sync",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        SyncQuery
+    ),
+    responses(
+        (status = 200, description = "Items created, updated or deleted since the cursor, and the next cursor", body = SyncResponse),
+        (status = 400, description = "Unknown cursor"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn pull_changes(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    Query(query): Query<SyncQuery>,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<SyncResponse>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    SyncService::pull(&pool, auth.user_id, query.since)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/sync",
+    post,
+    tag = "sync",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    request_body = SyncPushRequest,
+    responses(
+        (status = 200, description = "Per-mutation results: applied, conflict (with the server copy) or rejected", body = SyncPushResponse),
+        (status = 400, description = "Too many mutations"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn push_changes(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<SyncPushRequest>,
+) -> Result<Json<SyncPushResponse>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    SyncService::push(&pool, auth.user_id, request)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
@@ -0,0 +1,147 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get, patch, post},
+    Router,
+};
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::error;
+use crate::service::tag_service::TagService;
+use crate::type::tag_type::{CreateTagRequest, Tag, UpdateTagRequest};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/", get(get_tags))
+        .route("/", post(create_tag))
+        .route("/:id", patch(update_tag))
+        .route("/:id", delete(delete_tag))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/tags",
+    get,
+    tag = "tags",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "The user's tags", body = [Tag]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn get_tags(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<Tag>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    TagService::get_all_by_user(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/tags",
+    post,
+    tag = "tags",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    request_body = CreateTagRequest,
+    responses(
+        (status = 200, description = "Tag created", body = Tag),
+        (status = 400, description = "Invalid name or color"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 409, description = "A tag with that name already exists")
+    )
+)]
+async fn create_tag(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<CreateTagRequest>,
+) -> Result<Json<Tag>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    TagService::create(&pool, auth.user_id, request)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/tags/{id}",
+    patch,
+    tag = "tags",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Tag ID")
+    ),
+    request_body = UpdateTagRequest,
+    responses(
+        (status = 200, description = "Tag updated", body = Tag),
+        (status = 400, description = "Invalid name or color"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Tag not found"),
+        (status = 409, description = "A tag with that name already exists")
+    )
+)]
+async fn update_tag(
+    Path((user_id, tag_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<UpdateTagRequest>,
+) -> Result<Json<Tag>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    TagService::update(&pool, tag_id, auth.user_id, request)
+        .await
+        .map(|tag_opt| {
+            tag_opt.map(Json).ok_or(StatusCode::NOT_FOUND)
+        })
+        .map_err(|err| error::status_code(&err))?
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/tags/{id}",
+    delete,
+    tag = "tags",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("id" = Uuid, Path, description = "Tag ID")
+    ),
+    responses(
+        (status = 204, description = "Tag deleted and removed from all items"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Tag not found")
+    )
+)]
+async fn delete_tag(
+    Path((user_id, tag_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<StatusCode, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    TagService::delete(&pool, tag_id, auth.user_id)
+        .await
+        .map(|deleted| if deleted { StatusCode::NO_CONTENT } else { StatusCode::NOT_FOUND })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
@@ -0,0 +1,23 @@
+use axum::{extract::Multipart, http::StatusCode};
+use bytes::Bytes;
+
+/// Reads the `file` field of a multipart upload, returning its bytes and
+/// the declared content type.
+pub async fn read_file_field(mut multipart: Multipart) -> Result<(Bytes, Option<String>), StatusCode> {
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|err| err.status())?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let content_type = field.content_type().map(str::to_string);
+        let bytes = field.bytes().await.map_err(|err| err.status())?;
+
+        return Ok((bytes, content_type));
+    }
+
+    Err(StatusCode::BAD_REQUEST)
+}
@@ -1,20 +1,44 @@
 use axum::{
     extract::{Path, State},
     http::StatusCode,
-    response::Json,
-    routing::{get, post},
-    Router,
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, patch, post, put},
+    Extension, Router,
 };
+use std::sync::Arc;
 use uuid::Uuid;
 use utoipa::ToSchema;
 
+use crate::api::auth::AuthUser;
+use crate::blob_store::BlobStore;
+use crate::config::Config;
+use crate::error;
+use crate::mailer::Mailer;
+use crate::service::password_service::PasswordService;
+use crate::service::profile_service::ProfileService;
 use crate::service::user_service::UserService;
-use crate::type::user_type::{AuthResponse, CreateUserRequest, LoginRequest, UserResponse};
+use crate::type::profile_type::{UpdateProfileRequest, UserProfile};
+use crate::type::user_type::{
+    AuthResponse, ChangeEmailRequest, ChangePasswordRequest, CreateUserRequest,
+    DeleteAccountRequest, EmailRequest, LoginRequest, ResetPasswordRequest, UserResponse,
+    VerifyEmailRequest,
+};
 
 pub fn routes() -> Router<sqlx::PgPool> {
     Router::new()
         .route("/signup", post(signup))
         .route("/login", post(login))
+        .route("/verify", post(verify_email))
+        .route("/verify/resend", post(resend_verification))
+        .route("/password/forgot", post(forgot_password))
+        .route("/password/reset", post(reset_password))
+        .route("/me", get(get_me))
+        .route("/me", delete(delete_account))
+        .route("/me/password", put(change_password))
+        .route("/me/email", put(change_email))
+        .route("/me/deletion", delete(cancel_account_deletion))
+        .route("/me/profile", get(get_profile))
+        .route("/me/profile", patch(update_profile))
         .route("/me/:id", get(get_current_user))
 }
 
@@ -24,18 +48,23 @@ pub fn routes() -> Router<sqlx::PgPool> {
     tag = "auth",
     request_body = CreateUserRequest,
     responses(
-        (status = 200, description = "User created successfully", body = AuthResponse),
+        (status = 201, description = "User created, verification email sent", body = UserResponse),
+        (status = 400, description = "Invalid email or password rejected by policy"),
+        (status = 409, description = "Email is already registered"),
         (status = 500, description = "Internal server error")
     )
 )]
 async fn signup(
     State(pool): State<sqlx::PgPool>,
+    Extension(config): Extension<Config>,
+    Extension(passwords): Extension<Arc<PasswordService>>,
+    Extension(mailer): Extension<Arc<dyn Mailer>>,
     Json(request): Json<CreateUserRequest>,
-) -> Result<Json<AuthResponse>, StatusCode> {
-    UserService::signup(&pool, request)
+) -> Result<(StatusCode, Json<UserResponse>), StatusCode> {
+    UserService::signup(&pool, &config, &passwords, mailer, request)
         .await
-        .map(|response| Json(response))
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+        .map(|response| (StatusCode::CREATED, Json(response)))
+        .map_err(|err| error::status_code(&err))
 }
 
 #[utoipa::path(
@@ -45,37 +74,303 @@ async fn signup(
     request_body = LoginRequest,
     responses(
         (status = 200, description = "User logged in successfully", body = AuthResponse),
-        (status = 401, description = "Invalid credentials")
+        (status = 401, description = "Invalid credentials"),
+        (status = 403, description = "Email address not verified"),
+        (status = 429, description = "Too many failed attempts, account temporarily locked")
     )
 )]
 async fn login(
     State(pool): State<sqlx::PgPool>,
+    Extension(passwords): Extension<Arc<PasswordService>>,
     Json(request): Json<LoginRequest>,
 ) -> Result<Json<AuthResponse>, StatusCode> {
-    UserService::login(&pool, request)
+    UserService::login(&pool, &passwords, request)
         .await
         .map(|response| Json(response))
-        .map_err(|_| StatusCode::UNAUTHORIZED)
+        .map_err(|err| match error::status_code(&err) {
+            status @ (StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS) => status,
+            _ => StatusCode::UNAUTHORIZED,
+        })
+}
+
+#[utoipa::path(
+    "/api/users/verify",
+    post,
+    tag = "auth",
+    request_body = VerifyEmailRequest,
+    responses(
+        (status = 200, description = "Email verified", body = UserResponse),
+        (status = 400, description = "Invalid or expired token")
+    )
+)]
+async fn verify_email(
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<VerifyEmailRequest>,
+) -> Result<Json<UserResponse>, StatusCode> {
+    UserService::verify_email(&pool, &request.token)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/verify/resend",
+    post,
+    tag = "auth",
+    request_body = EmailRequest,
+    responses(
+        (status = 202, description = "Verification email sent if the account exists and is unverified")
+    )
+)]
+async fn resend_verification(
+    State(pool): State<sqlx::PgPool>,
+    Extension(config): Extension<Config>,
+    Extension(mailer): Extension<Arc<dyn Mailer>>,
+    Json(request): Json<EmailRequest>,
+) -> StatusCode {
+    if let Err(err) = UserService::resend_verification(&pool, &config, mailer, &request.email).await {
+        tracing::error!("Failed to resend verification: {:#}", err);
+    }
+
+    StatusCode::ACCEPTED
+}
+
+#[utoipa::path(
+    "/api/users/password/forgot",
+    post,
+    tag = "auth",
+    request_body = EmailRequest,
+    responses(
+        (status = 202, description = "Reset email sent if the account exists")
+    )
+)]
+async fn forgot_password(
+    State(pool): State<sqlx::PgPool>,
+    Extension(config): Extension<Config>,
+    Extension(mailer): Extension<Arc<dyn Mailer>>,
+    Json(request): Json<EmailRequest>,
+) -> StatusCode {
+    if let Err(err) = UserService::forgot_password(&pool, &config, mailer, &request.email).await {
+        tracing::error!("Failed to start password reset: {:#}", err);
+    }
+
+    StatusCode::ACCEPTED
+}
+
+#[utoipa::path(
+    "/api/users/password/reset",
+    post,
+    tag = "auth",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 204, description = "Password updated"),
+        (status = 400, description = "Invalid or expired token, or password rejected by policy")
+    )
+)]
+async fn reset_password(
+    State(pool): State<sqlx::PgPool>,
+    Extension(passwords): Extension<Arc<PasswordService>>,
+    Json(request): Json<ResetPasswordRequest>,
+) -> Result<StatusCode, StatusCode> {
+    UserService::reset_password(&pool, &passwords, request)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|err| error::status_code(&err))
 }
 
 #[utoipa::path(
     "/api/users/me/{id}",
     get,
     tag = "auth",
+    security(("bearer_auth" = [])),
     params(
         ("id" = Uuid, Path, description = "User ID")
     ),
     responses(
         (status = 200, description = "User found", body = UserResponse),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
         (status = 404, description = "User not found")
     )
 )]
 async fn get_current_user(
     Path(user_id): Path<Uuid>,
+    auth: AuthUser,
     State(pool): State<sqlx::PgPool>,
 ) -> Result<Json<UserResponse>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
     UserService::get_user(&pool, user_id)
         .await
         .map(|response| Json(response))
         .map_err(|_| StatusCode::NOT_FOUND)
 }
+
+#[utoipa::path(
+    "/api/users/me",
+    get,
+    tag = "account",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The signed-in user", body = UserResponse),
+        (status = 401, description = "Missing or invalid token")
+    )
+)]
+async fn get_me(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<UserResponse>, StatusCode> {
+    UserService::get_user(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::NOT_FOUND)
+}
+
+#[utoipa::path(
+    "/api/users/me/password",
+    put,
+    tag = "account",
+    security(("bearer_auth" = [])),
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 204, description = "Password changed, other sessions signed out"),
+        (status = 400, description = "New password rejected by policy"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Current password is incorrect")
+    )
+)]
+async fn change_password(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(passwords): Extension<Arc<PasswordService>>,
+    Json(request): Json<ChangePasswordRequest>,
+) -> Result<StatusCode, StatusCode> {
+    UserService::change_password(&pool, &passwords, auth.user_id, auth.session_id, request)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/me/email",
+    put,
+    tag = "account",
+    security(("bearer_auth" = [])),
+    request_body = ChangeEmailRequest,
+    responses(
+        (status = 200, description = "Verification sent to the new address", body = UserResponse),
+        (status = 400, description = "Invalid email address"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Password is incorrect"),
+        (status = 409, description = "Email is already registered")
+    )
+)]
+async fn change_email(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(config): Extension<Config>,
+    Extension(passwords): Extension<Arc<PasswordService>>,
+    Extension(mailer): Extension<Arc<dyn Mailer>>,
+    Json(request): Json<ChangeEmailRequest>,
+) -> Result<Json<UserResponse>, StatusCode> {
+    UserService::change_email(&pool, &config, &passwords, mailer, auth.user_id, request)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/me",
+    delete,
+    tag = "account",
+    security(("bearer_auth" = [])),
+    request_body = DeleteAccountRequest,
+    responses(
+        (status = 202, description = "Deletion scheduled after the grace period", body = UserResponse),
+        (status = 204, description = "Account and all collections deleted"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Password is incorrect")
+    )
+)]
+async fn delete_account(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Extension(store): Extension<Arc<dyn BlobStore>>,
+    Extension(config): Extension<Config>,
+    Extension(passwords): Extension<Arc<PasswordService>>,
+    Json(request): Json<DeleteAccountRequest>,
+) -> Result<Response, StatusCode> {
+    UserService::delete_account(&pool, store.as_ref(), &config, &passwords, auth.user_id, request)
+        .await
+        .map(|scheduled| match scheduled {
+            Some(user) => (StatusCode::ACCEPTED, Json(user)).into_response(),
+            None => StatusCode::NO_CONTENT.into_response(),
+        })
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/me/deletion",
+    delete,
+    tag = "account",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Scheduled deletion cancelled", body = UserResponse),
+        (status = 401, description = "Missing or invalid token")
+    )
+)]
+async fn cancel_account_deletion(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<UserResponse>, StatusCode> {
+    UserService::cancel_account_deletion(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/me/profile",
+    get,
+    tag = "profile",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The signed-in user's profile", body = UserProfile),
+        (status = 401, description = "Missing or invalid token")
+    )
+)]
+async fn get_profile(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<UserProfile>, StatusCode> {
+    ProfileService::get(&pool, auth.user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/me/profile",
+    patch,
+    tag = "profile",
+    security(("bearer_auth" = [])),
+    request_body = UpdateProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated", body = UserProfile),
+        (status = 400, description = "Invalid profile field"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 409, description = "Handle is already taken")
+    )
+)]
+async fn update_profile(
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<UpdateProfileRequest>,
+) -> Result<Json<UserProfile>, StatusCode> {
+    ProfileService::update(&pool, auth.user_id, request)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
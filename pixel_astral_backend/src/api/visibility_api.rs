@@ -0,0 +1,84 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+    routing::{get, put},
+    Router,
+};
+use uuid::Uuid;
+
+use crate::api::auth::AuthUser;
+use crate::error;
+use crate::service::public_service::PublicService;
+use crate::type::item_type::ItemKind;
+use crate::type::public_type::{ItemVisibility, SetItemVisibilityRequest};
+
+pub fn routes() -> Router<sqlx::PgPool> {
+    Router::new()
+        .route("/", get(get_item_visibilities))
+        .route("/:item_type/:item_id", put(set_item_visibility))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/visibility",
+    get,
+    tag = "visibility",
+    security(("bearer_auth" = [])),
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Items whose visibility differs from the profile's", body = [ItemVisibility]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user")
+    )
+)]
+async fn get_item_visibilities(
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+) -> Result<Json<Vec<ItemVisibility>>, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    PublicService::get_item_visibilities(&pool, user_id)
+        .await
+        .map(Json)
+        .map_err(|err| error::status_code(&err))
+}
+
+#[utoipa::path(
+    "/api/users/{user_id}/visibility/{item_type}/{item_id}",
+    put,
+    tag = "visibility",
+    security(("bearer_auth" = [])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("item_type" = ItemKind, Path, description = "game, manga or novel"),
+        ("item_id" = Uuid, Path, description = "Item ID")
+    ),
+    request_body = SetItemVisibilityRequest,
+    responses(
+        (status = 200, description = "Override set", body = ItemVisibility),
+        (status = 204, description = "Override removed; the item follows the profile"),
+        (status = 400, description = "Unknown visibility"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Token belongs to another user"),
+        (status = 404, description = "Item not found")
+    )
+)]
+async fn set_item_visibility(
+    Path((user_id, item_type, item_id)): Path<(Uuid, ItemKind, Uuid)>,
+    auth: AuthUser,
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<SetItemVisibilityRequest>,
+) -> Result<Response, StatusCode> {
+    if auth.user_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    match PublicService::set_item_visibility(&pool, user_id, item_type, item_id, request).await {
+        Ok(Some(visibility)) => Ok(Json(visibility).into_response()),
+        Ok(None) => Ok(StatusCode::NO_CONTENT.into_response()),
+        Err(err) => Err(error::status_code(&err)),
+    }
+}
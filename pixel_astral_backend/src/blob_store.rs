@@ -0,0 +1,164 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use object_store::aws::{AmazonS3, AmazonS3Builder};
+use object_store::path::Path as ObjectPath;
+use object_store::{Attribute, Attributes, ObjectStore, PutOptions, PutPayload};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::config::Config;
+
+/// Where uploaded files live. Keys are `/`-separated relative paths; every
+/// stored blob is reachable at `url(key)`.
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    async fn put(&self, key: &str, bytes: Bytes, content_type: &str) -> Result<()>;
+
+    async fn delete(&self, key: &str) -> Result<()>;
+
+    fn public_base_url(&self) -> &str;
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_base_url().trim_end_matches('/'), key)
+    }
+
+    /// Recovers the key from a URL produced by `url`, if it belongs to this store.
+    fn key_from_url(&self, url: &str) -> Option<String> {
+        url.strip_prefix(self.public_base_url().trim_end_matches('/'))
+            .and_then(|rest| rest.strip_prefix('/'))
+            .map(str::to_string)
+    }
+}
+
+/// Builds the store selected by `BLOB_BACKEND` (`local` or `s3`).
+pub fn from_config(config: &Config) -> Result<Arc<dyn BlobStore>> {
+    match config.blob_backend.as_str() {
+        "local" => Ok(Arc::new(LocalBlobStore::new(
+            &config.blob_local_dir,
+            &config.blob_public_url,
+        ))),
+        "s3" => Ok(Arc::new(S3BlobStore::new(config)?)),
+        other => Err(anyhow::anyhow!("Unknown BLOB_BACKEND: {}", other)),
+    }
+}
+
+/// Stores blobs under a directory that `main` serves at `/media`.
+pub struct LocalBlobStore {
+    root: PathBuf,
+    public_base_url: String,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl Into<PathBuf>, public_base_url: &str) -> Self {
+        Self {
+            root: root.into(),
+            public_base_url: public_base_url.to_string(),
+        }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        if key.split('/').any(|segment| segment.is_empty() || segment == "." || segment == "..") {
+            return Err(anyhow::anyhow!("Invalid blob key: {}", key));
+        }
+
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, bytes: Bytes, _content_type: &str) -> Result<()> {
+        let path = self.path(key)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .context("Failed to create blob directory")?;
+        }
+
+        tokio::fs::write(&path, bytes)
+            .await
+            .context("Failed to write blob")
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err).context("Failed to delete blob"),
+        }
+    }
+
+    fn public_base_url(&self) -> &str {
+        &self.public_base_url
+    }
+}
+
+/// Any S3-compatible service, including local MinIO-style stand-ins via
+/// `S3_ENDPOINT` with path-style requests.
+pub struct S3BlobStore {
+    store: AmazonS3,
+    public_base_url: String,
+}
+
+impl S3BlobStore {
+    pub fn new(config: &Config) -> Result<Self> {
+        let bucket = config
+            .s3_bucket
+            .as_deref()
+            .context("S3_BUCKET must be set when BLOB_BACKEND=s3")?;
+
+        let mut builder = AmazonS3Builder::new()
+            .with_bucket_name(bucket)
+            .with_region(&config.s3_region);
+
+        if let Some(endpoint) = &config.s3_endpoint {
+            builder = builder
+                .with_endpoint(endpoint)
+                .with_allow_http(endpoint.starts_with("http://"))
+                .with_virtual_hosted_style_request(false);
+        }
+
+        if let (Some(access_key), Some(secret_key)) = (&config.s3_access_key, &config.s3_secret_key) {
+            builder = builder
+                .with_access_key_id(access_key)
+                .with_secret_access_key(secret_key);
+        }
+
+        let store = builder.build().context("Failed to configure S3 store")?;
+
+        Ok(Self {
+            store,
+            public_base_url: config.blob_public_url.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn put(&self, key: &str, bytes: Bytes, content_type: &str) -> Result<()> {
+        let options = PutOptions {
+            attributes: Attributes::from_iter([(Attribute::ContentType, content_type.to_string())]),
+            ..Default::default()
+        };
+
+        self.store
+            .put_opts(&ObjectPath::from(key), PutPayload::from(bytes), options)
+            .await
+            .context("Failed to upload blob")?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match self.store.delete(&ObjectPath::from(key)).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(err) => Err(err).context("Failed to delete blob"),
+        }
+    }
+
+    fn public_base_url(&self) -> &str {
+        &self.public_base_url
+    }
+}
@@ -10,6 +10,32 @@ pub struct Config {
     pub jwt_secret: String,
     pub cors_allowed_origins: String,
     pub api_port: u16,
+    pub app_base_url: String,
+    pub frontend_url: String,
+    pub mailer: String,
+    pub mail_from: String,
+    pub mail_dir: String,
+    pub smtp_host: Option<String>,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub password_min_length: usize,
+    pub breached_passwords_file: Option<String>,
+    pub password_hash_algorithm: String,
+    pub bcrypt_cost: u32,
+    pub argon2_memory_kib: u32,
+    pub argon2_iterations: u32,
+    pub argon2_parallelism: u32,
+    pub account_deletion_grace_days: i64,
+    pub trash_retention_days: i64,
+    pub blob_backend: String,
+    pub blob_local_dir: String,
+    pub blob_public_url: String,
+    pub s3_endpoint: Option<String>,
+    pub s3_bucket: Option<String>,
+    pub s3_region: String,
+    pub s3_access_key: Option<String>,
+    pub s3_secret_key: Option<String>,
 }
 
 impl Config {
@@ -39,6 +65,97 @@ impl Config {
             .parse()
             .context("API_PORT must be a valid number")?;
 
+        let app_base_url = env::var("APP_BASE_URL")
+            .unwrap_or_else(|_| format!("http://localhost:{}", api_port));
+
+        // Links in emails open the app, which by default is served from the
+        // first allowed CORS origin.
+        let frontend_url = env::var("FRONTEND_URL")
+            .unwrap_or_else(|_| cors_allowed_origins.split(',').next().unwrap_or_default().to_string())
+            .trim()
+            .trim_end_matches('/')
+            .to_string();
+
+        let mailer = env::var("MAILER")
+            .unwrap_or_else(|_| "file".to_string());
+
+        let mail_from = env::var("MAIL_FROM")
+            .unwrap_or_else(|_| "PixelAstral <no-reply@pixelastral.local>".to_string());
+
+        let mail_dir = env::var("MAIL_DIR")
+            .unwrap_or_else(|_| "mail".to_string());
+
+        let smtp_host = env::var("SMTP_HOST").ok();
+
+        let smtp_port = env::var("SMTP_PORT")
+            .unwrap_or_else(|_| "587".to_string())
+            .parse()
+            .context("SMTP_PORT must be a valid number")?;
+
+        let smtp_username = env::var("SMTP_USERNAME").ok();
+
+        let smtp_password = env::var("SMTP_PASSWORD").ok();
+
+        let password_min_length = env::var("PASSWORD_MIN_LENGTH")
+            .unwrap_or_else(|_| "8".to_string())
+            .parse()
+            .context("PASSWORD_MIN_LENGTH must be a valid number")?;
+
+        let breached_passwords_file = env::var("BREACHED_PASSWORDS_FILE").ok();
+
+        let password_hash_algorithm = env::var("PASSWORD_HASH_ALGORITHM")
+            .unwrap_or_else(|_| "bcrypt".to_string());
+
+        let bcrypt_cost = env::var("BCRYPT_COST")
+            .unwrap_or_else(|_| bcrypt::DEFAULT_COST.to_string())
+            .parse()
+            .context("BCRYPT_COST must be a valid number")?;
+
+        let argon2_memory_kib = env::var("ARGON2_MEMORY_KIB")
+            .unwrap_or_else(|_| "19456".to_string())
+            .parse()
+            .context("ARGON2_MEMORY_KIB must be a valid number")?;
+
+        let argon2_iterations = env::var("ARGON2_ITERATIONS")
+            .unwrap_or_else(|_| "2".to_string())
+            .parse()
+            .context("ARGON2_ITERATIONS must be a valid number")?;
+
+        let argon2_parallelism = env::var("ARGON2_PARALLELISM")
+            .unwrap_or_else(|_| "1".to_string())
+            .parse()
+            .context("ARGON2_PARALLELISM must be a valid number")?;
+
+        let account_deletion_grace_days = env::var("ACCOUNT_DELETION_GRACE_DAYS")
+            .unwrap_or_else(|_| "14".to_string())
+            .parse()
+            .context("ACCOUNT_DELETION_GRACE_DAYS must be a valid number")?;
+
+        let trash_retention_days = env::var("TRASH_RETENTION_DAYS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .context("TRASH_RETENTION_DAYS must be a valid number")?;
+
+        let blob_backend = env::var("BLOB_BACKEND")
+            .unwrap_or_else(|_| "local".to_string());
+
+        let blob_local_dir = env::var("BLOB_LOCAL_DIR")
+            .unwrap_or_else(|_| "media".to_string());
+
+        let blob_public_url = env::var("BLOB_PUBLIC_URL")
+            .unwrap_or_else(|_| format!("{}/media", app_base_url));
+
+        let s3_endpoint = env::var("S3_ENDPOINT").ok();
+
+        let s3_bucket = env::var("S3_BUCKET").ok();
+
+        let s3_region = env::var("S3_REGION")
+            .unwrap_or_else(|_| "us-east-1".to_string());
+
+        let s3_access_key = env::var("S3_ACCESS_KEY").ok();
+
+        let s3_secret_key = env::var("S3_SECRET_KEY").ok();
+
         Ok(Self {
             database_url,
             supabase_url,
@@ -47,6 +164,32 @@ impl Config {
             jwt_secret,
             cors_allowed_origins,
             api_port,
+            app_base_url,
+            frontend_url,
+            mailer,
+            mail_from,
+            mail_dir,
+            smtp_host,
+            smtp_port,
+            smtp_username,
+            smtp_password,
+            password_min_length,
+            breached_passwords_file,
+            password_hash_algorithm,
+            bcrypt_cost,
+            argon2_memory_kib,
+            argon2_iterations,
+            argon2_parallelism,
+            account_deletion_grace_days,
+            trash_retention_days,
+            blob_backend,
+            blob_local_dir,
+            blob_public_url,
+            s3_endpoint,
+            s3_bucket,
+            s3_region,
+            s3_access_key,
+            s3_secret_key,
         })
     }
 }
@@ -0,0 +1,55 @@
+use axum::http::StatusCode;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum AppError {
+    Validation(String),
+    Unauthorized,
+    Forbidden(String),
+    NotFound,
+    Conflict(String),
+    PreconditionFailed,
+    PayloadTooLarge,
+    UnsupportedMediaType,
+    TooManyAttempts,
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Validation(message) => write!(f, "{}", message),
+            AppError::Unauthorized => write!(f, "Invalid email or password"),
+            AppError::Forbidden(message) => write!(f, "{}", message),
+            AppError::NotFound => write!(f, "Not found"),
+            AppError::Conflict(message) => write!(f, "{}", message),
+            AppError::PreconditionFailed => write!(f, "The item was changed since it was read"),
+            AppError::PayloadTooLarge => write!(f, "Upload is too large"),
+            AppError::UnsupportedMediaType => write!(f, "Unsupported file type"),
+            AppError::TooManyAttempts => write!(f, "Too many failed attempts, try again later"),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl AppError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::Validation(_) => StatusCode::BAD_REQUEST,
+            AppError::Unauthorized => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::NotFound => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::PreconditionFailed => StatusCode::PRECONDITION_FAILED,
+            AppError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::TooManyAttempts => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+}
+
+pub fn status_code(err: &anyhow::Error) -> StatusCode {
+    err.downcast_ref::<AppError>()
+        .map(AppError::status_code)
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
@@ -0,0 +1,37 @@
+use tokio::sync::broadcast;
+
+use crate::type::event_type::HubMessage;
+
+/// Buffer per subscriber; a stream that falls this far behind is closed
+/// and resumes from `Last-Event-ID`.
+const HUB_CAPACITY: usize = 1024;
+
+/// Fans item events received over `LISTEN` out to the event streams open
+/// on this instance. Each instance listens on its own, so events written
+/// through any instance reach every stream.
+#[derive(Clone)]
+pub struct EventHub {
+    sender: broadcast::Sender<HubMessage>,
+}
+
+impl EventHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(HUB_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<HubMessage> {
+        self.sender.subscribe()
+    }
+
+    /// Dropped silently when no stream is open.
+    pub fn publish(&self, message: HubMessage) {
+        let _ = self.sender.send(message);
+    }
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,89 @@
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::blob_store::BlobStore;
+use crate::event_hub::EventHub;
+use crate::service::event_service::EventService;
+use crate::service::recommendation_service::RecommendationService;
+use crate::service::trash_service::TrashService;
+use crate::service::user_service::UserService;
+use crate::type::event_type::HubMessage;
+
+const ACCOUNT_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const TRASH_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const RECOMMENDATION_REFRESH_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+const EVENT_LISTENER_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Periodically deletes accounts whose deletion grace period has ended,
+/// with the cover files of their items.
+pub fn spawn_account_purge(pool: PgPool, store: Arc<dyn BlobStore>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(ACCOUNT_PURGE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match UserService::purge_deleted_accounts(&pool, store.as_ref()).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Purged {} deleted accounts", count),
+                Err(err) => tracing::error!("Account purge failed: {:#}", err),
+            }
+        }
+    });
+}
+
+/// Periodically deletes collection items that have outlived the trash
+/// retention period, with their cover files.
+pub fn spawn_trash_purge(pool: PgPool, store: Arc<dyn BlobStore>, retention_days: i64) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TRASH_PURGE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match TrashService::purge_expired(&pool, store.as_ref(), retention_days).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Purged {} trashed items", count),
+                Err(err) => tracing::error!("Trash purge failed: {:#}", err),
+            }
+        }
+    });
+}
+
+/// Periodically rescores every user's `Plan to Play` backlog, starting
+/// right away so scores exist soon after a deploy.
+pub fn spawn_recommendation_refresh(pool: PgPool) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(RECOMMENDATION_REFRESH_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match RecommendationService::refresh(&pool).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Scored {} backlog games", count),
+                Err(err) => tracing::error!("Recommendation refresh failed: {:#}", err),
+            }
+        }
+    });
+}
+
+/// Keeps a `LISTEN` connection open for item events, reconnecting after
+/// failures. Streams are closed on each failure so clients replay what was
+/// missed.
+pub fn spawn_event_listener(pool: PgPool, hub: EventHub) {
+    tokio::spawn(async move {
+        loop {
+            if let Err(err) = EventService::listen(&pool, &hub).await {
+                tracing::error!("Event listener failed: {:#}", err);
+            }
+
+            hub.publish(HubMessage::Interrupted);
+            tokio::time::sleep(EVENT_LISTENER_RETRY_DELAY).await;
+        }
+    });
+}
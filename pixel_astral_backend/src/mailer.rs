@@ -0,0 +1,132 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::config::Config;
+
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, email: Email) -> Result<()>;
+}
+
+/// Builds the mailer selected by `MAILER` (`smtp`, `file` or `memory`).
+pub fn from_config(config: &Config) -> Result<Arc<dyn Mailer>> {
+    match config.mailer.as_str() {
+        "smtp" => Ok(Arc::new(SmtpMailer::new(config)?)),
+        "file" => Ok(Arc::new(FileMailer::new(&config.mail_dir))),
+        "memory" => Ok(Arc::new(MemoryMailer::default())),
+        other => Err(anyhow::anyhow!("Unknown MAILER backend: {}", other)),
+    }
+}
+
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(config: &Config) -> Result<Self> {
+        let host = config
+            .smtp_host
+            .as_deref()
+            .context("SMTP_HOST must be set when MAILER=smtp")?;
+
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+            .context("Failed to configure SMTP relay")?
+            .port(config.smtp_port);
+
+        if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        let from = config
+            .mail_from
+            .parse()
+            .context("MAIL_FROM must be a valid mailbox")?;
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, email: Email) -> Result<()> {
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(email.to.parse().context("Invalid recipient address")?)
+            .subject(email.subject)
+            .body(email.body)
+            .context("Failed to build email")?;
+
+        self.transport
+            .send(message)
+            .await
+            .context("Failed to send email")?;
+
+        Ok(())
+    }
+}
+
+/// Writes every message to `MAIL_DIR` as an `.eml` file for local development.
+pub struct FileMailer {
+    dir: PathBuf,
+}
+
+impl FileMailer {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, email: Email) -> Result<()> {
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .context("Failed to create mail directory")?;
+
+        let file_name = format!("{}-{}.eml", chrono::Utc::now().format("%Y%m%dT%H%M%S"), Uuid::new_v4());
+        let contents = format!("To: {}\r\nSubject: {}\r\n\r\n{}\r\n", email.to, email.subject, email.body);
+
+        tokio::fs::write(self.dir.join(file_name), contents)
+            .await
+            .context("Failed to write email")?;
+
+        Ok(())
+    }
+}
+
+/// Keeps sent messages in memory so tests can assert on them.
+#[derive(Default)]
+pub struct MemoryMailer {
+    sent: Mutex<Vec<Email>>,
+}
+
+impl MemoryMailer {
+    pub fn sent(&self) -> Vec<Email> {
+        self.sent.lock().expect("mailer lock poisoned").clone()
+    }
+}
+
+#[async_trait]
+impl Mailer for MemoryMailer {
+    async fn send(&self, email: Email) -> Result<()> {
+        self.sent.lock().expect("mailer lock poisoned").push(email);
+        Ok(())
+    }
+}
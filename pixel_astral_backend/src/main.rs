@@ -1,24 +1,38 @@
 mod api;
+mod blob_store;
 mod config;
 mod db;
+mod error;
+mod event_hub;
+mod jobs;
+mod mailer;
 mod service;
 mod sql;
 mod type;
 
-use api::{game_api, user_api};
+use api::{
+    collection_api, event_api, export_api, game_api, import_api, list_api, manga_api, movie_api,
+    novel_api, public_api, recommendation_api, review_api, social_api, stats_api, sync_api, tag_api,
+    user_api, visibility_api,
+};
 use config::Config;
 use db::Database;
+use event_hub::EventHub;
+use service::password_service::PasswordService;
+use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
+use tower_http::services::ServeDir;
 use tower_http::trace::TraceLayer;
-use utoipa::OpenApi;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
 
 use axum::{
     extract::State,
-    http::StatusCode,
+    http::{header, StatusCode},
     response::Json,
     routing::get,
-    Router,
+    Extension, Router,
 };
 use serde_json::json;
 
@@ -28,12 +42,123 @@ use serde_json::json;
         health_check,
         user_api::signup,
         user_api::login,
+        user_api::verify_email,
+        user_api::resend_verification,
+        user_api::forgot_password,
+        user_api::reset_password,
         user_api::get_current_user,
+        user_api::get_me,
+        user_api::change_password,
+        user_api::change_email,
+        user_api::delete_account,
+        user_api::cancel_account_deletion,
+        user_api::get_profile,
+        user_api::update_profile,
         game_api::get_games,
         game_api::create_game,
+        game_api::bulk_games,
         game_api::get_game,
+        game_api::replace_game,
         game_api::update_game,
         game_api::delete_game,
+        game_api::upload_cover,
+        game_api::delete_cover,
+        game_api::get_tags,
+        game_api::set_tags,
+        game_api::get_history,
+        game_api::revert,
+        game_api::get_trash,
+        game_api::restore,
+        game_api::purge,
+        game_api::empty_trash,
+        manga_api::get_manga_list,
+        manga_api::create_manga,
+        manga_api::get_manga,
+        manga_api::update_manga,
+        manga_api::advance_progress,
+        manga_api::delete_manga,
+        manga_api::upload_cover,
+        manga_api::delete_cover,
+        manga_api::get_tags,
+        manga_api::set_tags,
+        manga_api::get_history,
+        manga_api::revert,
+        manga_api::get_trash,
+        manga_api::restore,
+        manga_api::purge,
+        manga_api::empty_trash,
+        novel_api::get_novels,
+        novel_api::create_novel,
+        novel_api::get_novel,
+        novel_api::update_novel,
+        novel_api::advance_progress,
+        novel_api::delete_novel,
+        novel_api::upload_cover,
+        novel_api::delete_cover,
+        novel_api::get_tags,
+        novel_api::set_tags,
+        novel_api::get_history,
+        novel_api::revert,
+        novel_api::get_trash,
+        novel_api::restore,
+        novel_api::purge,
+        novel_api::empty_trash,
+        movie_api::get_movies,
+        movie_api::get_movie,
+        tag_api::get_tags,
+        tag_api::create_tag,
+        tag_api::update_tag,
+        tag_api::delete_tag,
+        list_api::get_lists,
+        list_api::create_list,
+        list_api::get_list,
+        list_api::update_list,
+        list_api::delete_list,
+        list_api::add_list_item,
+        list_api::move_list_item,
+        list_api::remove_list_item,
+        export_api::export_collection,
+        import_api::import_collection,
+        sync_api::pull_changes,
+        sync_api::push_changes,
+        event_api::stream_events,
+        stats_api::get_stats,
+        review_api::get_review,
+        list_api::get_share_links,
+        list_api::create_share_link,
+        list_api::revoke_share_link,
+        visibility_api::get_item_visibilities,
+        visibility_api::set_item_visibility,
+        public_api::get_public_games,
+        public_api::get_shared_list,
+        social_api::get_feed,
+        social_api::get_following,
+        social_api::follow_user,
+        social_api::unfollow_user,
+        social_api::get_followers,
+        social_api::approve_follower,
+        social_api::remove_follower,
+        social_api::get_blocks,
+        social_api::block_user,
+        social_api::unblock_user,
+        collection_api::get_collections,
+        collection_api::create_collection,
+        collection_api::get_received_invitations,
+        collection_api::accept_invitation,
+        collection_api::decline_invitation,
+        collection_api::get_collection,
+        collection_api::update_collection,
+        collection_api::delete_collection,
+        collection_api::get_members,
+        collection_api::set_member_role,
+        collection_api::remove_member,
+        collection_api::get_invitations,
+        collection_api::invite_member,
+        collection_api::revoke_invitation,
+        collection_api::get_games,
+        collection_api::create_game,
+        collection_api::get_trash,
+        recommendation_api::get_recommendations,
     ),
     components(
         schemas(
@@ -42,19 +167,137 @@ use serde_json::json;
             type::user_type::LoginRequest,
             type::user_type::UserResponse,
             type::user_type::AuthResponse,
+            type::user_type::VerifyEmailRequest,
+            type::user_type::EmailRequest,
+            type::user_type::ResetPasswordRequest,
+            type::user_type::ChangePasswordRequest,
+            type::user_type::ChangeEmailRequest,
+            type::user_type::DeleteAccountRequest,
+            type::profile_type::UserProfile,
+            type::profile_type::UpdateProfileRequest,
             type::game_type::Game,
             type::game_type::CreateGameRequest,
             type::game_type::UpdateGameRequest,
+            type::game_type::BulkMode,
+            type::game_type::BulkGameUpdate,
+            type::game_type::BulkGameRequest,
+            type::game_type::BulkOperation,
+            type::game_type::BulkGameResult,
+            type::game_type::BulkGameResponse,
+            type::cover_type::Cover,
+            type::manga_type::Manga,
+            type::manga_type::CreateMangaRequest,
+            type::manga_type::UpdateMangaRequest,
+            type::manga_type::MangaProgressRequest,
+            type::novel_type::Novel,
+            type::novel_type::CreateNovelRequest,
+            type::novel_type::UpdateNovelRequest,
+            type::novel_type::NovelProgressRequest,
+            type::movie_type::Movie,
+            type::item_type::ItemKind,
+            type::history_type::FieldChange,
+            type::history_type::ItemEvent,
+            type::tag_type::Tag,
+            type::tag_type::CreateTagRequest,
+            type::tag_type::UpdateTagRequest,
+            type::tag_type::SetItemTagsRequest,
+            type::list_type::CustomList,
+            type::list_type::ListItem,
+            type::list_type::ListWithItems,
+            type::list_type::CreateListRequest,
+            type::list_type::UpdateListRequest,
+            type::list_type::AddListItemRequest,
+            type::list_type::MoveListItemRequest,
+            type::tag_type::ItemTagLink,
+            type::export_type::ExportFormat,
+            type::import_type::ImportSource,
+            type::import_type::DuplicateStrategy,
+            type::import_type::ImportAction,
+            type::import_type::ImportRowResult,
+            type::import_type::ImportResponse,
+            type::sync_type::SyncDeletion,
+            type::sync_type::SyncResponse,
+            type::sync_type::GameMutation,
+            type::sync_type::SyncPushRequest,
+            type::sync_type::SyncStatus,
+            type::sync_type::SyncMutationResult,
+            type::sync_type::SyncPushResponse,
+            type::event_type::ItemNotification,
+            type::stats_type::CollectionStats,
+            type::stats_type::StatusCount,
+            type::stats_type::GenreCount,
+            type::stats_type::RatingCount,
+            type::stats_type::RatingStats,
+            type::stats_type::PeriodCount,
+            type::stats_type::StreakStats,
+            type::review_type::YearInReview,
+            type::review_type::ReviewItem,
+            type::review_type::BacklogItem,
+            type::review_type::MonthActivity,
+            type::review_type::ReviewFormat,
+            type::public_type::ItemVisibility,
+            type::public_type::SetItemVisibilityRequest,
+            type::public_type::PublicProfile,
+            type::public_type::PublicGame,
+            type::public_type::PublicCollection,
+            type::public_type::PublicItem,
+            type::public_type::SharedList,
+            type::public_type::ShareLink,
+            type::public_type::CreatedShareLink,
+            type::social_type::Follow,
+            type::social_type::FollowUser,
+            type::social_type::BlockedUser,
+            type::social_type::Activity,
+            type::social_type::FeedPage,
+            type::collection_type::SharedCollection,
+            type::collection_type::CollectionMember,
+            type::collection_type::CollectionInvitation,
+            type::collection_type::CreateCollectionRequest,
+            type::collection_type::UpdateCollectionRequest,
+            type::collection_type::InviteMemberRequest,
+            type::collection_type::SetMemberRoleRequest,
+            type::recommendation_type::Recommendation,
         )
     ),
     tags(
         (name = "health", description = "Health check endpoints"),
         (name = "auth", description = "Authentication endpoints"),
+        (name = "account", description = "Account management endpoints"),
+        (name = "profile", description = "User profile endpoints"),
         (name = "games", description = "Game collection endpoints"),
-    )
+        (name = "manga", description = "Manga collection endpoints"),
+        (name = "novels", description = "Novel collection endpoints"),
+        (name = "movies", description = "Movie collection endpoints (read-only; movies come from imports)"),
+        (name = "tags", description = "User tag endpoints"),
+        (name = "lists", description = "Custom list endpoints"),
+        (name = "export", description = "Collection export endpoints"),
+        (name = "import", description = "Collection import endpoints"),
+        (name = "sync", description = "Offline sync endpoints"),
+        (name = "events", description = "Real-time item event stream"),
+        (name = "stats", description = "Collection statistics endpoints"),
+        (name = "review", description = "Year-in-review endpoints"),
+        (name = "visibility", description = "Per-item visibility endpoints"),
+        (name = "public", description = "Public profiles and shared lists, no authentication"),
+        (name = "social", description = "Follows, blocks and activity feed endpoints"),
+        (name = "collections", description = "Shared collection, member and invitation endpoints"),
+        (name = "recommendations", description = "What to play next from the Plan to Play backlog"),
+    ),
+    modifiers(&SecurityAddon)
 )]
 struct ApiDoc;
 
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init();
@@ -68,17 +311,61 @@ async fn main() -> anyhow::Result<()> {
     database.test_connection().await?;
     tracing::info!("Database connection test passed");
 
+    let mailer = mailer::from_config(&config)?;
+    tracing::info!("Mailer backend: {}", config.mailer);
+
+    let passwords = Arc::new(PasswordService::from_config(&config)?);
+    tracing::info!("Password hashing: {}", config.password_hash_algorithm);
+
+    let blob_store = blob_store::from_config(&config)?;
+    tracing::info!("Blob backend: {}", config.blob_backend);
+
+    jobs::spawn_account_purge(database.pool.clone(), blob_store.clone());
+    jobs::spawn_trash_purge(database.pool.clone(), blob_store.clone(), config.trash_retention_days);
+    jobs::spawn_recommendation_refresh(database.pool.clone());
+
+    let events = EventHub::new();
+    jobs::spawn_event_listener(database.pool.clone(), events.clone());
+
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
-        .allow_headers(Any);
+        .allow_headers(Any)
+        .expose_headers([header::ETAG]);
 
-    let app = Router::new()
+    let mut app = Router::new()
         .route("/health", get(health_check))
         .route("/api/health", get(health_check))
         .nest("/api/users", user_api::routes())
         .nest("/api/users/:user_id/games", game_api::routes())
-        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .nest("/api/users/:user_id/manga", manga_api::routes())
+        .nest("/api/users/:user_id/novels", novel_api::routes())
+        .nest("/api/users/:user_id/movies", movie_api::routes())
+        .nest("/api/users/:user_id/tags", tag_api::routes())
+        .nest("/api/users/:user_id/lists", list_api::routes())
+        .nest("/api/users/:user_id/export", export_api::routes())
+        .nest("/api/users/:user_id/import", import_api::routes())
+        .nest("/api/users/:user_id/sync", sync_api::routes())
+        .nest("/api/users/:user_id/events", event_api::routes())
+        .nest("/api/users/:user_id/stats", stats_api::routes())
+        .nest("/api/users/:user_id/review", review_api::routes())
+        .nest("/api/users/:user_id/visibility", visibility_api::routes())
+        .nest("/api/users/:user_id/recommendations", recommendation_api::routes())
+        .nest("/api/public", public_api::routes())
+        .nest("/api/social", social_api::routes())
+        .nest("/api/collections", collection_api::routes())
+        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()));
+
+    if config.blob_backend == "local" {
+        app = app.nest_service("/media", ServeDir::new(&config.blob_local_dir));
+    }
+
+    let app = app
+        .layer(Extension(blob_store))
+        .layer(Extension(mailer))
+        .layer(Extension(passwords))
+        .layer(Extension(events))
+        .layer(Extension(config.clone()))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(database.pool);
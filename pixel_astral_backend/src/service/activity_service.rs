@@ -0,0 +1,101 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::sql::activity_sql;
+use crate::type::history_type::{ItemAction, NewItemEvent};
+use crate::type::social_type::{
+    ActivityVerb, FeedPage, FeedQuery, NewActivity, DEFAULT_FEED_LIMIT, MAX_FEED_LIMIT,
+};
+use sqlx::PgPool;
+
+pub struct ActivityService;
+
+impl ActivityService {
+    /// The feed entry a history event implies, if any: completing, starting
+    /// or dropping an item, adding one, or rating it. Trash moves and plain
+    /// edits are not shown.
+    pub fn derive(event: &NewItemEvent, event_id: Uuid) -> Option<NewActivity> {
+        let change = |field: &str| event.changes.iter().find(|change| change.field == field);
+        let status = change("status").and_then(|change| change.new.as_str());
+        let rated = change("rating").is_some_and(|change| !change.new.is_null());
+
+        let verb = match (event.action, status) {
+            (ItemAction::Delete | ItemAction::Restore, _) => return None,
+            (_, Some("Completed")) => ActivityVerb::Completed,
+            (ItemAction::Create, _) => ActivityVerb::Added,
+            (_, Some("Playing" | "Reading")) => ActivityVerb::Started,
+            (_, Some("Dropped")) => ActivityVerb::Dropped,
+            _ if rated => ActivityVerb::Rated,
+            _ => return None,
+        };
+
+        Some(NewActivity {
+            user_id: event.user_id,
+            event_id,
+            item_type: event.item_type,
+            item_id: event.item_id,
+            verb,
+            title: event.snapshot.get("title")?.as_str()?.to_string(),
+            rating: event
+                .snapshot
+                .get("rating")
+                .and_then(Value::as_i64)
+                .and_then(|rating| i32::try_from(rating).ok()),
+        })
+    }
+
+    /// One page of the feed, newest first. Pages are keyed on the last
+    /// entry's `(created_at, id)`, so entries written meanwhile neither shift
+    /// nor repeat later pages.
+    pub async fn get_feed(pool: &PgPool, user_id: Uuid, query: FeedQuery) -> Result<FeedPage> {
+        let limit = query.limit.unwrap_or(DEFAULT_FEED_LIMIT);
+        if !(1..=MAX_FEED_LIMIT).contains(&limit) {
+            return Err(AppError::Validation("Limit must be between 1 and 100".to_string()).into());
+        }
+
+        let before = query
+            .cursor
+            .as_deref()
+            .map(Self::decode_cursor)
+            .transpose()?;
+
+        let mut activities = activity_sql::find_feed(pool, user_id, before, limit + 1)
+            .await
+            .context("Failed to get feed")?;
+
+        let next_cursor = if activities.len() as i64 > limit {
+            activities.truncate(limit as usize);
+            activities
+                .last()
+                .map(|last| Self::encode_cursor(last.created_at, last.id))
+        } else {
+            None
+        };
+
+        Ok(FeedPage {
+            activities,
+            next_cursor,
+        })
+    }
+
+    fn encode_cursor(created_at: DateTime<Utc>, id: Uuid) -> String {
+        format!("{}_{}", created_at.timestamp_micros(), id)
+    }
+
+    fn decode_cursor(cursor: &str) -> Result<(DateTime<Utc>, Uuid)> {
+        let invalid = || AppError::Validation("Invalid feed cursor".to_string());
+
+        let (micros, id) = cursor.split_once('_').ok_or_else(invalid)?;
+        let created_at = micros
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or_else(invalid)?;
+        let id = id.parse().map_err(|_| invalid())?;
+
+        Ok((created_at, id))
+    }
+}
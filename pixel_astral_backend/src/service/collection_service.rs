@@ -0,0 +1,355 @@
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::mailer::{Email, Mailer};
+use crate::service::user_service::UserService;
+use crate::sql::{collection_sql, profile_sql, social_sql};
+use crate::type::collection_type::{
+    CollectionInvitation, CollectionMember, CollectionRole, CreateCollectionRequest, InviteMemberRequest,
+    SetMemberRoleRequest, SharedCollection, UpdateCollectionRequest, MAX_COLLECTION_NAME_LENGTH,
+};
+use sqlx::{PgExecutor, PgPool};
+
+pub struct CollectionService;
+
+impl CollectionService {
+    pub async fn create(
+        pool: &PgPool,
+        user_id: Uuid,
+        request: CreateCollectionRequest,
+    ) -> Result<SharedCollection> {
+        let name = Self::validate_name(&request.name)?;
+
+        collection_sql::create_collection(pool, user_id, &name)
+            .await
+            .context("Failed to create collection")
+    }
+
+    pub async fn get_all_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<SharedCollection>> {
+        collection_sql::find_collections_by_user(pool, user_id)
+            .await
+            .context("Failed to get collections")
+    }
+
+    pub async fn get_by_id(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<Option<SharedCollection>> {
+        collection_sql::find_collection(pool, id, user_id)
+            .await
+            .context("Failed to get collection")
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        request: UpdateCollectionRequest,
+    ) -> Result<Option<SharedCollection>> {
+        let name = Self::validate_name(&request.name)?;
+
+        if !Self::authorize(pool, id, user_id, CollectionRole::Owner).await? {
+            return Ok(None);
+        }
+
+        collection_sql::update_collection(pool, id, &name)
+            .await
+            .context("Failed to update collection")?;
+
+        Self::get_by_id(pool, id, user_id).await
+    }
+
+    /// Deletes the collection and every game in it.
+    pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<bool> {
+        if !Self::authorize(pool, id, user_id, CollectionRole::Owner).await? {
+            return Ok(false);
+        }
+
+        collection_sql::delete_collection(pool, id)
+            .await
+            .context("Failed to delete collection")
+    }
+
+    /// Whether `user_id` may act on the collection with at least `role`.
+    /// Non-members get `false`, so callers can answer 404 and not reveal
+    /// the collection; members with a lesser role get `Forbidden`.
+    pub async fn authorize<'e>(
+        executor: impl PgExecutor<'e>,
+        collection_id: Uuid,
+        user_id: Uuid,
+        role: CollectionRole,
+    ) -> Result<bool> {
+        let Some(member_role) = collection_sql::find_member_role(executor, collection_id, user_id)
+            .await
+            .context("Failed to get collection role")?
+        else {
+            return Ok(false);
+        };
+
+        let member_role = CollectionRole::parse(&member_role).context("Unknown collection role")?;
+        if member_role < role {
+            return Err(AppError::Forbidden(format!(
+                "This needs the {} role in the collection",
+                role.as_str()
+            ))
+            .into());
+        }
+
+        Ok(true)
+    }
+
+    pub async fn get_members(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Vec<CollectionMember>>> {
+        if !Self::authorize(pool, id, user_id, CollectionRole::Viewer).await? {
+            return Ok(None);
+        }
+
+        collection_sql::find_members(pool, id)
+            .await
+            .map(Some)
+            .context("Failed to get collection members")
+    }
+
+    /// Changes a member's role. The last owner cannot step down.
+    pub async fn set_member_role(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        member_id: Uuid,
+        request: SetMemberRoleRequest,
+    ) -> Result<bool> {
+        let role = Self::parse_role(&request.role)?;
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        if !Self::authorize(&mut *tx, id, user_id, CollectionRole::Owner).await? {
+            return Ok(false);
+        }
+
+        let owners = collection_sql::find_owners_for_update(&mut *tx, id)
+            .await
+            .context("Failed to get collection owners")?;
+        if role != CollectionRole::Owner && owners == [member_id] {
+            return Err(AppError::Conflict("A collection needs at least one owner".to_string()).into());
+        }
+
+        let updated = collection_sql::update_member_role(&mut *tx, id, member_id, role.as_str())
+            .await
+            .context("Failed to update member role")?;
+        tx.commit().await.context("Failed to commit member role")?;
+
+        Ok(updated)
+    }
+
+    /// Removes a member; owners can remove anyone and every member can
+    /// leave. The last owner cannot leave while others remain.
+    pub async fn remove_member(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        member_id: Uuid,
+    ) -> Result<bool> {
+        let role = if member_id == user_id { CollectionRole::Viewer } else { CollectionRole::Owner };
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        if !Self::authorize(&mut *tx, id, user_id, role).await? {
+            return Ok(false);
+        }
+
+        let owners = collection_sql::find_owners_for_update(&mut *tx, id)
+            .await
+            .context("Failed to get collection owners")?;
+        if owners == [member_id] {
+            return Err(AppError::Conflict(
+                "Make someone else an owner, or delete the collection".to_string(),
+            )
+            .into());
+        }
+
+        let removed = collection_sql::delete_member(&mut *tx, id, member_id)
+            .await
+            .context("Failed to remove member")?;
+        tx.commit().await.context("Failed to commit member removal")?;
+
+        Ok(removed)
+    }
+
+    /// Invites someone by email or handle. Email invitations are also sent
+    /// by mail, pointing at sign-up for people without an account.
+    pub async fn invite(
+        pool: &PgPool,
+        config: &Config,
+        mailer: Arc<dyn Mailer>,
+        id: Uuid,
+        user_id: Uuid,
+        request: InviteMemberRequest,
+    ) -> Result<Option<CollectionInvitation>> {
+        let role = match request.role.as_deref() {
+            Some(role) => Self::parse_role(role)?,
+            None => CollectionRole::Editor,
+        };
+
+        let (email, invitee_id) = match (request.email.as_deref(), request.handle.as_deref()) {
+            (Some(email), None) => (Some(UserService::normalize_email(email)?), None),
+            (None, Some(handle)) => (None, Some(Self::find_invitee(pool, user_id, handle).await?)),
+            _ => {
+                return Err(AppError::Validation("Send either an email or a handle".to_string()).into());
+            }
+        };
+
+        if !Self::authorize(pool, id, user_id, CollectionRole::Owner).await? {
+            return Ok(None);
+        }
+
+        if let Some(invitee_id) = invitee_id {
+            let member = collection_sql::find_member_role(pool, id, invitee_id)
+                .await
+                .context("Failed to get collection role")?;
+            if member.is_some() {
+                return Err(AppError::Conflict("Already a member".to_string()).into());
+            }
+        }
+
+        let invitation = collection_sql::create_invitation(pool, id, user_id, email.as_deref(), invitee_id, role.as_str())
+            .await
+            .map_err(|err| match &err {
+                sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                    AppError::Conflict("Already invited".to_string()).into()
+                }
+                _ => anyhow::Error::new(err).context("Failed to create invitation"),
+            })?;
+
+        if let Some(email) = email {
+            let email = Email {
+                to: email,
+                subject: format!("You're invited to \"{}\" on PixelAstral", invitation.collection_name),
+                body: format!(
+                    "You've been invited to join the shared collection \"{}\" on PixelAstral.\n\n\
+                     Sign in or create an account with this address to accept:\n{}/collections/invitations",
+                    invitation.collection_name, config.frontend_url
+                ),
+            };
+            tokio::spawn(async move {
+                if let Err(err) = mailer.send(email).await {
+                    tracing::warn!("Failed to send invitation email: {:#}", err);
+                }
+            });
+        }
+
+        Ok(Some(invitation))
+    }
+
+    pub async fn get_invitations(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Vec<CollectionInvitation>>> {
+        if !Self::authorize(pool, id, user_id, CollectionRole::Owner).await? {
+            return Ok(None);
+        }
+
+        collection_sql::find_invitations_by_collection(pool, id)
+            .await
+            .map(Some)
+            .context("Failed to get invitations")
+    }
+
+    pub async fn revoke_invitation(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        invitation_id: Uuid,
+    ) -> Result<bool> {
+        if !Self::authorize(pool, id, user_id, CollectionRole::Owner).await? {
+            return Ok(false);
+        }
+
+        collection_sql::delete_invitation(pool, invitation_id, id)
+            .await
+            .context("Failed to revoke invitation")
+    }
+
+    /// Invitations waiting for the caller, by handle or verified email.
+    pub async fn get_received_invitations(pool: &PgPool, user_id: Uuid) -> Result<Vec<CollectionInvitation>> {
+        collection_sql::find_invitations_for_user(pool, user_id)
+            .await
+            .context("Failed to get invitations")
+    }
+
+    /// Joins the collection with the invited role. Members who are already
+    /// in it keep their role.
+    pub async fn accept_invitation(
+        pool: &PgPool,
+        user_id: Uuid,
+        invitation_id: Uuid,
+    ) -> Result<Option<SharedCollection>> {
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(invitation) = collection_sql::take_invitation_for_user(&mut *tx, invitation_id, user_id)
+            .await
+            .context("Failed to get invitation")?
+        else {
+            return Ok(None);
+        };
+
+        collection_sql::create_member(&mut *tx, invitation.collection_id, user_id, &invitation.role)
+            .await
+            .context("Failed to add member")?;
+        tx.commit().await.context("Failed to commit invitation")?;
+
+        Self::get_by_id(pool, invitation.collection_id, user_id).await
+    }
+
+    pub async fn decline_invitation(pool: &PgPool, user_id: Uuid, invitation_id: Uuid) -> Result<bool> {
+        collection_sql::take_invitation_for_user(pool, invitation_id, user_id)
+            .await
+            .map(|invitation| invitation.is_some())
+            .context("Failed to decline invitation")
+    }
+
+    /// Users who blocked the inviter, or were blocked, look like they do
+    /// not exist.
+    async fn find_invitee(pool: &PgPool, user_id: Uuid, handle: &str) -> Result<Uuid> {
+        let profile = profile_sql::find_profile_by_handle(pool, handle.trim())
+            .await
+            .context("Failed to find profile")?
+            .ok_or(AppError::NotFound)?;
+
+        if profile.user_id == user_id {
+            return Err(AppError::Validation("You cannot invite yourself".to_string()).into());
+        }
+
+        if social_sql::is_blocked_between(pool, user_id, profile.user_id)
+            .await
+            .context("Failed to check blocks")?
+        {
+            return Err(AppError::NotFound.into());
+        }
+
+        Ok(profile.user_id)
+    }
+
+    fn parse_role(role: &str) -> Result<CollectionRole> {
+        CollectionRole::parse(role)
+            .ok_or_else(|| AppError::Validation("Role must be owner, editor or viewer".to_string()).into())
+    }
+
+    fn validate_name(name: &str) -> Result<String> {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err(AppError::Validation("Collection name is required".to_string()).into());
+        }
+
+        if name.chars().count() > MAX_COLLECTION_NAME_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Collection name must be at most {} characters",
+                MAX_COLLECTION_NAME_LENGTH
+            ))
+            .into());
+        }
+
+        Ok(name.to_string())
+    }
+}
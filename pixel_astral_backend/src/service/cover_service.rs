@@ -0,0 +1,194 @@
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use image::{imageops::FilterType, DynamicImage, ImageFormat, ImageReader, Limits};
+use std::io::Cursor;
+use uuid::Uuid;
+
+use crate::blob_store::BlobStore;
+use crate::error::AppError;
+use crate::service::game_service::GameService;
+use crate::service::manga_service::MangaService;
+use crate::service::novel_service::NovelService;
+use crate::sql::item_sql;
+use crate::type::cover_type::{
+    Cover, COVER_MEDIUM_SIZE, COVER_SMALL_SIZE, MAX_COVER_BYTES, MAX_COVER_DIMENSION,
+};
+use crate::type::item_type::ItemKind;
+use sqlx::PgPool;
+
+pub struct CoverService;
+
+impl CoverService {
+    /// Stores an uploaded cover with medium and small JPEG thumbnails and
+    /// points the item at them, then removes the previous cover's files.
+    /// Nothing is decoded or stored unless the caller may edit the item. The
+    /// swap locks the item and is recorded in its history, so of two
+    /// concurrent uploads the later one deletes the earlier one's files.
+    ///
+    /// The format is sniffed from the bytes; the client's MIME type is
+    /// only used to reject obviously wrong uploads early.
+    pub async fn upload(
+        pool: &PgPool,
+        store: &dyn BlobStore,
+        kind: ItemKind,
+        id: Uuid,
+        user_id: Uuid,
+        bytes: Bytes,
+        content_type: Option<&str>,
+    ) -> Result<Option<Cover>> {
+        if bytes.len() > MAX_COVER_BYTES {
+            return Err(AppError::PayloadTooLarge.into());
+        }
+
+        if content_type.is_some_and(|content_type| !content_type.starts_with("image/")) {
+            return Err(AppError::UnsupportedMediaType.into());
+        }
+
+        let format = image::guess_format(&bytes).map_err(|_| AppError::UnsupportedMediaType)?;
+        let (extension, mime) = match format {
+            ImageFormat::Jpeg => ("jpg", "image/jpeg"),
+            ImageFormat::Png => ("png", "image/png"),
+            ImageFormat::WebP => ("webp", "image/webp"),
+            ImageFormat::Gif => ("gif", "image/gif"),
+            _ => return Err(AppError::UnsupportedMediaType.into()),
+        };
+
+        if !Self::can_edit(pool, kind, id, user_id).await? {
+            return Ok(None);
+        }
+
+        let decode_bytes = bytes.clone();
+        let (medium, small) = tokio::task::spawn_blocking(move || Self::thumbnails(decode_bytes, format))
+            .await
+            .context("Thumbnail task failed")??;
+
+        let prefix = format!("covers/{}/{}/{}/{}", user_id, kind.as_str(), id, Uuid::new_v4());
+        let original_key = format!("{}-original.{}", prefix, extension);
+        let medium_key = format!("{}-medium.jpg", prefix);
+        let small_key = format!("{}-small.jpg", prefix);
+
+        store.put(&original_key, bytes, mime).await?;
+        store.put(&medium_key, medium, "image/jpeg").await?;
+        store.put(&small_key, small, "image/jpeg").await?;
+
+        let cover = Cover {
+            cover_url: Some(store.url(&original_key)),
+            cover_medium_url: Some(store.url(&medium_key)),
+            cover_small_url: Some(store.url(&small_key)),
+        };
+
+        match Self::swap(pool, kind, id, user_id, &cover).await {
+            Ok(Some(previous)) => {
+                Self::delete_blobs(store, &previous).await;
+                Ok(Some(cover))
+            }
+            Ok(None) => {
+                Self::delete_blobs(store, &cover).await;
+                Ok(None)
+            }
+            Err(err) => {
+                Self::delete_blobs(store, &cover).await;
+                Err(err)
+            }
+        }
+    }
+
+    /// Clears the item's cover. Returns `false` if the item does not exist.
+    pub async fn delete(
+        pool: &PgPool,
+        store: &dyn BlobStore,
+        kind: ItemKind,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool> {
+        let Some(previous) = Self::swap(pool, kind, id, user_id, &Cover::default()).await? else {
+            return Ok(false);
+        };
+
+        Self::delete_blobs(store, &previous).await;
+
+        Ok(true)
+    }
+
+    /// Whether the item exists outside the trash and `user_id` may change
+    /// it. [`Self::swap`] checks again under a row lock.
+    async fn can_edit(pool: &PgPool, kind: ItemKind, id: Uuid, user_id: Uuid) -> Result<bool> {
+        match kind {
+            ItemKind::Game => GameService::can_edit(pool, id, user_id).await,
+            ItemKind::Manga | ItemKind::Novel => item_sql::item_exists(pool, kind, id, user_id)
+                .await
+                .context("Failed to find item"),
+        }
+    }
+
+    /// Replaces the item's cover in its own transaction and returns the
+    /// previous one, or `None` if the item does not exist. Blobs are only
+    /// deleted after the commit, so a rollback never leaves the item
+    /// pointing at missing files.
+    async fn swap(pool: &PgPool, kind: ItemKind, id: Uuid, user_id: Uuid, cover: &Cover) -> Result<Option<Cover>> {
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let previous = match kind {
+            ItemKind::Game => GameService::set_cover(&mut tx, id, user_id, cover).await?,
+            ItemKind::Manga => MangaService::set_cover(&mut tx, id, user_id, cover).await?,
+            ItemKind::Novel => NovelService::set_cover(&mut tx, id, user_id, cover).await?,
+        };
+        tx.commit().await.context("Failed to commit cover")?;
+
+        Ok(previous)
+    }
+
+    /// Removes the files of covers whose items were deleted for good.
+    pub async fn delete_files(store: &dyn BlobStore, covers: &[Cover]) {
+        for cover in covers {
+            Self::delete_blobs(store, cover).await;
+        }
+    }
+
+    fn thumbnails(bytes: Bytes, format: ImageFormat) -> Result<(Bytes, Bytes)> {
+        let mut limits = Limits::default();
+        limits.max_image_width = Some(MAX_COVER_DIMENSION);
+        limits.max_image_height = Some(MAX_COVER_DIMENSION);
+
+        let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+        reader.limits(limits);
+
+        let image = reader
+            .decode()
+            .map_err(|_| AppError::Validation("Image could not be decoded".to_string()))?;
+
+        Ok((
+            Self::encode_thumbnail(&image, COVER_MEDIUM_SIZE)?,
+            Self::encode_thumbnail(&image, COVER_SMALL_SIZE)?,
+        ))
+    }
+
+    fn encode_thumbnail(image: &DynamicImage, size: u32) -> Result<Bytes> {
+        let thumbnail = if image.width() > size || image.height() > size {
+            image.resize(size, size, FilterType::Lanczos3)
+        } else {
+            image.clone()
+        };
+
+        let mut buffer = Cursor::new(Vec::new());
+        DynamicImage::ImageRgb8(thumbnail.to_rgb8())
+            .write_to(&mut buffer, ImageFormat::Jpeg)
+            .context("Failed to encode thumbnail")?;
+
+        Ok(Bytes::from(buffer.into_inner()))
+    }
+
+    /// Best effort: a leftover file is harmless, so failures are only logged.
+    async fn delete_blobs(store: &dyn BlobStore, cover: &Cover) {
+        let urls = [&cover.cover_url, &cover.cover_medium_url, &cover.cover_small_url];
+
+        for url in urls.into_iter().flatten() {
+            let Some(key) = store.key_from_url(url) else {
+                continue;
+            };
+
+            if let Err(err) = store.delete(&key).await {
+                tracing::warn!("Failed to delete cover blob {}: {:#}", key, err);
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use anyhow::{Context, Result};
+use futures::future;
+use futures::stream::{self, BoxStream, StreamExt};
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use crate::event_hub::EventHub;
+use crate::sql::history_sql;
+use crate::type::event_type::{
+    HubMessage, ItemNotification, StreamEvent, ITEM_EVENTS_CHANNEL, MAX_REPLAY_EVENTS,
+};
+
+pub struct EventService;
+
+impl EventService {
+    /// Forwards every notification on the item events channel to the hub.
+    /// Only returns on error; the caller reconnects.
+    pub async fn listen(pool: &PgPool, hub: &EventHub) -> Result<()> {
+        let mut listener = PgListener::connect_with(pool)
+            .await
+            .context("Failed to connect event listener")?;
+        listener
+            .listen(ITEM_EVENTS_CHANNEL)
+            .await
+            .context("Failed to listen for item events")?;
+
+        loop {
+            let Some(notification) = listener
+                .try_recv()
+                .await
+                .context("Failed to receive item event")?
+            else {
+                // The connection dropped; the next `try_recv` reconnects, but
+                // anything sent in between is lost.
+                hub.publish(HubMessage::Interrupted);
+                continue;
+            };
+
+            match serde_json::from_str::<ItemNotification>(notification.payload()) {
+                Ok(event) => hub.publish(HubMessage::Event(event)),
+                Err(err) => tracing::warn!("Ignoring malformed item event: {}", err),
+            }
+        }
+    }
+
+    /// The user's events from now on, preceded by the ones after
+    /// `last_event_id` when resuming. The stream ends when this instance may
+    /// have missed events, so the client reconnects and replays them.
+    pub async fn subscribe(
+        pool: &PgPool,
+        hub: &EventHub,
+        user_id: Uuid,
+        last_event_id: Option<Uuid>,
+    ) -> Result<BoxStream<'static, StreamEvent>> {
+        // Subscribe before replaying so nothing committed in between is lost;
+        // events seen in both are dropped from the live part.
+        let receiver = hub.subscribe();
+
+        let replayed = match last_event_id {
+            Some(last_event_id) => Self::replay(pool, user_id, last_event_id).await?,
+            None => Some(Vec::new()),
+        };
+
+        // After a reset the client refetches and keeps listening to the live
+        // part of this stream.
+        let (head, seen): (Vec<StreamEvent>, HashSet<Uuid>) = match replayed {
+            Some(events) => (
+                events.iter().cloned().map(StreamEvent::Item).collect(),
+                events.iter().map(|event| event.id).collect(),
+            ),
+            None => (vec![StreamEvent::Reset], HashSet::new()),
+        };
+
+        let live = stream::unfold(receiver, move |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(HubMessage::Event(event)) if event.user_id == user_id => {
+                        return Some((event, receiver));
+                    }
+                    Ok(HubMessage::Event(_)) => continue,
+                    Ok(HubMessage::Interrupted)
+                    | Err(RecvError::Lagged(_))
+                    | Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .filter(move |event| future::ready(!seen.contains(&event.id)))
+        .map(StreamEvent::Item);
+
+        Ok(stream::iter(head).chain(live)
+            .boxed())
+    }
+
+    /// `None` when `last_event_id` is unknown (e.g. its item was purged) or
+    /// more than `MAX_REPLAY_EVENTS` behind.
+    async fn replay(
+        pool: &PgPool,
+        user_id: Uuid,
+        last_event_id: Uuid,
+    ) -> Result<Option<Vec<ItemNotification>>> {
+        let events = history_sql::find_item_notifications_after(
+            pool,
+            user_id,
+            last_event_id,
+            MAX_REPLAY_EVENTS + 1,
+        )
+        .await
+        .context("Failed to replay item events")?;
+
+        if events.len() as i64 > MAX_REPLAY_EVENTS {
+            return Ok(None);
+        }
+
+        if events.is_empty()
+            && !history_sql::item_event_exists(pool, last_event_id, user_id)
+                .await
+                .context("Failed to find item event")?
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(events))
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::{bail, Context, Result};
+use bytes::Bytes;
+use chrono::Utc;
+use futures::stream::{BoxStream, StreamExt};
+use serde::Serialize;
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::sql::{game_sql, manga_sql, movie_sql, novel_sql, profile_sql, tag_sql, user_sql};
+use crate::type::export_type::{ExportCsvRow, ExportFormat, EXPORT_CSV_COLUMNS, EXPORT_TAG_SEPARATOR, EXPORT_VERSION};
+use crate::type::profile_type::UserProfile;
+use crate::type::tag_type::{ItemTagLink, Tag};
+use sqlx::PgPool;
+
+/// Chunks buffered between the database cursor and a slow client.
+const EXPORT_CHANNEL_CAPACITY: usize = 32;
+
+pub type ExportStream = ReceiverStream<Result<Bytes>>;
+
+type Sender = mpsc::Sender<Result<Bytes>>;
+
+pub struct ExportService;
+
+impl ExportService {
+    /// Streams the user's whole collection in the requested format.
+    ///
+    /// The profile and tags are loaded before the response starts, so a
+    /// missing user is still a `404`. Items are then written from sqlx
+    /// cursors as they arrive; an error part-way through ends the stream
+    /// early, which the client sees as a truncated download.
+    pub async fn export(
+        pool: &PgPool,
+        user_id: Uuid,
+        format: ExportFormat,
+    ) -> Result<ExportStream> {
+        user_sql::find_user_by_id(pool, user_id)
+            .await
+            .context("Failed to get user")?
+            .ok_or(AppError::NotFound)?;
+
+        let profile = profile_sql::find_profile_by_user(pool, user_id)
+            .await
+            .context("Failed to get profile")?;
+        let tags = tag_sql::find_all_tags_by_user(pool, user_id)
+            .await
+            .context("Failed to get tags")?;
+        let links = tag_sql::find_item_tag_links_by_user(pool, user_id)
+            .await
+            .context("Failed to get item tags")?;
+
+        let (tx, rx) = mpsc::channel(EXPORT_CHANNEL_CAPACITY);
+        let pool = pool.clone();
+
+        tokio::spawn(async move {
+            let result = match format {
+                ExportFormat::Json => Self::write_json(&pool, user_id, profile.as_ref(), &tags, &links, &tx).await,
+                ExportFormat::Csv => Self::write_csv(&pool, user_id, &tags, &links, &tx).await,
+            };
+
+            if let Err(err) = result {
+                if !tx.is_closed() {
+                    tracing::error!("Export for {} failed: {:#}", user_id, err);
+                    let _ = tx.send(Err(err)).await;
+                }
+            }
+        });
+
+        Ok(ReceiverStream::new(rx))
+    }
+
+    /// `{"version":..,"exported_at":..,"profile":..,"tags":[..],"item_tags":[..],"games":[..],"manga":[..],"novels":[..],"movies":[..]}`
+    ///
+    /// `profile` is `null` for users who never set one up.
+    async fn write_json(
+        pool: &PgPool,
+        user_id: Uuid,
+        profile: Option<&UserProfile>,
+        tags: &[Tag],
+        links: &[ItemTagLink],
+        tx: &Sender,
+    ) -> Result<()> {
+        let mut head = format!("{{\"version\":{},\"exported_at\":", EXPORT_VERSION);
+        head.push_str(&serde_json::to_string(&Utc::now())?);
+        head.push_str(",\"profile\":");
+        head.push_str(&serde_json::to_string(profile)?);
+        head.push_str(",\"tags\":");
+        head.push_str(&serde_json::to_string(tags)?);
+        head.push_str(",\"item_tags\":");
+        head.push_str(&serde_json::to_string(links)?);
+        Self::send(tx, head.into_bytes()).await?;
+
+        Self::write_json_array(tx, "games", game_sql::stream_games_by_user(pool, user_id)).await?;
+        Self::write_json_array(tx, "manga", manga_sql::stream_manga_by_user(pool, user_id)).await?;
+        Self::write_json_array(tx, "novels", novel_sql::stream_novels_by_user(pool, user_id)).await?;
+        Self::write_json_array(tx, "movies", movie_sql::stream_movies_by_user(pool, user_id)).await?;
+
+        Self::send(tx, b"}".to_vec()).await
+    }
+
+    async fn write_json_array<T: Serialize>(
+        tx: &Sender,
+        name: &str,
+        mut rows: BoxStream<'_, Result<T, sqlx::Error>>,
+    ) -> Result<()> {
+        Self::send(tx, format!(",\"{}\":[", name).into_bytes()).await?;
+
+        let mut first = true;
+        while let Some(row) = rows.next().await {
+            let row = row.with_context(|| format!("Failed to read {}", name))?;
+
+            let mut chunk = if first { Vec::new() } else { b",".to_vec() };
+            serde_json::to_writer(&mut chunk, &row)?;
+            Self::send(tx, chunk).await?;
+            first = false;
+        }
+
+        Self::send(tx, b"]".to_vec()).await
+    }
+
+    async fn write_csv(
+        pool: &PgPool,
+        user_id: Uuid,
+        tags: &[Tag],
+        links: &[ItemTagLink],
+        tx: &Sender,
+    ) -> Result<()> {
+        let tag_names: HashMap<Uuid, &str> = tags.iter().map(|tag| (tag.id, tag.name.as_str())).collect();
+        let mut item_tags: HashMap<Uuid, Vec<&str>> = HashMap::new();
+        for link in links {
+            if let Some(name) = tag_names.get(&link.tag_id) {
+                item_tags.entry(link.item_id).or_default().push(*name);
+            }
+        }
+
+        let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(Vec::new());
+        writer.write_record(EXPORT_CSV_COLUMNS)?;
+        Self::flush_csv(&mut writer, tx).await?;
+
+        Self::write_csv_rows(&mut writer, tx, &item_tags, game_sql::stream_games_by_user(pool, user_id)).await?;
+        Self::write_csv_rows(&mut writer, tx, &item_tags, manga_sql::stream_manga_by_user(pool, user_id)).await?;
+        Self::write_csv_rows(&mut writer, tx, &item_tags, novel_sql::stream_novels_by_user(pool, user_id)).await?;
+        Self::write_csv_rows(&mut writer, tx, &item_tags, movie_sql::stream_movies_by_user(pool, user_id)).await
+    }
+
+    async fn write_csv_rows<T: Into<ExportCsvRow>>(
+        writer: &mut csv::Writer<Vec<u8>>,
+        tx: &Sender,
+        item_tags: &HashMap<Uuid, Vec<&str>>,
+        mut rows: BoxStream<'_, Result<T, sqlx::Error>>,
+    ) -> Result<()> {
+        while let Some(row) = rows.next().await {
+            let mut row: ExportCsvRow = row.context("Failed to read collection item")?.into();
+            row.tags = row
+                .id
+                .and_then(|id| item_tags.get(&id))
+                .map(|names| names.join(EXPORT_TAG_SEPARATOR));
+
+            writer.serialize(&row)?;
+            Self::flush_csv(writer, tx).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn flush_csv(writer: &mut csv::Writer<Vec<u8>>, tx: &Sender) -> Result<()> {
+        writer.flush()?;
+        let chunk = std::mem::take(writer.get_mut());
+        Self::send(tx, chunk).await
+    }
+
+    async fn send(tx: &Sender, chunk: Vec<u8>) -> Result<()> {
+        if tx.send(Ok(Bytes::from(chunk))).await.is_err() {
+            bail!("Export client disconnected");
+        }
+
+        Ok(())
+    }
+}
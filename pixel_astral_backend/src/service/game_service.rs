@@ -1,9 +1,21 @@
 use anyhow::{Context, Result};
 use uuid::Uuid;
 
-use crate::sql::game_sql;
-use crate::type::game_type::{CreateGameRequest, Game, UpdateGameRequest};
-use sqlx::PgPool;
+use crate::blob_store::BlobStore;
+use crate::error::{self, AppError};
+use crate::service::collection_service::CollectionService;
+use crate::service::cover_service::CoverService;
+use crate::service::history_service::HistoryService;
+use crate::sql::{cover_sql, game_sql, history_sql, tag_sql};
+use crate::type::collection_type::CollectionRole;
+use crate::type::cover_type::Cover;
+use crate::type::game_type::{
+    BulkGameRequest, BulkGameResponse, BulkGameResult, BulkMode, BulkOperation, CreateGameRequest,
+    Game, GameFilter, UpdateGameRequest, GAME_PLATFORMS, GAME_STATUSES, MAX_BULK_GAME_OPERATIONS,
+};
+use crate::type::history_type::{ItemAction, ItemEvent};
+use crate::type::item_type::ItemKind;
+use sqlx::{PgConnection, PgExecutor, PgPool, Postgres, Transaction};
 
 pub struct GameService;
 
@@ -13,28 +25,115 @@ impl GameService {
         user_id: Uuid,
         request: CreateGameRequest,
     ) -> Result<Game> {
-        game_sql::create_game(pool, user_id, request)
+        Self::validate_create(&request)?;
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let game = game_sql::create_game(&mut *tx, user_id, None, request)
+            .await
+            .map_err(|err| Self::map_sql_error(err, "Failed to create game"))?;
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Game, game.id, ItemAction::Create, None, &game).await?;
+        tx.commit().await.context("Failed to commit game")?;
+
+        Ok(game)
+    }
+
+    /// Adds a game to a shared collection; `user_id` needs the editor role.
+    pub async fn create_shared(
+        pool: &PgPool,
+        collection_id: Uuid,
+        user_id: Uuid,
+        request: CreateGameRequest,
+    ) -> Result<Option<Game>> {
+        Self::validate_create(&request)?;
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        if !CollectionService::authorize(&mut *tx, collection_id, user_id, CollectionRole::Editor).await? {
+            return Ok(None);
+        }
+        let game = game_sql::create_game(&mut *tx, user_id, Some(collection_id), request)
             .await
-            .context("Failed to create game")
+            .map_err(|err| Self::map_sql_error(err, "Failed to create game"))?;
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Game, game.id, ItemAction::Create, None, &game).await?;
+        tx.commit().await.context("Failed to commit game")?;
+
+        Ok(Some(game))
     }
 
     pub async fn get_all_by_user(
         pool: &PgPool,
         user_id: Uuid,
+        filter: GameFilter,
     ) -> Result<Vec<Game>> {
-        game_sql::find_all_games_by_user(pool, user_id)
+        let mut tag_ids = filter
+            .tags
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| {
+                id.parse::<Uuid>()
+                    .map_err(|_| AppError::Validation(format!("Invalid tag ID: {}", id)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        tag_ids.sort();
+        tag_ids.dedup();
+
+        game_sql::find_all_games_by_user(pool, user_id, &tag_ids, filter.list)
             .await
             .context("Failed to get games")
     }
 
+    pub async fn get_all_by_collection(
+        pool: &PgPool,
+        collection_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Vec<Game>>> {
+        if !CollectionService::authorize(pool, collection_id, user_id, CollectionRole::Viewer).await? {
+            return Ok(None);
+        }
+
+        game_sql::find_games_by_collection(pool, collection_id)
+            .await
+            .map(Some)
+            .context("Failed to get games")
+    }
+
     pub async fn get_by_id(
         pool: &PgPool,
         id: Uuid,
         user_id: Uuid,
     ) -> Result<Option<Game>> {
-        game_sql::find_game_by_id(pool, id, user_id)
+        let Some(game) = game_sql::find_game_by_id(pool, id)
+            .await
+            .context("Failed to get game")?
+        else {
+            return Ok(None);
+        };
+
+        if !Self::authorize(pool, &game, user_id, CollectionRole::Viewer).await? {
+            return Ok(None);
+        }
+
+        Ok(Some(game))
+    }
+
+    /// Whether the game exists outside the trash and `user_id` may change it.
+    pub async fn can_edit(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool> {
+        let Some(game) = game_sql::find_game_by_id(pool, id)
             .await
-            .context("Failed to get game")
+            .context("Failed to get game")?
+        else {
+            return Ok(false);
+        };
+
+        Self::authorize(pool, &game, user_id, CollectionRole::Editor).await
     }
 
     pub async fn update(
@@ -42,19 +141,700 @@ impl GameService {
         id: Uuid,
         user_id: Uuid,
         request: UpdateGameRequest,
+        if_match: Option<&[i32]>,
+    ) -> Result<Option<Game>> {
+        Self::validate_update(&request)?;
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(before) = Self::lock_live(&mut tx, id, user_id).await? else {
+            return Ok(None);
+        };
+        Self::check_version(&before, if_match)?;
+        let game = game_sql::update_game(&mut *tx, id, request)
+            .await
+            .map_err(|err| Self::map_sql_error(err, "Failed to update game"))?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Game, id, ItemAction::Update, Some(&before), &game).await?;
+        tx.commit().await.context("Failed to commit game")?;
+
+        Ok(Some(game))
+    }
+
+    /// Full replacement for `PUT`: fields left out of the request are cleared.
+    pub async fn replace(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        request: CreateGameRequest,
+        if_match: Option<&[i32]>,
     ) -> Result<Option<Game>> {
-        game_sql::update_game(pool, id, user_id, request)
+        Self::validate_create(&request)?;
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(before) = Self::lock_live(&mut tx, id, user_id).await? else {
+            return Ok(None);
+        };
+        Self::check_version(&before, if_match)?;
+        let game = game_sql::replace_game(&mut *tx, id, request)
             .await
-            .context("Failed to update game")
+            .map_err(|err| Self::map_sql_error(err, "Failed to replace game"))?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Game, id, ItemAction::Update, Some(&before), &game).await?;
+        tx.commit().await.context("Failed to commit game")?;
+
+        Ok(Some(game))
     }
 
     pub async fn delete(
         pool: &PgPool,
         id: Uuid,
         user_id: Uuid,
+        if_match: Option<&[i32]>,
+    ) -> Result<bool> {
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let deleted = Self::delete_game(&mut tx, id, user_id, if_match).await?;
+        tx.commit().await.context("Failed to commit game")?;
+
+        Ok(deleted.is_some())
+    }
+
+    pub async fn get_trash(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<Game>> {
+        game_sql::find_trashed_games_by_user(pool, user_id)
+            .await
+            .context("Failed to get trashed games")
+    }
+
+    pub async fn get_collection_trash(
+        pool: &PgPool,
+        collection_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Vec<Game>>> {
+        if !CollectionService::authorize(pool, collection_id, user_id, CollectionRole::Viewer).await? {
+            return Ok(None);
+        }
+
+        game_sql::find_trashed_games_by_collection(pool, collection_id)
+            .await
+            .map(Some)
+            .context("Failed to get trashed games")
+    }
+
+    pub async fn restore(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Game>> {
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(before) = game_sql::find_game_for_update(&mut *tx, id)
+            .await
+            .context("Failed to get game")?
+        else {
+            return Ok(None);
+        };
+        if !Self::authorize(&mut *tx, &before, user_id, CollectionRole::Editor).await? {
+            return Ok(None);
+        }
+        let Some(game) = game_sql::restore_game(&mut *tx, id)
+            .await
+            .context("Failed to restore game")?
+        else {
+            return Ok(None);
+        };
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Game, id, ItemAction::Restore, Some(&before), &game).await?;
+        tx.commit().await.context("Failed to commit game")?;
+
+        Ok(Some(game))
+    }
+
+    /// Deletes the game for good, along with its cover files. Shared games
+    /// can only be purged by an owner of the collection.
+    pub async fn purge(
+        pool: &PgPool,
+        store: &dyn BlobStore,
+        id: Uuid,
+        user_id: Uuid,
     ) -> Result<bool> {
-        game_sql::delete_game(pool, id, user_id)
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(game) = game_sql::find_game_for_update(&mut *tx, id)
+            .await
+            .context("Failed to get game")?
+        else {
+            return Ok(false);
+        };
+        if !Self::authorize(&mut *tx, &game, user_id, CollectionRole::Owner).await? {
+            return Ok(false);
+        }
+        let purged = game_sql::purge_game(&mut *tx, id)
+            .await
+            .context("Failed to purge game")?;
+        tx.commit().await.context("Failed to commit game")?;
+
+        let Some(cover) = purged else {
+            return Ok(false);
+        };
+        CoverService::delete_files(store, &[cover]).await;
+
+        Ok(true)
+    }
+
+    pub async fn empty_trash(
+        pool: &PgPool,
+        store: &dyn BlobStore,
+        user_id: Uuid,
+    ) -> Result<u64> {
+        let covers = game_sql::purge_trashed_games_by_user(pool, user_id)
+            .await
+            .context("Failed to empty game trash")?;
+
+        CoverService::delete_files(store, &covers).await;
+
+        Ok(covers.len() as u64)
+    }
+
+    /// Also works for games in the trash, so a version can be picked
+    /// before restoring.
+    pub async fn get_history(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Vec<ItemEvent>>> {
+        let Some(game) = game_sql::find_game_with_trashed(pool, id)
+            .await
+            .context("Failed to get game")?
+        else {
+            return Ok(None);
+        };
+
+        if !Self::authorize(pool, &game, user_id, CollectionRole::Viewer).await? {
+            return Ok(None);
+        }
+
+        history_sql::find_item_events(pool, ItemKind::Game, id)
+            .await
+            .map(Some)
+            .context("Failed to get game history")
+    }
+
+    /// Writes the game back to how it was right after `event_id`.
+    pub async fn revert(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        event_id: Uuid,
+    ) -> Result<Option<Game>> {
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(before) = Self::lock_live(&mut tx, id, user_id).await? else {
+            return Ok(None);
+        };
+        let version: Game = HistoryService::find_version(&mut tx, ItemKind::Game, id, event_id).await?;
+        let game = game_sql::revert_game(&mut *tx, id, version)
+            .await
+            .map_err(|err| Self::map_sql_error(err, "Failed to revert game"))?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record_revert(&mut tx, user_id, ItemKind::Game, id, event_id, &before, &game).await?;
+        tx.commit().await.context("Failed to commit game")?;
+
+        Ok(Some(game))
+    }
+
+    /// Points the game at a new cover, or none, in the caller's transaction
+    /// and returns the cover it replaced, whose files the caller deletes
+    /// once the transaction commits.
+    pub async fn set_cover(
+        conn: &mut PgConnection,
+        id: Uuid,
+        user_id: Uuid,
+        cover: &Cover,
+    ) -> Result<Option<Cover>> {
+        let Some(before) = Self::lock_live(conn, id, user_id).await? else {
+            return Ok(None);
+        };
+        let game = cover_sql::update_game_cover(&mut *conn, id, cover)
             .await
-            .context("Failed to delete game")
+            .context("Failed to save cover")?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record(conn, user_id, ItemKind::Game, id, ItemAction::Update, Some(&before), &game).await?;
+
+        Ok(Some(Cover {
+            cover_url: before.cover_url,
+            cover_medium_url: before.cover_medium_url,
+            cover_small_url: before.cover_small_url,
+        }))
+    }
+
+    /// Replaces the caller's own tags on the game. Returns `false` if the
+    /// game is gone or the caller may not edit it.
+    pub async fn set_tags(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        tag_ids: &[Uuid],
+    ) -> Result<bool> {
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        if Self::lock_live(&mut tx, id, user_id).await?.is_none() {
+            return Ok(false);
+        }
+        let updated = tag_sql::replace_item_tags(&mut tx, user_id, ItemKind::Game, id, tag_ids)
+            .await
+            .context("Failed to set game tags")?;
+
+        if !updated {
+            return Err(AppError::Validation("Unknown tag".to_string()).into());
+        }
+
+        tx.commit().await.context("Failed to commit game tags")?;
+
+        Ok(true)
+    }
+
+    /// Runs creates, then updates, then deletes in one transaction. Each
+    /// operation gets its own savepoint so a failure is reported per item
+    /// without aborting the rest; in `AllOrNothing` mode the whole
+    /// transaction is rolled back if anything failed.
+    pub async fn bulk(
+        pool: &PgPool,
+        user_id: Uuid,
+        request: BulkGameRequest,
+    ) -> Result<BulkGameResponse> {
+        let total = request.create.len()
+            + request.update.iter().map(|update| update.ids.len()).sum::<usize>()
+            + request.delete.len();
+
+        if total == 0 {
+            return Err(AppError::Validation("No operations given".to_string()).into());
+        }
+
+        if total > MAX_BULK_GAME_OPERATIONS {
+            return Err(AppError::Validation(format!(
+                "At most {} operations per request",
+                MAX_BULK_GAME_OPERATIONS
+            ))
+            .into());
+        }
+
+        let mut tx = pool.begin().await.context("Failed to start bulk transaction")?;
+        let mut results = Vec::with_capacity(total);
+
+        for (index, create) in request.create.into_iter().enumerate() {
+            let outcome = match Self::validate_create(&create) {
+                Ok(()) => {
+                    let mut savepoint = tx.begin().await.context("Failed to create savepoint")?;
+                    let outcome = Self::bulk_create_game(&mut savepoint, user_id, create)
+                        .await
+                        .map(Some);
+                    Self::finish_savepoint(savepoint, outcome).await?
+                }
+                Err(err) => Err(err),
+            };
+            results.push(Self::bulk_result(BulkOperation::Create, Some(index), None, outcome));
+        }
+
+        for update in request.update {
+            let validation = match &update.changes {
+                Some(changes) => Self::validate_update(changes).map_err(|err| err.to_string()),
+                None => Ok(()),
+            };
+            let tag_ids = update.tag_ids.map(|mut tag_ids| {
+                tag_ids.sort();
+                tag_ids.dedup();
+                tag_ids
+            });
+
+            for id in update.ids {
+                let outcome = match &validation {
+                    Ok(()) => {
+                        let mut savepoint = tx.begin().await.context("Failed to create savepoint")?;
+                        let outcome = Self::bulk_update_game(
+                            &mut savepoint,
+                            user_id,
+                            id,
+                            update.changes.clone(),
+                            tag_ids.as_deref(),
+                        )
+                        .await
+                        .map(Some);
+                        Self::finish_savepoint(savepoint, outcome).await?
+                    }
+                    Err(message) => Err(AppError::Validation(message.clone()).into()),
+                };
+                results.push(Self::bulk_result(BulkOperation::Update, None, Some(id), outcome));
+            }
+        }
+
+        for id in request.delete {
+            let mut savepoint = tx.begin().await.context("Failed to create savepoint")?;
+            let outcome = match Self::delete_game(&mut savepoint, id, user_id, None).await {
+                Ok(Some(_)) => Ok(None),
+                Ok(None) => Err(AppError::NotFound.into()),
+                Err(err) => Err(err),
+            };
+            let outcome = Self::finish_savepoint(savepoint, outcome).await?;
+            results.push(Self::bulk_result(BulkOperation::Delete, None, Some(id), outcome));
+        }
+
+        let failed = results.iter().filter(|result| result.error.is_some()).count();
+        let committed = failed == 0 || request.mode == BulkMode::BestEffort;
+
+        if committed {
+            tx.commit().await.context("Failed to commit bulk transaction")?;
+        } else {
+            tx.rollback().await.context("Failed to roll back bulk transaction")?;
+        }
+
+        Ok(BulkGameResponse {
+            committed,
+            succeeded: results.len() - failed,
+            failed,
+            results,
+        })
+    }
+
+    async fn bulk_create_game(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        request: CreateGameRequest,
+    ) -> Result<Game> {
+        let game = game_sql::create_game(&mut *conn, user_id, None, request)
+            .await
+            .map_err(|err| Self::map_sql_error(err, "Failed to create game"))?;
+
+        HistoryService::record(conn, user_id, ItemKind::Game, game.id, ItemAction::Create, None, &game).await?;
+
+        Ok(game)
+    }
+
+    async fn bulk_update_game(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        id: Uuid,
+        changes: Option<UpdateGameRequest>,
+        tag_ids: Option<&[Uuid]>,
+    ) -> Result<Game> {
+        let before = Self::lock_live(conn, id, user_id).await?.ok_or(AppError::NotFound)?;
+
+        let game = match changes {
+            Some(changes) => {
+                let game = game_sql::update_game(&mut *conn, id, changes)
+                    .await
+                    .map_err(|err| Self::map_sql_error(err, "Failed to update game"))?
+                    .ok_or(AppError::NotFound)?;
+
+                HistoryService::record(conn, user_id, ItemKind::Game, id, ItemAction::Update, Some(&before), &game)
+                    .await?;
+                game
+            }
+            None => before,
+        };
+
+        if let Some(tag_ids) = tag_ids {
+            let updated = tag_sql::replace_item_tags(conn, user_id, ItemKind::Game, id, tag_ids)
+                .await
+                .context("Failed to set game tags")?;
+
+            if !updated {
+                return Err(AppError::Validation("Unknown tag".to_string()).into());
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Locks the game if it exists, is not in the trash and `user_id` may
+    /// change it.
+    async fn lock_live(
+        conn: &mut PgConnection,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Game>> {
+        let Some(game) = game_sql::find_game_for_update(&mut *conn, id)
+            .await
+            .context("Failed to get game")?
+        else {
+            return Ok(None);
+        };
+
+        if game.deleted_at.is_some() || !Self::authorize(&mut *conn, &game, user_id, CollectionRole::Editor).await? {
+            return Ok(None);
+        }
+
+        Ok(Some(game))
+    }
+
+    /// Whether `user_id` may act on the game: the owner of a personal game,
+    /// or a member of its collection with at least `role`.
+    async fn authorize<'e>(
+        executor: impl PgExecutor<'e>,
+        game: &Game,
+        user_id: Uuid,
+        role: CollectionRole,
+    ) -> Result<bool> {
+        match game.collection_id {
+            Some(collection_id) => CollectionService::authorize(executor, collection_id, user_id, role).await,
+            None => Ok(game.user_id == user_id),
+        }
+    }
+
+    async fn delete_game(
+        conn: &mut PgConnection,
+        id: Uuid,
+        user_id: Uuid,
+        if_match: Option<&[i32]>,
+    ) -> Result<Option<Game>> {
+        let Some(before) = Self::lock_live(conn, id, user_id).await? else {
+            return Ok(None);
+        };
+        Self::check_version(&before, if_match)?;
+        let game = game_sql::delete_game(&mut *conn, id)
+            .await
+            .context("Failed to delete game")?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record(conn, user_id, ItemKind::Game, id, ItemAction::Delete, Some(&before), &game).await?;
+
+        Ok(Some(game))
+    }
+
+    /// `If-Match` check, made after locking the row: fails unless the stored
+    /// version is one the client listed.
+    fn check_version(game: &Game, if_match: Option<&[i32]>) -> Result<()> {
+        if if_match.is_some_and(|versions| !versions.contains(&game.version)) {
+            return Err(AppError::PreconditionFailed.into());
+        }
+
+        Ok(())
+    }
+
+    /// Releases the savepoint if the operation succeeded and rolls it back
+    /// otherwise. The outer `Result` is only for the savepoint itself.
+    async fn finish_savepoint<T>(
+        savepoint: Transaction<'_, Postgres>,
+        outcome: Result<T>,
+    ) -> Result<Result<T>> {
+        match outcome {
+            Ok(value) => {
+                savepoint.commit().await.context("Failed to release savepoint")?;
+                Ok(Ok(value))
+            }
+            Err(err) => {
+                savepoint.rollback().await.context("Failed to roll back savepoint")?;
+                Ok(Err(err))
+            }
+        }
+    }
+
+    fn bulk_result(
+        operation: BulkOperation,
+        index: Option<usize>,
+        id: Option<Uuid>,
+        outcome: Result<Option<Game>>,
+    ) -> BulkGameResult {
+        match outcome {
+            Ok(game) => BulkGameResult {
+                operation,
+                index,
+                id: id.or(game.as_ref().map(|game| game.id)),
+                status: match operation {
+                    BulkOperation::Create => 201,
+                    BulkOperation::Update => 200,
+                    BulkOperation::Delete => 204,
+                },
+                error: None,
+                game,
+            },
+            Err(err) => {
+                let status = error::status_code(&err);
+                let message = match err.downcast_ref::<AppError>() {
+                    Some(app_err) => app_err.to_string(),
+                    None => {
+                        tracing::error!("Bulk game operation failed: {:#}", err);
+                        "Internal server error".to_string()
+                    }
+                };
+
+                BulkGameResult {
+                    operation,
+                    index,
+                    id,
+                    status: status.as_u16(),
+                    error: Some(message),
+                    game: None,
+                }
+            }
+        }
+    }
+
+    pub fn validate_create(request: &CreateGameRequest) -> Result<()> {
+        if request.title.trim().is_empty() {
+            return Err(AppError::Validation("Title is required".to_string()).into());
+        }
+
+        Self::validate_status(&request.status)?;
+        Self::validate_progress(
+            request.rating,
+            request.platform.as_deref(),
+            request.hours_played,
+            request.completion_percent,
+        )?;
+
+        if let (Some(started_at), Some(finished_at)) = (request.started_at, request.finished_at) {
+            if finished_at < started_at {
+                return Err(AppError::Validation("finished_at must not be before started_at".to_string()).into());
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_update(request: &UpdateGameRequest) -> Result<()> {
+        if request.title.is_null() || request.title.value().is_some_and(|title| title.trim().is_empty()) {
+            return Err(AppError::Validation("Title must not be empty".to_string()).into());
+        }
+
+        if request.status.is_null() {
+            return Err(AppError::Validation("Status cannot be cleared".to_string()).into());
+        }
+
+        if let Some(status) = request.status.value() {
+            Self::validate_status(status)?;
+        }
+
+        Self::validate_progress(
+            request.rating.value().copied(),
+            request.platform.value().map(String::as_str),
+            request.hours_played.value().copied(),
+            request.completion_percent.value().copied(),
+        )
+    }
+
+    fn validate_status(status: &str) -> Result<()> {
+        if !GAME_STATUSES.contains(&status) {
+            return Err(AppError::Validation(format!("Unknown status: {}", status)).into());
+        }
+
+        Ok(())
+    }
+
+    fn validate_progress(
+        rating: Option<i32>,
+        platform: Option<&str>,
+        hours_played: Option<f64>,
+        completion_percent: Option<i32>,
+    ) -> Result<()> {
+        if rating.is_some_and(|rating| !(1..=5).contains(&rating)) {
+            return Err(AppError::Validation("Rating must be between 1 and 5".to_string()).into());
+        }
+
+        if let Some(platform) = platform {
+            if !GAME_PLATFORMS.contains(&platform) {
+                return Err(AppError::Validation(format!("Unknown platform: {}", platform)).into());
+            }
+        }
+
+        if hours_played.is_some_and(|hours| !hours.is_finite() || hours < 0.0) {
+            return Err(AppError::Validation("hours_played must be zero or more".to_string()).into());
+        }
+
+        if completion_percent.is_some_and(|percent| !(0..=100).contains(&percent)) {
+            return Err(AppError::Validation("completion_percent must be between 0 and 100".to_string()).into());
+        }
+
+        Ok(())
+    }
+
+    /// Turns constraint violations (e.g. `finished_at` before `started_at`
+    /// after a partial update) into validation errors.
+    pub fn map_sql_error(err: sqlx::Error, context: &'static str) -> anyhow::Error {
+        match &err {
+            sqlx::Error::Database(db_err) if db_err.is_check_violation() => {
+                AppError::Validation(db_err.message().to_string()).into()
+            }
+            _ => anyhow::Error::new(err).context(context),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GameService;
+    use crate::error::AppError;
+    use crate::type::game_type::{CreateGameRequest, UpdateGameRequest};
+    use crate::type::patch_type::Patch;
+
+    fn update(json: &str) -> UpdateGameRequest {
+        serde_json::from_str(json).expect("valid update")
+    }
+
+    fn is_validation_error(result: anyhow::Result<()>) -> bool {
+        result.is_err_and(|err| matches!(err.downcast_ref::<AppError>(), Some(AppError::Validation(_))))
+    }
+
+    #[test]
+    fn update_clears_nullable_fields() {
+        let request = update(r#"{"genre": null, "rating": null, "notes": null}"#);
+
+        assert!(request.genre.is_null());
+        assert!(request.rating.is_null());
+        assert!(request.notes.is_null());
+        assert_eq!(request.title, Patch::Absent);
+        assert_eq!(request.status, Patch::Absent);
+        assert!(GameService::validate_update(&request).is_ok());
+    }
+
+    #[test]
+    fn update_leaves_missing_fields_alone() {
+        let request = update(r#"{"rating": 4}"#);
+
+        assert_eq!(request.rating, Patch::Value(4));
+        assert!(!request.genre.is_set());
+        assert!(!request.notes.is_set());
+        assert!(GameService::validate_update(&request).is_ok());
+    }
+
+    #[test]
+    fn update_rejects_null_title() {
+        assert!(is_validation_error(GameService::validate_update(&update(r#"{"title": null}"#))));
+        assert!(is_validation_error(GameService::validate_update(&update(r#"{"title": "  "}"#))));
+    }
+
+    #[test]
+    fn update_rejects_null_status() {
+        assert!(is_validation_error(GameService::validate_update(&update(r#"{"status": null}"#))));
+    }
+
+    #[test]
+    fn update_still_validates_values() {
+        assert!(is_validation_error(GameService::validate_update(&update(r#"{"rating": 6}"#))));
+    }
+
+    #[test]
+    fn replace_clears_fields_left_out() {
+        let request: CreateGameRequest =
+            serde_json::from_str(r#"{"title": "Hades", "status": "Playing"}"#).expect("valid game");
+
+        // `replace_game` writes every one of these, so leaving them out of a
+        // PUT clears them instead of keeping the stored values.
+        assert_eq!(request.genre, None);
+        assert_eq!(request.rating, None);
+        assert_eq!(request.notes, None);
+        assert_eq!(request.platform, None);
+        assert_eq!(request.hours_played, None);
+        assert_eq!(request.completion_percent, None);
+        assert_eq!(request.started_at, None);
+        assert_eq!(request.finished_at, None);
+        assert!(GameService::validate_create(&request).is_ok());
+    }
+
+    #[test]
+    fn replace_requires_title_and_status() {
+        assert!(serde_json::from_str::<CreateGameRequest>(r#"{"status": "Playing"}"#).is_err());
+        assert!(serde_json::from_str::<CreateGameRequest>(r#"{"title": "Hades"}"#).is_err());
     }
 }
@@ -0,0 +1,170 @@
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::service::activity_service::ActivityService;
+use crate::sql::{activity_sql, history_sql, item_sql};
+use crate::type::history_type::{FieldChange, ItemAction, ItemEvent, NewItemEvent, UNTRACKED_FIELDS};
+use crate::type::item_type::ItemKind;
+use sqlx::{PgConnection, PgPool};
+
+pub struct HistoryService;
+
+impl HistoryService {
+    /// Events for one item, newest first, whether or not it is in the trash;
+    /// `None` if the user has no such item.
+    pub async fn get_history(
+        pool: &PgPool,
+        user_id: Uuid,
+        kind: ItemKind,
+        item_id: Uuid,
+    ) -> Result<Option<Vec<ItemEvent>>> {
+        if !item_sql::item_owned(pool, kind, item_id, user_id)
+            .await
+            .context("Failed to find item")?
+        {
+            return Ok(None);
+        }
+
+        history_sql::find_item_events(pool, kind, item_id)
+            .await
+            .map(Some)
+            .context("Failed to get item history")
+    }
+
+    /// Writes an event holding `after` and its diff against `before`, plus
+    /// the feed activity it implies. Call it on the connection that made the
+    /// change so everything commits together.
+    pub async fn record<T: Serialize>(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        kind: ItemKind,
+        item_id: Uuid,
+        action: ItemAction,
+        before: Option<&T>,
+        after: &T,
+    ) -> Result<()> {
+        let event = Self::event(user_id, kind, item_id, action, before, after)?;
+        Self::write(conn, event, true).await
+    }
+
+    /// Like `record`, without a feed activity, so a bulk import does not
+    /// flood followers' feeds.
+    pub async fn record_imported<T: Serialize>(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        kind: ItemKind,
+        item_id: Uuid,
+        action: ItemAction,
+        before: Option<&T>,
+        after: &T,
+    ) -> Result<()> {
+        let event = Self::event(user_id, kind, item_id, action, before, after)?;
+        Self::write(conn, event, false).await
+    }
+
+    /// Loads the item as it was right after `event_id`, ready to be written
+    /// back by a revert. Call it after checking access to the item.
+    pub async fn find_version<T: DeserializeOwned>(
+        conn: &mut PgConnection,
+        kind: ItemKind,
+        item_id: Uuid,
+        event_id: Uuid,
+    ) -> Result<T> {
+        let event = history_sql::find_item_event(conn, event_id, kind, item_id)
+            .await
+            .context("Failed to get item event")?
+            .ok_or(AppError::NotFound)?;
+
+        serde_json::from_value(event.snapshot).context("Failed to read item snapshot")
+    }
+
+    /// Like `record`, for a revert to the version saved by `event_id`.
+    pub async fn record_revert<T: Serialize>(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        kind: ItemKind,
+        item_id: Uuid,
+        event_id: Uuid,
+        before: &T,
+        after: &T,
+    ) -> Result<()> {
+        let event = NewItemEvent {
+            reverted_event_id: Some(event_id),
+            ..Self::event(user_id, kind, item_id, ItemAction::Revert, Some(before), after)?
+        };
+
+        Self::write(conn, event, true).await
+    }
+
+    async fn write(conn: &mut PgConnection, event: NewItemEvent, with_activity: bool) -> Result<()> {
+        let event_id = history_sql::create_item_event(&mut *conn, &event)
+            .await
+            .context("Failed to record item history")?;
+
+        if !with_activity {
+            return Ok(());
+        }
+
+        if let Some(activity) = ActivityService::derive(&event, event_id) {
+            activity_sql::create_activity(conn, activity)
+                .await
+                .context("Failed to record activity")?;
+        }
+
+        Ok(())
+    }
+
+    fn event<T: Serialize>(
+        user_id: Uuid,
+        kind: ItemKind,
+        item_id: Uuid,
+        action: ItemAction,
+        before: Option<&T>,
+        after: &T,
+    ) -> Result<NewItemEvent> {
+        let before = before
+            .map(serde_json::to_value)
+            .transpose()
+            .context("Failed to serialize item")?;
+        let snapshot = serde_json::to_value(after).context("Failed to serialize item")?;
+
+        Ok(NewItemEvent {
+            user_id,
+            item_type: kind,
+            item_id,
+            action,
+            changes: Self::diff(before.as_ref(), &snapshot),
+            snapshot,
+            reverted_event_id: None,
+        })
+    }
+
+    /// Tracked fields whose value differs. With no `before`, every field
+    /// that is set counts as a change from `null`.
+    fn diff(before: Option<&Value>, after: &Value) -> Vec<FieldChange> {
+        let Value::Object(after) = after else {
+            return Vec::new();
+        };
+
+        after
+            .iter()
+            .filter(|(field, _)| !UNTRACKED_FIELDS.contains(&field.as_str()))
+            .filter_map(|(field, new)| {
+                let old = before
+                    .and_then(|before| before.get(field))
+                    .cloned()
+                    .unwrap_or(Value::Null);
+
+                (old != *new).then(|| FieldChange {
+                    field: field.clone(),
+                    old,
+                    new: new.clone(),
+                })
+            })
+            .collect()
+    }
+}
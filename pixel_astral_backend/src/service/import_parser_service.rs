@@ -0,0 +1,362 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::error::AppError;
+use crate::type::export_type::ExportFormat;
+use crate::type::import_type::{ImportKind, ImportRow, ImportSource, IMPORT_FIELDS};
+
+/// Arrays read from our export envelope, and the kind of their rows.
+const ENVELOPE_SECTIONS: &[(&str, ImportKind)] = &[
+    ("games", ImportKind::Game),
+    ("manga", ImportKind::Manga),
+    ("novels", ImportKind::Novel),
+    ("movies", ImportKind::Movie),
+];
+
+/// Turns uploaded files into `ImportRow`s on our scales and status names.
+/// Everything is parsed from the upload itself; nothing is fetched.
+pub struct ImportParserService;
+
+impl ImportParserService {
+    pub fn parse(
+        source: ImportSource,
+        format: ExportFormat,
+        body: &[u8],
+        mapping: &HashMap<String, String>,
+    ) -> Result<Vec<ImportRow>> {
+        let body = body.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(body);
+
+        match source {
+            ImportSource::Generic => match format {
+                ExportFormat::Csv => Self::generic_csv(body, mapping),
+                ExportFormat::Json => Self::generic_json(body, mapping),
+            },
+            ImportSource::Steam => Self::steam(body),
+            ImportSource::MyAnimeList => Self::myanimelist(body),
+            ImportSource::Goodreads => Self::goodreads(body),
+            ImportSource::Letterboxd => Self::letterboxd(body),
+        }
+    }
+
+    pub fn parse_mapping(mapping: Option<&str>) -> Result<HashMap<String, String>> {
+        let Some(mapping) = mapping else {
+            return Ok(HashMap::new());
+        };
+
+        let mapping: HashMap<String, String> = serde_json::from_str(mapping)
+            .map_err(|_| AppError::Validation("mapping must be a JSON object of field to column name".to_string()))?;
+
+        if let Some(field) = mapping.keys().find(|field| !IMPORT_FIELDS.contains(&field.as_str())) {
+            return Err(AppError::Validation(format!("Unknown field in mapping: {}", field)).into());
+        }
+
+        Ok(mapping)
+    }
+
+    fn generic_csv(body: &[u8], mapping: &HashMap<String, String>) -> Result<Vec<ImportRow>> {
+        let (headers, records) = Self::csv_records(body)?;
+
+        if !headers.contains(&Self::column_for(mapping, "title")) {
+            return Err(AppError::Validation("No title column found; map one with `mapping`".to_string()).into());
+        }
+
+        Ok(records.iter().map(|record| Self::apply_mapping(record, mapping)).collect())
+    }
+
+    /// Accepts our export envelope (its `games`, `manga`, `novels` and
+    /// `movies` arrays, each row tagged with its kind) or a bare array of
+    /// objects.
+    fn generic_json(body: &[u8], mapping: &HashMap<String, String>) -> Result<Vec<ImportRow>> {
+        let value: serde_json::Value = serde_json::from_slice(body)
+            .map_err(|err| AppError::Validation(format!("Invalid JSON: {}", err)))?;
+
+        let sections = match value {
+            serde_json::Value::Array(items) => vec![(None, items)],
+            serde_json::Value::Object(mut envelope) => {
+                let mut sections = Vec::new();
+                for (key, kind) in ENVELOPE_SECTIONS {
+                    match envelope.remove(*key) {
+                        Some(serde_json::Value::Array(items)) => sections.push((Some(*kind), items)),
+                        Some(_) => {
+                            return Err(AppError::Validation(format!("Expected `{}` to be an array", key)).into());
+                        }
+                        None => {}
+                    }
+                }
+                if sections.is_empty() {
+                    return Err(AppError::Validation("Expected a `games`, `manga`, `novels` or `movies` array".to_string()).into());
+                }
+                sections
+            }
+            _ => return Err(AppError::Validation("Expected an array or an export envelope".to_string()).into()),
+        };
+
+        let mut rows = Vec::new();
+        for (kind, items) in sections {
+            for item in items {
+                let serde_json::Value::Object(object) = item else {
+                    return Err(AppError::Validation(format!("Row {} is not an object", rows.len() + 1)).into());
+                };
+
+                let record: HashMap<String, String> = object
+                    .into_iter()
+                    .filter_map(|(key, value)| {
+                        let value = match value {
+                            serde_json::Value::Null => return None,
+                            serde_json::Value::String(value) => value.trim().to_string(),
+                            other => other.to_string(),
+                        };
+                        (!value.is_empty()).then(|| (key.trim().to_lowercase(), value))
+                    })
+                    .collect();
+
+                let mut row = Self::apply_mapping(&record, mapping);
+                if let Some(kind) = kind {
+                    row.insert("item_type".to_string(), kind.as_str().to_string());
+                }
+                rows.push(row);
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Steam Web API `GetOwnedGames` output, either the full
+    /// `{"response":{"games":[..]}}` body or just the games array.
+    fn steam(body: &[u8]) -> Result<Vec<ImportRow>> {
+        #[derive(Deserialize)]
+        struct SteamGame {
+            appid: Option<u64>,
+            name: Option<String>,
+            /// Minutes
+            #[serde(default)]
+            playtime_forever: u64,
+        }
+
+        #[derive(Deserialize)]
+        struct SteamGames {
+            #[serde(default)]
+            games: Vec<SteamGame>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum SteamExport {
+            Response { response: SteamGames },
+            Games(SteamGames),
+            List(Vec<SteamGame>),
+        }
+
+        let export: SteamExport = serde_json::from_slice(body)
+            .map_err(|err| AppError::Validation(format!("Not a Steam library export: {}", err)))?;
+        let games = match export {
+            SteamExport::Response { response } => response.games,
+            SteamExport::Games(games) => games.games,
+            SteamExport::List(games) => games,
+        };
+
+        Ok(games
+            .into_iter()
+            .map(|game| {
+                let mut row = Self::row(ImportKind::Game);
+                let title = game.name.or_else(|| game.appid.map(|appid| format!("Steam app {}", appid)));
+                Self::set(&mut row, "title", title);
+                Self::set(&mut row, "platform", Some("PC".to_string()));
+
+                let status = if game.playtime_forever == 0 { "Plan to Play" } else { "Playing" };
+                Self::set(&mut row, "status", Some(status.to_string()));
+
+                if game.playtime_forever > 0 {
+                    let hours = (game.playtime_forever as f64 / 60.0 * 10.0).round() / 10.0;
+                    Self::set(&mut row, "hours_played", Some(hours.to_string()));
+                }
+
+                row
+            })
+            .collect())
+    }
+
+    /// MyAnimeList manga list export (`mangalist_*.xml`). Anime entries in
+    /// the same file are ignored.
+    fn myanimelist(body: &[u8]) -> Result<Vec<ImportRow>> {
+        #[derive(Deserialize)]
+        struct MalManga {
+            manga_title: Option<String>,
+            my_read_chapters: Option<String>,
+            my_score: Option<String>,
+            my_status: Option<String>,
+            my_comments: Option<String>,
+        }
+
+        #[derive(Deserialize)]
+        struct MalExport {
+            #[serde(default)]
+            manga: Vec<MalManga>,
+        }
+
+        let body = std::str::from_utf8(body)
+            .map_err(|_| AppError::Validation("MyAnimeList export must be UTF-8 XML".to_string()))?;
+        let export: MalExport = quick_xml::de::from_str(body)
+            .map_err(|err| AppError::Validation(format!("Not a MyAnimeList export: {}", err)))?;
+
+        if export.manga.is_empty() {
+            return Err(AppError::Validation("No manga found in the MyAnimeList export".to_string()).into());
+        }
+
+        Ok(export
+            .manga
+            .into_iter()
+            .map(|manga| {
+                let mut row = Self::row(ImportKind::Manga);
+                Self::set(&mut row, "title", manga.manga_title);
+                Self::set(&mut row, "chapter", manga.my_read_chapters);
+                Self::set(&mut row, "notes", manga.my_comments);
+
+                let rating = manga
+                    .my_score
+                    .and_then(|score| score.trim().parse::<f64>().ok())
+                    .and_then(|score| Self::scale_rating(score, 10.0));
+                Self::set(&mut row, "rating", rating.map(|rating| rating.to_string()));
+
+                // Older exports use numeric codes; 3 is On-Hold, which we
+                // treat as still reading.
+                let status = manga.my_status.and_then(|status| match status.trim() {
+                    "Reading" | "On-Hold" | "1" | "3" => Some("Reading"),
+                    "Completed" | "2" => Some("Completed"),
+                    "Dropped" | "4" => Some("Dropped"),
+                    "Plan to Read" | "6" => Some("Plan to Read"),
+                    _ => None,
+                });
+                Self::set(&mut row, "status", status.map(str::to_string));
+
+                row
+            })
+            .collect())
+    }
+
+    /// Goodreads "Export Library" CSV.
+    fn goodreads(body: &[u8]) -> Result<Vec<ImportRow>> {
+        let (_, records) = Self::csv_records(body)?;
+
+        Ok(records
+            .into_iter()
+            .map(|mut record| {
+                let mut row = Self::row(ImportKind::Novel);
+                Self::set(&mut row, "title", record.remove("title"));
+
+                let rating = record
+                    .get("my rating")
+                    .and_then(|rating| rating.parse::<f64>().ok())
+                    .and_then(|rating| Self::scale_rating(rating, 5.0));
+                Self::set(&mut row, "rating", rating.map(|rating| rating.to_string()));
+
+                let shelves = record.get("bookshelves").cloned().unwrap_or_default();
+                let status = match record.get("exclusive shelf").map(String::as_str) {
+                    Some("read") => "Completed",
+                    Some("currently-reading") => "Reading",
+                    _ if shelves.contains("did-not-finish") || shelves.contains("dnf") => "Dropped",
+                    _ => "Plan to Read",
+                };
+                Self::set(&mut row, "status", Some(status.to_string()));
+
+                let review = record
+                    .remove("my review")
+                    .map(|review| review.replace("<br/>", "\n").replace("<br />", "\n"));
+                Self::set(&mut row, "notes", review);
+
+                row
+            })
+            .collect())
+    }
+
+    /// Letterboxd `watched.csv`, `ratings.csv`, `diary.csv` or `reviews.csv`.
+    /// Every entry is a watched film, so the status is always `Completed`.
+    fn letterboxd(body: &[u8]) -> Result<Vec<ImportRow>> {
+        let (_, records) = Self::csv_records(body)?;
+
+        Ok(records
+            .into_iter()
+            .map(|mut record| {
+                let mut row = Self::row(ImportKind::Movie);
+                Self::set(&mut row, "title", record.remove("name"));
+                Self::set(&mut row, "status", Some("Completed".to_string()));
+                Self::set(&mut row, "notes", record.remove("review"));
+
+                let rating = record
+                    .get("rating")
+                    .and_then(|rating| rating.parse::<f64>().ok())
+                    .and_then(|rating| Self::scale_rating(rating, 5.0));
+                Self::set(&mut row, "rating", rating.map(|rating| rating.to_string()));
+
+                row
+            })
+            .collect())
+    }
+
+    /// Maps a score out of `max` onto our 1-5 scale, rounding up so that
+    /// e.g. MAL 9/10 and Letterboxd 4.5 both become 5. Zero means unrated.
+    fn scale_rating(score: f64, max: f64) -> Option<i32> {
+        if !score.is_finite() || score <= 0.0 {
+            return None;
+        }
+
+        Some(((score / max * 5.0).ceil() as i32).clamp(1, 5))
+    }
+
+    /// The lowercased headers and the records keyed by them, with empty
+    /// cells left out.
+    fn csv_records(body: &[u8]) -> Result<(Vec<String>, Vec<HashMap<String, String>>)> {
+        let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(body);
+
+        let headers: Vec<String> = reader
+            .headers()
+            .map_err(|err| AppError::Validation(format!("Invalid CSV header: {}", err)))?
+            .iter()
+            .map(|header| header.trim().to_lowercase())
+            .collect();
+
+        let mut records = Vec::new();
+        for (index, record) in reader.records().enumerate() {
+            let record = record
+                .map_err(|err| AppError::Validation(format!("Invalid CSV on row {}: {}", index + 1, err)))?;
+
+            let record = headers
+                .iter()
+                .zip(record.iter())
+                .filter_map(|(header, value)| {
+                    let value = value.trim();
+                    (!value.is_empty()).then(|| (header.clone(), value.to_string()))
+                })
+                .collect();
+            records.push(record);
+        }
+
+        Ok((headers, records))
+    }
+
+    fn apply_mapping(record: &HashMap<String, String>, mapping: &HashMap<String, String>) -> ImportRow {
+        IMPORT_FIELDS
+            .iter()
+            .filter_map(|field| {
+                let value = record.get(&Self::column_for(mapping, field))?;
+                Some((field.to_string(), value.clone()))
+            })
+            .collect()
+    }
+
+    /// The source column for a field, matched case-insensitively.
+    fn column_for(mapping: &HashMap<String, String>, field: &str) -> String {
+        mapping.get(field).map(String::as_str).unwrap_or(field).trim().to_lowercase()
+    }
+
+    fn row(kind: ImportKind) -> ImportRow {
+        HashMap::from([("item_type".to_string(), kind.as_str().to_string())])
+    }
+
+    fn set(row: &mut ImportRow, field: &str, value: Option<String>) {
+        if let Some(value) = value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty()) {
+            row.insert(field.to_string(), value);
+        }
+    }
+}
@@ -0,0 +1,479 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::service::game_service::GameService;
+use crate::service::history_service::HistoryService;
+use crate::service::import_parser_service::ImportParserService;
+use crate::service::manga_service::MangaService;
+use crate::service::movie_service::MovieService;
+use crate::service::novel_service::NovelService;
+use crate::sql::{game_sql, manga_sql, movie_sql, novel_sql};
+use crate::type::export_type::ExportFormat;
+use crate::type::game_type::{CreateGameRequest, GameFilter, UpdateGameRequest};
+use crate::type::history_type::ItemAction;
+use crate::type::import_type::{
+    DuplicateStrategy, ImportAction, ImportKind, ImportQuery, ImportResponse, ImportRow, ImportRowResult,
+    MAX_IMPORT_ROWS,
+};
+use crate::type::item_type::ItemKind;
+use crate::type::manga_type::{CreateMangaRequest, UpdateMangaRequest};
+use crate::type::movie_type::{CreateMovieRequest, UpdateMovieRequest};
+use crate::type::novel_type::{CreateNovelRequest, UpdateNovelRequest};
+use crate::type::patch_type::Patch;
+use sqlx::{PgConnection, PgPool};
+
+/// A parsed row, ready to be created or (once turned into an update)
+/// applied to a duplicate.
+enum ImportItem {
+    Game(CreateGameRequest),
+    Manga(CreateMangaRequest),
+    Novel(CreateNovelRequest),
+    Movie(CreateMovieRequest),
+}
+
+enum ImportUpdate {
+    Game(UpdateGameRequest),
+    Manga(UpdateMangaRequest),
+    Novel(UpdateNovelRequest),
+    Movie(UpdateMovieRequest),
+}
+
+enum PlannedChange {
+    Create(ImportItem),
+    Update(Uuid, ImportUpdate),
+    None,
+}
+
+type DuplicateKey = (ImportKind, String, String);
+
+pub struct ImportService;
+
+impl ImportService {
+    /// Parses and validates every row, matches rows against the existing
+    /// collection by normalised title (and platform for games), and
+    /// (unless `dry_run`) applies the creates and updates in a single
+    /// transaction.
+    ///
+    /// Nothing is written if any row is invalid; the response then lists
+    /// the problems so the file can be fixed and resubmitted.
+    pub async fn import(
+        pool: &PgPool,
+        user_id: Uuid,
+        format: ExportFormat,
+        body: &[u8],
+        query: &ImportQuery,
+    ) -> Result<ImportResponse> {
+        let dry_run = query.dry_run;
+        let mapping = ImportParserService::parse_mapping(query.mapping.as_deref())?;
+        let rows = ImportParserService::parse(query.source, format, body, &mapping)?;
+
+        if rows.len() > MAX_IMPORT_ROWS {
+            return Err(AppError::Validation(format!("At most {} rows can be imported at once", MAX_IMPORT_ROWS)).into());
+        }
+
+        let existing = Self::existing_keys(pool, user_id).await?;
+        let (results, changes) = Self::plan(rows, &existing, query.on_duplicate);
+
+        let mut response = ImportResponse {
+            dry_run,
+            applied: false,
+            created: Self::count(&results, ImportAction::Create),
+            updated: Self::count(&results, ImportAction::Update),
+            skipped: Self::count(&results, ImportAction::Skip),
+            invalid: Self::count(&results, ImportAction::Invalid),
+            rows: results,
+        };
+
+        if dry_run || response.invalid > 0 {
+            return Ok(response);
+        }
+
+        let mut tx = pool.begin().await.context("Failed to start import transaction")?;
+
+        for (result, change) in response.rows.iter_mut().zip(changes) {
+            match change {
+                PlannedChange::Create(item) => {
+                    let id = Self::create_item(&mut tx, user_id, item)
+                        .await
+                        .with_context(|| format!("Failed to import row {}", result.row))?;
+                    result.item_id = Some(id);
+                }
+                PlannedChange::Update(id, update) => {
+                    Self::update_item(&mut tx, user_id, id, update)
+                        .await
+                        .with_context(|| format!("Failed to import row {}", result.row))?;
+                }
+                PlannedChange::None => {}
+            }
+        }
+
+        tx.commit().await.context("Failed to commit import")?;
+        response.applied = true;
+
+        Ok(response)
+    }
+
+    async fn existing_keys(pool: &PgPool, user_id: Uuid) -> Result<HashMap<DuplicateKey, Uuid>> {
+        let mut keys = HashMap::new();
+
+        // Imports only touch personal games, so shared ones never count as
+        // duplicates to update.
+        let games = GameService::get_all_by_user(pool, user_id, GameFilter::default()).await?;
+        for game in games.into_iter().filter(|game| game.collection_id.is_none()) {
+            keys.insert(Self::duplicate_key(ImportKind::Game, &game.title, game.platform.as_deref()), game.id);
+        }
+        for manga in MangaService::get_all_by_user(pool, user_id).await? {
+            keys.insert(Self::duplicate_key(ImportKind::Manga, &manga.title, None), manga.id);
+        }
+        for novel in NovelService::get_all_by_user(pool, user_id).await? {
+            keys.insert(Self::duplicate_key(ImportKind::Novel, &novel.title, None), novel.id);
+        }
+        for movie in MovieService::get_all_by_user(pool, user_id).await? {
+            keys.insert(Self::duplicate_key(ImportKind::Movie, &movie.title, None), movie.id);
+        }
+
+        Ok(keys)
+    }
+
+    fn plan(
+        rows: Vec<ImportRow>,
+        existing: &HashMap<DuplicateKey, Uuid>,
+        on_duplicate: DuplicateStrategy,
+    ) -> (Vec<ImportRowResult>, Vec<PlannedChange>) {
+        let mut seen: HashMap<DuplicateKey, usize> = HashMap::new();
+        let mut results = Vec::with_capacity(rows.len());
+        let mut changes = Vec::with_capacity(rows.len());
+
+        for (index, row) in rows.into_iter().enumerate() {
+            let number = index + 1;
+            let item_type = row.get("item_type").map(String::as_str).unwrap_or("game");
+            let mut result = ImportRowResult {
+                row: number,
+                action: ImportAction::Skip,
+                item_type: Some(item_type.to_string()),
+                title: row.get("title").cloned(),
+                platform: row.get("platform").cloned(),
+                item_id: None,
+                reason: None,
+                errors: Vec::new(),
+            };
+
+            let Some(kind) = ImportKind::parse(item_type) else {
+                result.action = ImportAction::Invalid;
+                result.errors.push(format!("Unknown item type: {}", item_type));
+                results.push(result);
+                changes.push(PlannedChange::None);
+                continue;
+            };
+
+            let item = match Self::parse_item(kind, &row) {
+                Ok(item) => item,
+                Err(errors) => {
+                    result.action = ImportAction::Invalid;
+                    result.errors = errors;
+                    results.push(result);
+                    changes.push(PlannedChange::None);
+                    continue;
+                }
+            };
+
+            let key = Self::duplicate_key(kind, &result.title.clone().unwrap_or_default(), result.platform.as_deref());
+
+            if let Some(first) = seen.get(&key) {
+                result.reason = Some(format!("Duplicate of row {}", first));
+                results.push(result);
+                changes.push(PlannedChange::None);
+                continue;
+            }
+            seen.insert(key.clone(), number);
+
+            let (action, change) = match (existing.get(&key), on_duplicate) {
+                (Some(&id), DuplicateStrategy::Skip) => {
+                    result.item_id = Some(id);
+                    result.reason = Some("Already in the collection".to_string());
+                    (ImportAction::Skip, PlannedChange::None)
+                }
+                (Some(&id), DuplicateStrategy::Update) => {
+                    result.item_id = Some(id);
+                    let update = Self::to_update(&row, item);
+                    match Self::validate_update(&update) {
+                        Ok(()) => (ImportAction::Update, PlannedChange::Update(id, update)),
+                        Err(err) => {
+                            result.errors.push(err.to_string());
+                            (ImportAction::Invalid, PlannedChange::None)
+                        }
+                    }
+                }
+                (None, _) => match Self::validate_create(&item) {
+                    Ok(()) => (ImportAction::Create, PlannedChange::Create(item)),
+                    Err(err) => {
+                        result.errors.push(err.to_string());
+                        (ImportAction::Invalid, PlannedChange::None)
+                    }
+                },
+            };
+
+            result.action = action;
+            results.push(result);
+            changes.push(change);
+        }
+
+        (results, changes)
+    }
+
+    /// Builds a create request from a row, collecting every field error
+    /// rather than stopping at the first.
+    fn parse_item(kind: ImportKind, row: &ImportRow) -> Result<ImportItem, Vec<String>> {
+        let mut errors = Vec::new();
+
+        let title = row.get("title").cloned().unwrap_or_default();
+        if title.is_empty() {
+            errors.push("Title is required".to_string());
+        }
+
+        let genre = row.get("genre").cloned();
+        let notes = row.get("notes").cloned();
+        let rating = Self::parse_field(row, "rating", &mut errors, |value| value.parse::<i32>().ok());
+        let status = row.get("status").cloned().unwrap_or_else(|| Self::default_status(kind).to_string());
+
+        let item = match kind {
+            ImportKind::Game => ImportItem::Game(CreateGameRequest {
+                title,
+                genre,
+                rating,
+                status,
+                notes,
+                platform: row.get("platform").cloned(),
+                hours_played: Self::parse_field(row, "hours_played", &mut errors, |value| value.parse::<f64>().ok()),
+                completion_percent: Self::parse_field(row, "completion_percent", &mut errors, |value| {
+                    value.trim_end_matches('%').trim().parse::<i32>().ok()
+                }),
+                started_at: Self::parse_field(row, "started_at", &mut errors, Self::parse_timestamp),
+                finished_at: Self::parse_field(row, "finished_at", &mut errors, Self::parse_timestamp),
+            }),
+            ImportKind::Manga => ImportItem::Manga(CreateMangaRequest {
+                title,
+                genre,
+                rating,
+                status,
+                notes,
+                url: row.get("url").cloned(),
+                chapter: Self::parse_field(row, "chapter", &mut errors, |value| value.parse::<i32>().ok()),
+                page: Self::parse_field(row, "page", &mut errors, |value| value.parse::<i32>().ok()),
+                publication_status: row.get("publication_status").cloned(),
+            }),
+            ImportKind::Novel => ImportItem::Novel(CreateNovelRequest {
+                title,
+                genre,
+                rating,
+                status,
+                notes,
+                url: row.get("url").cloned(),
+                chapter: Self::parse_field(row, "chapter", &mut errors, |value| value.parse::<i32>().ok()),
+                page: Self::parse_field(row, "page", &mut errors, |value| value.parse::<i32>().ok()),
+            }),
+            ImportKind::Movie => ImportItem::Movie(CreateMovieRequest {
+                title,
+                genre,
+                rating,
+                status,
+                notes,
+            }),
+        };
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(item)
+    }
+
+    /// Only the fields present in the source row overwrite the existing
+    /// item; in particular a defaulted status is not applied.
+    fn to_update(row: &ImportRow, item: ImportItem) -> ImportUpdate {
+        let status = |status: String| row.contains_key("status").then_some(status);
+
+        match item {
+            ImportItem::Game(request) => ImportUpdate::Game(UpdateGameRequest {
+                title: Patch::Value(request.title),
+                genre: request.genre.into(),
+                rating: request.rating.into(),
+                status: status(request.status).into(),
+                notes: request.notes.into(),
+                platform: request.platform.into(),
+                hours_played: request.hours_played.into(),
+                completion_percent: request.completion_percent.into(),
+                started_at: request.started_at.into(),
+                finished_at: request.finished_at.into(),
+            }),
+            ImportItem::Manga(request) => ImportUpdate::Manga(UpdateMangaRequest {
+                title: Some(request.title),
+                genre: request.genre,
+                rating: request.rating,
+                status: status(request.status),
+                notes: request.notes,
+                url: request.url,
+                chapter: request.chapter,
+                page: request.page,
+                publication_status: request.publication_status,
+            }),
+            ImportItem::Novel(request) => ImportUpdate::Novel(UpdateNovelRequest {
+                title: Some(request.title),
+                genre: request.genre,
+                rating: request.rating,
+                status: status(request.status),
+                notes: request.notes,
+                url: request.url,
+                chapter: request.chapter,
+                page: request.page,
+            }),
+            ImportItem::Movie(request) => ImportUpdate::Movie(UpdateMovieRequest {
+                title: Some(request.title),
+                genre: request.genre,
+                rating: request.rating,
+                status: status(request.status),
+                notes: request.notes,
+            }),
+        }
+    }
+
+    fn validate_create(item: &ImportItem) -> Result<()> {
+        match item {
+            ImportItem::Game(request) => GameService::validate_create(request),
+            ImportItem::Manga(request) => MangaService::validate_create(request),
+            ImportItem::Novel(request) => NovelService::validate_create(request),
+            ImportItem::Movie(request) => MovieService::validate_create(request),
+        }
+    }
+
+    fn validate_update(update: &ImportUpdate) -> Result<()> {
+        match update {
+            ImportUpdate::Game(request) => GameService::validate_update(request),
+            ImportUpdate::Manga(request) => MangaService::validate_update(request),
+            ImportUpdate::Novel(request) => NovelService::validate_update(request),
+            ImportUpdate::Movie(request) => MovieService::validate_update(request),
+        }
+    }
+
+    async fn create_item(conn: &mut PgConnection, user_id: Uuid, item: ImportItem) -> Result<Uuid> {
+        let id = match item {
+            ImportItem::Game(request) => {
+                let game = game_sql::create_game(&mut *conn, user_id, None, request).await?;
+                HistoryService::record_imported(conn, user_id, ItemKind::Game, game.id, ItemAction::Create, None, &game).await?;
+                game.id
+            }
+            ImportItem::Manga(request) => {
+                let manga = manga_sql::create_manga(&mut *conn, user_id, request).await?;
+                HistoryService::record_imported(conn, user_id, ItemKind::Manga, manga.id, ItemAction::Create, None, &manga).await?;
+                manga.id
+            }
+            ImportItem::Novel(request) => {
+                let novel = novel_sql::create_novel(&mut *conn, user_id, request).await?;
+                HistoryService::record_imported(conn, user_id, ItemKind::Novel, novel.id, ItemAction::Create, None, &novel).await?;
+                novel.id
+            }
+            ImportItem::Movie(request) => movie_sql::create_movie(conn, user_id, request).await?.id,
+        };
+
+        Ok(id)
+    }
+
+    /// Items that went away since planning (e.g. trashed meanwhile) are left
+    /// alone.
+    async fn update_item(conn: &mut PgConnection, user_id: Uuid, id: Uuid, update: ImportUpdate) -> Result<()> {
+        match update {
+            ImportUpdate::Game(request) => {
+                let before = game_sql::find_game_for_update(&mut *conn, id)
+                    .await?
+                    .filter(|game| game.user_id == user_id && game.collection_id.is_none());
+                let Some(before) = before else {
+                    return Ok(());
+                };
+                if let Some(game) = game_sql::update_game(&mut *conn, id, request).await? {
+                    HistoryService::record_imported(conn, user_id, ItemKind::Game, id, ItemAction::Update, Some(&before), &game)
+                        .await?;
+                }
+            }
+            ImportUpdate::Manga(request) => {
+                let before = manga_sql::find_manga_for_update(&mut *conn, id, user_id).await?;
+                if let Some(manga) = manga_sql::update_manga(&mut *conn, id, user_id, request).await? {
+                    HistoryService::record_imported(conn, user_id, ItemKind::Manga, id, ItemAction::Update, before.as_ref(), &manga)
+                        .await?;
+                }
+            }
+            ImportUpdate::Novel(request) => {
+                let before = novel_sql::find_novel_for_update(&mut *conn, id, user_id).await?;
+                if let Some(novel) = novel_sql::update_novel(&mut *conn, id, user_id, request).await? {
+                    HistoryService::record_imported(conn, user_id, ItemKind::Novel, id, ItemAction::Update, before.as_ref(), &novel)
+                        .await?;
+                }
+            }
+            ImportUpdate::Movie(request) => {
+                movie_sql::update_movie(conn, id, user_id, request).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn default_status(kind: ImportKind) -> &'static str {
+        match kind {
+            ImportKind::Game => "Plan to Play",
+            ImportKind::Manga | ImportKind::Novel => "Plan to Read",
+            ImportKind::Movie => "Plan to Watch",
+        }
+    }
+
+    fn parse_field<T>(
+        row: &ImportRow,
+        field: &str,
+        errors: &mut Vec<String>,
+        parse: impl Fn(&str) -> Option<T>,
+    ) -> Option<T> {
+        let value = row.get(field)?;
+        let parsed = parse(value);
+        if parsed.is_none() {
+            errors.push(format!("Invalid {}: {}", field, value));
+        }
+        parsed
+    }
+
+    /// RFC 3339 timestamps or plain `YYYY-MM-DD` dates (taken as midnight UTC).
+    fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(value)
+            .map(|timestamp| timestamp.with_timezone(&Utc))
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    .map(|timestamp| timestamp.and_utc())
+            })
+    }
+
+    /// Case-insensitive title with punctuation dropped and whitespace
+    /// collapsed, plus the lowercased platform (games only).
+    fn duplicate_key(kind: ImportKind, title: &str, platform: Option<&str>) -> DuplicateKey {
+        let title = title
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let platform = match kind {
+            ImportKind::Game => platform.unwrap_or_default().trim().to_lowercase(),
+            _ => String::new(),
+        };
+
+        (kind, title, platform)
+    }
+
+    fn count(results: &[ImportRowResult], action: ImportAction) -> usize {
+        results.iter().filter(|result| result.action == action).count()
+    }
+}
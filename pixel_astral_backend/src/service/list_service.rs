@@ -0,0 +1,156 @@
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::sql::{item_sql, list_sql};
+use crate::type::item_type::ItemKind;
+use crate::type::list_type::{
+    AddListItemRequest, CreateListRequest, CustomList, ListItem, ListWithItems, UpdateListRequest,
+    MAX_LIST_NAME_LENGTH,
+};
+use sqlx::PgPool;
+
+pub struct ListService;
+
+impl ListService {
+    pub async fn create(
+        pool: &PgPool,
+        user_id: Uuid,
+        mut request: CreateListRequest,
+    ) -> Result<CustomList> {
+        request.name = Self::validate_name(&request.name)?;
+
+        list_sql::create_list(pool, user_id, request)
+            .await
+            .context("Failed to create list")
+    }
+
+    pub async fn get_all_by_user(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<CustomList>> {
+        list_sql::find_all_lists_by_user(pool, user_id)
+            .await
+            .context("Failed to get lists")
+    }
+
+    pub async fn get_by_id(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<ListWithItems>> {
+        let Some(list) = list_sql::find_list_by_id(pool, id, user_id)
+            .await
+            .context("Failed to get list")?
+        else {
+            return Ok(None);
+        };
+
+        let items = list_sql::find_list_items(pool, id)
+            .await
+            .context("Failed to get list items")?;
+
+        Ok(Some(ListWithItems { list, items }))
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        mut request: UpdateListRequest,
+    ) -> Result<Option<CustomList>> {
+        if let Some(name) = &request.name {
+            request.name = Some(Self::validate_name(name)?);
+        }
+
+        list_sql::update_list(pool, id, user_id, request)
+            .await
+            .context("Failed to update list")
+    }
+
+    pub async fn delete(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool> {
+        list_sql::delete_list(pool, id, user_id)
+            .await
+            .context("Failed to delete list")
+    }
+
+    /// Adds an item the user owns to the list. `None` if the list or item
+    /// does not exist.
+    pub async fn add_item(
+        pool: &PgPool,
+        list_id: Uuid,
+        user_id: Uuid,
+        request: AddListItemRequest,
+    ) -> Result<Option<ListItem>> {
+        if request.position.is_some_and(|position| position < 0) {
+            return Err(AppError::Validation("Position must be zero or more".to_string()).into());
+        }
+
+        if !item_sql::item_exists(pool, request.item_type, request.item_id, user_id)
+            .await
+            .context("Failed to find item")?
+        {
+            return Ok(None);
+        }
+
+        list_sql::add_list_item(pool, list_id, user_id, request.item_type, request.item_id, request.position)
+            .await
+            .map_err(|err| match &err {
+                sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                    AppError::Conflict("Item is already in the list".to_string()).into()
+                }
+                _ => anyhow::Error::new(err).context("Failed to add list item"),
+            })
+    }
+
+    pub async fn move_item(
+        pool: &PgPool,
+        list_id: Uuid,
+        user_id: Uuid,
+        kind: ItemKind,
+        item_id: Uuid,
+        position: i32,
+    ) -> Result<Option<ListItem>> {
+        if position < 0 {
+            return Err(AppError::Validation("Position must be zero or more".to_string()).into());
+        }
+
+        list_sql::move_list_item(pool, list_id, user_id, kind, item_id, position)
+            .await
+            .context("Failed to move list item")
+    }
+
+    pub async fn remove_item(
+        pool: &PgPool,
+        list_id: Uuid,
+        user_id: Uuid,
+        kind: ItemKind,
+        item_id: Uuid,
+    ) -> Result<bool> {
+        list_sql::remove_list_item(pool, list_id, user_id, kind, item_id)
+            .await
+            .context("Failed to remove list item")
+    }
+
+    fn validate_name(name: &str) -> Result<String> {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err(AppError::Validation("List name is required".to_string()).into());
+        }
+
+        if name.chars().count() > MAX_LIST_NAME_LENGTH {
+            return Err(AppError::Validation(format!(
+                "List name must be at most {} characters",
+                MAX_LIST_NAME_LENGTH
+            ))
+            .into());
+        }
+
+        Ok(name.to_string())
+    }
+}
@@ -0,0 +1,326 @@
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::service::history_service::HistoryService;
+use crate::blob_store::BlobStore;
+use crate::service::cover_service::CoverService;
+use crate::sql::{cover_sql, manga_sql};
+use crate::type::cover_type::Cover;
+use crate::type::history_type::{ItemAction, ItemEvent};
+use crate::type::item_type::ItemKind;
+use crate::type::manga_type::{
+    CreateMangaRequest, Manga, MangaProgressRequest, UpdateMangaRequest, MANGA_PUBLICATION_STATUSES,
+    MANGA_STATUSES,
+};
+use sqlx::{PgConnection, PgPool};
+
+pub struct MangaService;
+
+impl MangaService {
+    pub async fn create(
+        pool: &PgPool,
+        user_id: Uuid,
+        request: CreateMangaRequest,
+    ) -> Result<Manga> {
+        Self::validate_create(&request)?;
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let manga = manga_sql::create_manga(&mut *tx, user_id, request)
+            .await
+            .context("Failed to create manga")?;
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Manga, manga.id, ItemAction::Create, None, &manga).await?;
+        tx.commit().await.context("Failed to commit manga")?;
+
+        Ok(manga)
+    }
+
+    pub async fn get_all_by_user(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<Manga>> {
+        manga_sql::find_all_manga_by_user(pool, user_id)
+            .await
+            .context("Failed to get manga")
+    }
+
+    pub async fn get_by_id(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Manga>> {
+        manga_sql::find_manga_by_id(pool, id, user_id)
+            .await
+            .context("Failed to get manga")
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        request: UpdateMangaRequest,
+    ) -> Result<Option<Manga>> {
+        Self::validate_update(&request)?;
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(before) = Self::lock_live(&mut tx, id, user_id).await? else {
+            return Ok(None);
+        };
+        let manga = manga_sql::update_manga(&mut *tx, id, user_id, request)
+            .await
+            .context("Failed to update manga")?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Manga, id, ItemAction::Update, Some(&before), &manga).await?;
+        tx.commit().await.context("Failed to commit manga")?;
+
+        Ok(Some(manga))
+    }
+
+    pub async fn advance_progress(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        request: MangaProgressRequest,
+    ) -> Result<Option<Manga>> {
+        if request.chapters == 0 && request.pages == 0 {
+            return Err(AppError::Validation("Progress must change chapters or pages".to_string()).into());
+        }
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(before) = Self::lock_live(&mut tx, id, user_id).await? else {
+            return Ok(None);
+        };
+        let manga = manga_sql::advance_manga_progress(&mut *tx, id, user_id, request)
+            .await
+            .context("Failed to update manga progress")?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Manga, id, ItemAction::Update, Some(&before), &manga).await?;
+        tx.commit().await.context("Failed to commit manga")?;
+
+        Ok(Some(manga))
+    }
+
+    pub async fn delete(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool> {
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(before) = Self::lock_live(&mut tx, id, user_id).await? else {
+            return Ok(false);
+        };
+        let manga = manga_sql::delete_manga(&mut *tx, id, user_id)
+            .await
+            .context("Failed to delete manga")?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Manga, id, ItemAction::Delete, Some(&before), &manga).await?;
+        tx.commit().await.context("Failed to commit manga")?;
+
+        Ok(true)
+    }
+
+    pub async fn get_trash(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<Manga>> {
+        manga_sql::find_trashed_manga_by_user(pool, user_id)
+            .await
+            .context("Failed to get trashed manga")
+    }
+
+    pub async fn restore(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Manga>> {
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let before = manga_sql::find_manga_for_update(&mut *tx, id, user_id)
+            .await
+            .context("Failed to get manga")?;
+        let Some(manga) = manga_sql::restore_manga(&mut *tx, id, user_id)
+            .await
+            .context("Failed to restore manga")?
+        else {
+            return Ok(None);
+        };
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Manga, id, ItemAction::Restore, before.as_ref(), &manga).await?;
+        tx.commit().await.context("Failed to commit manga")?;
+
+        Ok(Some(manga))
+    }
+
+    /// Deletes the manga for good, along with its cover files.
+    pub async fn purge(
+        pool: &PgPool,
+        store: &dyn BlobStore,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool> {
+        let Some(cover) = manga_sql::purge_manga(pool, id, user_id)
+            .await
+            .context("Failed to purge manga")?
+        else {
+            return Ok(false);
+        };
+
+        CoverService::delete_files(store, &[cover]).await;
+
+        Ok(true)
+    }
+
+    pub async fn empty_trash(
+        pool: &PgPool,
+        store: &dyn BlobStore,
+        user_id: Uuid,
+    ) -> Result<u64> {
+        let covers = manga_sql::purge_trashed_manga_by_user(pool, user_id)
+            .await
+            .context("Failed to empty manga trash")?;
+
+        CoverService::delete_files(store, &covers).await;
+
+        Ok(covers.len() as u64)
+    }
+
+    pub async fn get_history(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Vec<ItemEvent>>> {
+        HistoryService::get_history(pool, user_id, ItemKind::Manga, id).await
+    }
+
+    /// Writes the manga back to how it was right after `event_id`.
+    pub async fn revert(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        event_id: Uuid,
+    ) -> Result<Option<Manga>> {
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(before) = Self::lock_live(&mut tx, id, user_id).await? else {
+            return Ok(None);
+        };
+        let version: Manga = HistoryService::find_version(&mut tx, ItemKind::Manga, id, event_id).await?;
+        let manga = manga_sql::revert_manga(&mut *tx, id, user_id, version)
+            .await
+            .context("Failed to revert manga")?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record_revert(&mut tx, user_id, ItemKind::Manga, id, event_id, &before, &manga).await?;
+        tx.commit().await.context("Failed to commit manga")?;
+
+        Ok(Some(manga))
+    }
+
+    /// Points the manga at a new cover, or none, in the caller's transaction
+    /// and returns the cover it replaced, whose files the caller deletes
+    /// once the transaction commits.
+    pub async fn set_cover(
+        conn: &mut PgConnection,
+        id: Uuid,
+        user_id: Uuid,
+        cover: &Cover,
+    ) -> Result<Option<Cover>> {
+        let Some(before) = Self::lock_live(conn, id, user_id).await? else {
+            return Ok(None);
+        };
+        let manga = cover_sql::update_manga_cover(&mut *conn, id, cover)
+            .await
+            .context("Failed to save cover")?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record(conn, user_id, ItemKind::Manga, id, ItemAction::Update, Some(&before), &manga).await?;
+
+        Ok(Some(Cover {
+            cover_url: before.cover_url,
+            cover_medium_url: before.cover_medium_url,
+            cover_small_url: before.cover_small_url,
+        }))
+    }
+
+    /// Locks the manga if it exists and is not in the trash.
+    async fn lock_live(
+        conn: &mut PgConnection,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Manga>> {
+        let manga = manga_sql::find_manga_for_update(conn, id, user_id)
+            .await
+            .context("Failed to get manga")?;
+
+        Ok(manga.filter(|manga| manga.deleted_at.is_none()))
+    }
+
+    pub fn validate_create(request: &CreateMangaRequest) -> Result<()> {
+        if request.title.trim().is_empty() {
+            return Err(AppError::Validation("Title is required".to_string()).into());
+        }
+
+        Self::validate_fields(
+            Some(&request.status),
+            request.rating,
+            request.url.as_deref(),
+            request.chapter,
+            request.page,
+            request.publication_status.as_deref(),
+        )
+    }
+
+    pub fn validate_update(request: &UpdateMangaRequest) -> Result<()> {
+        if request.title.as_deref().is_some_and(|title| title.trim().is_empty()) {
+            return Err(AppError::Validation("Title must not be empty".to_string()).into());
+        }
+
+        Self::validate_fields(
+            request.status.as_deref(),
+            request.rating,
+            request.url.as_deref(),
+            request.chapter,
+            request.page,
+            request.publication_status.as_deref(),
+        )
+    }
+
+    fn validate_fields(
+        status: Option<&str>,
+        rating: Option<i32>,
+        url: Option<&str>,
+        chapter: Option<i32>,
+        page: Option<i32>,
+        publication_status: Option<&str>,
+    ) -> Result<()> {
+        let invalid = |message: String| -> Result<()> { Err(AppError::Validation(message).into()) };
+
+        if let Some(status) = status {
+            if !MANGA_STATUSES.contains(&status) {
+                return invalid(format!("Unknown status: {}", status));
+            }
+        }
+
+        if rating.is_some_and(|rating| !(1..=5).contains(&rating)) {
+            return invalid("Rating must be between 1 and 5".to_string());
+        }
+
+        if url.is_some_and(|url| !url.starts_with("https://") && !url.starts_with("http://")) {
+            return invalid("URL must start with http:// or https://".to_string());
+        }
+
+        if chapter.is_some_and(|chapter| chapter < 0) || page.is_some_and(|page| page < 0) {
+            return invalid("Chapter and page must be zero or more".to_string());
+        }
+
+        if let Some(publication_status) = publication_status {
+            if !MANGA_PUBLICATION_STATUSES.contains(&publication_status) {
+                return invalid(format!("Unknown publication status: {}", publication_status));
+            }
+        }
+
+        Ok(())
+    }
+}
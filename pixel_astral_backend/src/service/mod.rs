@@ -1,2 +1,25 @@
+pub mod activity_service;
+pub mod collection_service;
+pub mod cover_service;
+pub mod event_service;
+pub mod export_service;
 pub mod game_service;
+pub mod history_service;
+pub mod import_parser_service;
+pub mod import_service;
+pub mod list_service;
+pub mod manga_service;
+pub mod movie_service;
+pub mod novel_service;
+pub mod password_service;
+pub mod profile_service;
+pub mod public_service;
+pub mod recommendation_service;
+pub mod review_service;
+pub mod share_service;
+pub mod social_service;
+pub mod stats_service;
+pub mod sync_service;
+pub mod tag_service;
+pub mod trash_service;
 pub mod user_service;
@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::sql::movie_sql;
+use crate::type::movie_type::{CreateMovieRequest, Movie, UpdateMovieRequest, MOVIE_STATUSES};
+use sqlx::PgPool;
+
+/// Movies can be read and exported; for now they are only written by
+/// imports.
+pub struct MovieService;
+
+impl MovieService {
+    pub async fn get_all_by_user(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<Movie>> {
+        movie_sql::find_all_movies_by_user(pool, user_id)
+            .await
+            .context("Failed to get movies")
+    }
+
+    pub async fn get_by_id(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Movie>> {
+        movie_sql::find_movie_by_id(pool, id, user_id)
+            .await
+            .context("Failed to get movie")
+    }
+
+    pub fn validate_create(request: &CreateMovieRequest) -> Result<()> {
+        if request.title.trim().is_empty() {
+            return Err(AppError::Validation("Title is required".to_string()).into());
+        }
+
+        Self::validate_fields(Some(&request.status), request.rating)
+    }
+
+    pub fn validate_update(request: &UpdateMovieRequest) -> Result<()> {
+        if request.title.as_deref().is_some_and(|title| title.trim().is_empty()) {
+            return Err(AppError::Validation("Title must not be empty".to_string()).into());
+        }
+
+        Self::validate_fields(request.status.as_deref(), request.rating)
+    }
+
+    fn validate_fields(status: Option<&str>, rating: Option<i32>) -> Result<()> {
+        if let Some(status) = status {
+            if !MOVIE_STATUSES.contains(&status) {
+                return Err(AppError::Validation(format!("Unknown status: {}", status)).into());
+            }
+        }
+
+        if rating.is_some_and(|rating| !(1..=5).contains(&rating)) {
+            return Err(AppError::Validation("Rating must be between 1 and 5".to_string()).into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,316 @@
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::service::history_service::HistoryService;
+use crate::blob_store::BlobStore;
+use crate::service::cover_service::CoverService;
+use crate::sql::{cover_sql, novel_sql};
+use crate::type::cover_type::Cover;
+use crate::type::history_type::{ItemAction, ItemEvent};
+use crate::type::item_type::ItemKind;
+use crate::type::novel_type::{
+    CreateNovelRequest, Novel, NovelProgressRequest, UpdateNovelRequest, NOVEL_STATUSES,
+};
+use sqlx::{PgConnection, PgPool};
+
+pub struct NovelService;
+
+impl NovelService {
+    pub async fn create(
+        pool: &PgPool,
+        user_id: Uuid,
+        request: CreateNovelRequest,
+    ) -> Result<Novel> {
+        Self::validate_create(&request)?;
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let novel = novel_sql::create_novel(&mut *tx, user_id, request)
+            .await
+            .context("Failed to create novel")?;
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Novel, novel.id, ItemAction::Create, None, &novel).await?;
+        tx.commit().await.context("Failed to commit novel")?;
+
+        Ok(novel)
+    }
+
+    pub async fn get_all_by_user(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<Novel>> {
+        novel_sql::find_all_novels_by_user(pool, user_id)
+            .await
+            .context("Failed to get novels")
+    }
+
+    pub async fn get_by_id(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Novel>> {
+        novel_sql::find_novel_by_id(pool, id, user_id)
+            .await
+            .context("Failed to get novel")
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        request: UpdateNovelRequest,
+    ) -> Result<Option<Novel>> {
+        Self::validate_update(&request)?;
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(before) = Self::lock_live(&mut tx, id, user_id).await? else {
+            return Ok(None);
+        };
+        let novel = novel_sql::update_novel(&mut *tx, id, user_id, request)
+            .await
+            .context("Failed to update novel")?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Novel, id, ItemAction::Update, Some(&before), &novel).await?;
+        tx.commit().await.context("Failed to commit novel")?;
+
+        Ok(Some(novel))
+    }
+
+    pub async fn advance_progress(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        request: NovelProgressRequest,
+    ) -> Result<Option<Novel>> {
+        if request.chapters == 0 && request.pages == 0 {
+            return Err(AppError::Validation("Progress must change chapters or pages".to_string()).into());
+        }
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(before) = Self::lock_live(&mut tx, id, user_id).await? else {
+            return Ok(None);
+        };
+        let novel = novel_sql::advance_novel_progress(&mut *tx, id, user_id, request)
+            .await
+            .context("Failed to update novel progress")?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Novel, id, ItemAction::Update, Some(&before), &novel).await?;
+        tx.commit().await.context("Failed to commit novel")?;
+
+        Ok(Some(novel))
+    }
+
+    pub async fn delete(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool> {
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(before) = Self::lock_live(&mut tx, id, user_id).await? else {
+            return Ok(false);
+        };
+        let novel = novel_sql::delete_novel(&mut *tx, id, user_id)
+            .await
+            .context("Failed to delete novel")?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Novel, id, ItemAction::Delete, Some(&before), &novel).await?;
+        tx.commit().await.context("Failed to commit novel")?;
+
+        Ok(true)
+    }
+
+    pub async fn get_trash(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<Novel>> {
+        novel_sql::find_trashed_novels_by_user(pool, user_id)
+            .await
+            .context("Failed to get trashed novels")
+    }
+
+    pub async fn restore(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Novel>> {
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let before = novel_sql::find_novel_for_update(&mut *tx, id, user_id)
+            .await
+            .context("Failed to get novel")?;
+        let Some(novel) = novel_sql::restore_novel(&mut *tx, id, user_id)
+            .await
+            .context("Failed to restore novel")?
+        else {
+            return Ok(None);
+        };
+
+        HistoryService::record(&mut tx, user_id, ItemKind::Novel, id, ItemAction::Restore, before.as_ref(), &novel).await?;
+        tx.commit().await.context("Failed to commit novel")?;
+
+        Ok(Some(novel))
+    }
+
+    /// Deletes the novel for good, along with its cover files.
+    pub async fn purge(
+        pool: &PgPool,
+        store: &dyn BlobStore,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool> {
+        let Some(cover) = novel_sql::purge_novel(pool, id, user_id)
+            .await
+            .context("Failed to purge novel")?
+        else {
+            return Ok(false);
+        };
+
+        CoverService::delete_files(store, &[cover]).await;
+
+        Ok(true)
+    }
+
+    pub async fn empty_trash(
+        pool: &PgPool,
+        store: &dyn BlobStore,
+        user_id: Uuid,
+    ) -> Result<u64> {
+        let covers = novel_sql::purge_trashed_novels_by_user(pool, user_id)
+            .await
+            .context("Failed to empty novel trash")?;
+
+        CoverService::delete_files(store, &covers).await;
+
+        Ok(covers.len() as u64)
+    }
+
+    pub async fn get_history(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Vec<ItemEvent>>> {
+        HistoryService::get_history(pool, user_id, ItemKind::Novel, id).await
+    }
+
+    /// Writes the novel back to how it was right after `event_id`.
+    pub async fn revert(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        event_id: Uuid,
+    ) -> Result<Option<Novel>> {
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+        let Some(before) = Self::lock_live(&mut tx, id, user_id).await? else {
+            return Ok(None);
+        };
+        let version: Novel = HistoryService::find_version(&mut tx, ItemKind::Novel, id, event_id).await?;
+        let novel = novel_sql::revert_novel(&mut *tx, id, user_id, version)
+            .await
+            .context("Failed to revert novel")?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record_revert(&mut tx, user_id, ItemKind::Novel, id, event_id, &before, &novel).await?;
+        tx.commit().await.context("Failed to commit novel")?;
+
+        Ok(Some(novel))
+    }
+
+    /// Points the novel at a new cover, or none, in the caller's transaction
+    /// and returns the cover it replaced, whose files the caller deletes
+    /// once the transaction commits.
+    pub async fn set_cover(
+        conn: &mut PgConnection,
+        id: Uuid,
+        user_id: Uuid,
+        cover: &Cover,
+    ) -> Result<Option<Cover>> {
+        let Some(before) = Self::lock_live(conn, id, user_id).await? else {
+            return Ok(None);
+        };
+        let novel = cover_sql::update_novel_cover(&mut *conn, id, cover)
+            .await
+            .context("Failed to save cover")?
+            .ok_or(AppError::NotFound)?;
+
+        HistoryService::record(conn, user_id, ItemKind::Novel, id, ItemAction::Update, Some(&before), &novel).await?;
+
+        Ok(Some(Cover {
+            cover_url: before.cover_url,
+            cover_medium_url: before.cover_medium_url,
+            cover_small_url: before.cover_small_url,
+        }))
+    }
+
+    /// Locks the novel if it exists and is not in the trash.
+    async fn lock_live(
+        conn: &mut PgConnection,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Novel>> {
+        let novel = novel_sql::find_novel_for_update(conn, id, user_id)
+            .await
+            .context("Failed to get novel")?;
+
+        Ok(novel.filter(|novel| novel.deleted_at.is_none()))
+    }
+
+    pub fn validate_create(request: &CreateNovelRequest) -> Result<()> {
+        if request.title.trim().is_empty() {
+            return Err(AppError::Validation("Title is required".to_string()).into());
+        }
+
+        Self::validate_fields(
+            Some(&request.status),
+            request.rating,
+            request.url.as_deref(),
+            request.chapter,
+            request.page,
+        )
+    }
+
+    pub fn validate_update(request: &UpdateNovelRequest) -> Result<()> {
+        if request.title.as_deref().is_some_and(|title| title.trim().is_empty()) {
+            return Err(AppError::Validation("Title must not be empty".to_string()).into());
+        }
+
+        Self::validate_fields(
+            request.status.as_deref(),
+            request.rating,
+            request.url.as_deref(),
+            request.chapter,
+            request.page,
+        )
+    }
+
+    fn validate_fields(
+        status: Option<&str>,
+        rating: Option<i32>,
+        url: Option<&str>,
+        chapter: Option<i32>,
+        page: Option<i32>,
+    ) -> Result<()> {
+        let invalid = |message: String| -> Result<()> { Err(AppError::Validation(message).into()) };
+
+        if let Some(status) = status {
+            if !NOVEL_STATUSES.contains(&status) {
+                return invalid(format!("Unknown status: {}", status));
+            }
+        }
+
+        if rating.is_some_and(|rating| !(1..=5).contains(&rating)) {
+            return invalid("Rating must be between 1 and 5".to_string());
+        }
+
+        if url.is_some_and(|url| !url.starts_with("https://") && !url.starts_with("http://")) {
+            return invalid("URL must start with http:// or https://".to_string());
+        }
+
+        if chapter.is_some_and(|chapter| chapter < 0) || page.is_some_and(|page| page < 0) {
+            return invalid("Chapter and page must be zero or more".to_string());
+        }
+
+        Ok(())
+    }
+}
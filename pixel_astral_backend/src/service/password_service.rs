@@ -0,0 +1,173 @@
+use anyhow::{Context, Result};
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use std::collections::HashSet;
+
+use crate::config::Config;
+use crate::error::AppError;
+
+/// Bcrypt only looks at the first 72 bytes; the cap also bounds hashing work.
+const MAX_PASSWORD_LENGTH: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Bcrypt { cost: u32 },
+    Argon2id { memory_kib: u32, iterations: u32, parallelism: u32 },
+}
+
+/// Password policy and hashing, configured once at startup.
+///
+/// Hashes are self-describing (`$2b$...` or `$argon2id$...`), so existing
+/// hashes keep verifying after the configured algorithm changes and are
+/// upgraded on the next successful login via `needs_rehash`.
+pub struct PasswordService {
+    algorithm: HashAlgorithm,
+    min_length: usize,
+    breached: HashSet<String>,
+    dummy_hash: String,
+}
+
+impl PasswordService {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let algorithm = match config.password_hash_algorithm.as_str() {
+            "bcrypt" => HashAlgorithm::Bcrypt {
+                cost: config.bcrypt_cost,
+            },
+            "argon2id" => HashAlgorithm::Argon2id {
+                memory_kib: config.argon2_memory_kib,
+                iterations: config.argon2_iterations,
+                parallelism: config.argon2_parallelism,
+            },
+            other => return Err(anyhow::anyhow!("Unknown PASSWORD_HASH_ALGORITHM: {}", other)),
+        };
+
+        let breached = match &config.breached_passwords_file {
+            Some(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read breached password list {}", path))?
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+            None => HashSet::new(),
+        };
+
+        Self::new(algorithm, config.password_min_length, breached)
+    }
+
+    pub fn new(algorithm: HashAlgorithm, min_length: usize, breached: HashSet<String>) -> Result<Self> {
+        let mut service = Self {
+            algorithm,
+            min_length,
+            breached,
+            dummy_hash: String::new(),
+        };
+        service.dummy_hash = service.hash("pixel-astral-dummy-password")?;
+
+        Ok(service)
+    }
+
+    pub fn validate(&self, password: &str) -> Result<()> {
+        let length = password.chars().count();
+
+        if length < self.min_length {
+            return Err(AppError::Validation(format!(
+                "Password must be at least {} characters",
+                self.min_length
+            ))
+            .into());
+        }
+
+        if length > MAX_PASSWORD_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Password must be at most {} characters",
+                MAX_PASSWORD_LENGTH
+            ))
+            .into());
+        }
+
+        if self.breached.contains(password) {
+            return Err(AppError::Validation(
+                "Password appears in a list of breached passwords".to_string(),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    pub fn hash(&self, password: &str) -> Result<String> {
+        match &self.algorithm {
+            HashAlgorithm::Bcrypt { cost } => {
+                bcrypt::hash(password, *cost).context("Failed to hash password")
+            }
+            HashAlgorithm::Argon2id { .. } => {
+                let salt = SaltString::generate(&mut OsRng);
+                self.argon2()?
+                    .hash_password(password.as_bytes(), &salt)
+                    .map(|hash| hash.to_string())
+                    .map_err(|err| anyhow::anyhow!("Failed to hash password: {}", err))
+            }
+        }
+    }
+
+    pub fn verify(&self, password: &str, hash: &str) -> Result<bool> {
+        if hash.starts_with("$argon2") {
+            let parsed = PasswordHash::new(hash)
+                .map_err(|err| anyhow::anyhow!("Invalid argon2 hash: {}", err))?;
+
+            return Ok(Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok());
+        }
+
+        bcrypt::verify(password, hash).context("Failed to verify password")
+    }
+
+    /// Whether `hash` was produced with a different algorithm or cost than
+    /// the one currently configured.
+    pub fn needs_rehash(&self, hash: &str) -> bool {
+        match &self.algorithm {
+            HashAlgorithm::Bcrypt { cost } => match hash.split('$').nth(2) {
+                Some(hash_cost) if hash.starts_with("$2") => hash_cost.parse::<u32>().ok() != Some(*cost),
+                _ => true,
+            },
+            HashAlgorithm::Argon2id { memory_kib, iterations, parallelism } => {
+                let Ok(parsed) = PasswordHash::new(hash) else {
+                    return true;
+                };
+
+                if parsed.algorithm != Algorithm::Argon2id.ident() {
+                    return true;
+                }
+
+                match Params::try_from(&parsed) {
+                    Ok(params) => {
+                        params.m_cost() != *memory_kib
+                            || params.t_cost() != *iterations
+                            || params.p_cost() != *parallelism
+                    }
+                    Err(_) => true,
+                }
+            }
+        }
+    }
+
+    /// A hash of a throwaway password made with the configured algorithm, so
+    /// checks against unknown accounts take as long as real ones.
+    pub fn dummy_hash(&self) -> &str {
+        &self.dummy_hash
+    }
+
+    fn argon2(&self) -> Result<Argon2<'static>> {
+        let HashAlgorithm::Argon2id { memory_kib, iterations, parallelism } = self.algorithm else {
+            return Err(anyhow::anyhow!("Argon2id is not the configured algorithm"));
+        };
+
+        let params = Params::new(memory_kib, iterations, parallelism, None)
+            .map_err(|err| anyhow::anyhow!("Invalid argon2 parameters: {}", err))?;
+
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+}
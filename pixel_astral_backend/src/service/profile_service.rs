@@ -0,0 +1,156 @@
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::sql::profile_sql;
+use crate::type::profile_type::{
+    UpdateProfileRequest, UserProfile, PROFILE_THEMES, PROFILE_VIEWS, RESERVED_HANDLES,
+};
+use crate::type::public_type::VISIBILITIES;
+use sqlx::PgPool;
+
+const HANDLE_MIN_LENGTH: usize = 3;
+const HANDLE_MAX_LENGTH: usize = 30;
+const DISPLAY_NAME_MAX_LENGTH: usize = 50;
+const BIO_MAX_LENGTH: usize = 500;
+const AVATAR_URL_MAX_LENGTH: usize = 2048;
+
+pub struct ProfileService;
+
+impl ProfileService {
+    pub async fn get(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<UserProfile> {
+        profile_sql::find_or_create_profile(pool, user_id)
+            .await
+            .context("Failed to get profile")
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        user_id: Uuid,
+        mut request: UpdateProfileRequest,
+    ) -> Result<UserProfile> {
+        request.display_name = request.display_name.map(|name| name.trim().to_string());
+        request.bio = request.bio.map(|bio| bio.trim().to_string());
+        request.handle = request.handle.map(|handle| handle.trim().to_lowercase());
+
+        Self::validate(&request)?;
+
+        let current = profile_sql::find_or_create_profile(pool, user_id)
+            .await
+            .context("Failed to get profile")?;
+
+        if let Some(handle) = request.handle.value() {
+            if current.handle.as_deref() != Some(handle.as_str()) {
+                let taken = profile_sql::find_profile_by_handle(pool, handle)
+                    .await
+                    .context("Failed to check handle")?
+                    .is_some();
+
+                if taken {
+                    return Err(AppError::Conflict("Handle is already taken".to_string()).into());
+                }
+            }
+        }
+
+        profile_sql::update_profile(pool, user_id, request)
+            .await
+            .map_err(|err| match &err {
+                sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                    AppError::Conflict("Handle is already taken".to_string()).into()
+                }
+                _ => anyhow::Error::new(err).context("Failed to update profile"),
+            })?
+            .ok_or_else(|| AppError::NotFound.into())
+    }
+
+    fn validate(request: &UpdateProfileRequest) -> Result<()> {
+        let invalid = |message: &str| -> Result<()> {
+            Err(AppError::Validation(message.to_string()).into())
+        };
+
+        if let Some(handle) = request.handle.value() {
+            Self::validate_handle(handle)?;
+        }
+
+        if let Some(name) = request.display_name.value() {
+            if name.is_empty() || name.chars().count() > DISPLAY_NAME_MAX_LENGTH {
+                return invalid("Display name must be 1-50 characters");
+            }
+        }
+
+        if let Some(bio) = request.bio.value() {
+            if bio.chars().count() > BIO_MAX_LENGTH {
+                return invalid("Bio must be at most 500 characters");
+            }
+        }
+
+        if let Some(url) = request.avatar_url.value() {
+            let is_http = url.starts_with("https://") || url.starts_with("http://");
+            if !is_http || url.len() > AVATAR_URL_MAX_LENGTH {
+                return invalid("Avatar must be an http(s) URL");
+            }
+        }
+
+        if let Some(theme) = &request.theme {
+            if !PROFILE_THEMES.contains(&theme.as_str()) {
+                return invalid("Unknown theme");
+            }
+        }
+
+        if let Some(view) = &request.default_view {
+            if !PROFILE_VIEWS.contains(&view.as_str()) {
+                return invalid("Unknown collection view");
+            }
+        }
+
+        if let Some(locale) = &request.locale {
+            if !Self::is_valid_locale(locale) {
+                return invalid("Locale must look like `en` or `en-US`");
+            }
+        }
+
+        if let Some(visibility) = &request.visibility {
+            if !VISIBILITIES.contains(&visibility.as_str()) {
+                return invalid("Visibility must be private, unlisted or public");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handles are lowercase ASCII letters, digits and underscores, start
+    /// with a letter and avoid the reserved list.
+    pub fn validate_handle(handle: &str) -> Result<()> {
+        let length = handle.len();
+        let is_valid = (HANDLE_MIN_LENGTH..=HANDLE_MAX_LENGTH).contains(&length)
+            && handle.starts_with(|c: char| c.is_ascii_lowercase())
+            && handle.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+        if !is_valid {
+            return Err(AppError::Validation(
+                "Handle must be 3-30 lowercase letters, digits or underscores, starting with a letter".to_string(),
+            )
+            .into());
+        }
+
+        if RESERVED_HANDLES.contains(&handle) {
+            return Err(AppError::Validation("Handle is reserved".to_string()).into());
+        }
+
+        Ok(())
+    }
+
+    fn is_valid_locale(locale: &str) -> bool {
+        let mut parts = locale.split('-');
+        let language = parts.next().unwrap_or_default();
+        let region = parts.next();
+
+        (2..=3).contains(&language.len())
+            && language.chars().all(|c| c.is_ascii_lowercase())
+            && region.is_none_or(|region| region.len() == 2 && region.chars().all(|c| c.is_ascii_uppercase()))
+            && parts.next().is_none()
+    }
+}
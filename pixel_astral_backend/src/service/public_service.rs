@@ -0,0 +1,88 @@
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::sql::{item_sql, profile_sql, public_sql};
+use crate::type::item_type::ItemKind;
+use crate::type::profile_type::UserProfile;
+use crate::type::public_type::{
+    ItemVisibility, PublicCollection, PublicProfile, SetItemVisibilityRequest, VISIBILITIES,
+};
+use sqlx::PgPool;
+
+pub struct PublicService;
+
+impl PublicService {
+    /// The games of a public profile. Unknown handles and profiles that are
+    /// not public are both `NotFound`, so the two cannot be told apart.
+    pub async fn get_public_games(pool: &PgPool, handle: &str) -> Result<PublicCollection> {
+        let profile = profile_sql::find_profile_by_handle(pool, handle)
+            .await
+            .context("Failed to get profile")?
+            .filter(|profile| profile.visibility == "public")
+            .ok_or(AppError::NotFound)?;
+
+        let games = public_sql::find_public_games(pool, profile.user_id)
+            .await
+            .context("Failed to get public games")?;
+
+        Ok(PublicCollection {
+            profile: Self::public_profile(profile),
+            games,
+        })
+    }
+
+    pub fn public_profile(profile: UserProfile) -> PublicProfile {
+        PublicProfile {
+            handle: profile.handle,
+            display_name: profile.display_name,
+            bio: profile.bio,
+            avatar_url: profile.avatar_url,
+        }
+    }
+
+    pub async fn get_item_visibilities(pool: &PgPool, user_id: Uuid) -> Result<Vec<ItemVisibility>> {
+        public_sql::find_item_visibilities(pool, user_id)
+            .await
+            .context("Failed to get item visibility")
+    }
+
+    /// Sets or, with `null`, removes an item's override. Returns the
+    /// override left in place, if any.
+    pub async fn set_item_visibility(
+        pool: &PgPool,
+        user_id: Uuid,
+        kind: ItemKind,
+        item_id: Uuid,
+        request: SetItemVisibilityRequest,
+    ) -> Result<Option<ItemVisibility>> {
+        if let Some(visibility) = &request.visibility {
+            if !VISIBILITIES.contains(&visibility.as_str()) {
+                return Err(AppError::Validation(
+                    "Visibility must be private, unlisted or public".to_string(),
+                )
+                .into());
+            }
+        }
+
+        let exists = item_sql::item_exists(pool, kind, item_id, user_id)
+            .await
+            .context("Failed to find item")?;
+        if !exists {
+            return Err(AppError::NotFound.into());
+        }
+
+        match request.visibility {
+            Some(visibility) => public_sql::set_item_visibility(pool, user_id, kind, item_id, &visibility)
+                .await
+                .map(Some)
+                .context("Failed to set item visibility"),
+            None => {
+                public_sql::clear_item_visibility(pool, user_id, kind, item_id)
+                    .await
+                    .context("Failed to clear item visibility")?;
+                Ok(None)
+            }
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::sql::recommendation_sql;
+use crate::type::recommendation_type::{
+    BacklogGame, NewRecommendation, RatedGame, Recommendation, RecommendationQuery,
+    DEFAULT_RECOMMENDATION_LIMIT, MAX_RECOMMENDATION_LIMIT,
+};
+use sqlx::PgPool;
+
+/// A rating counts half as much after this many days.
+const RATING_HALF_LIFE_DAYS: f64 = 365.0;
+
+/// Weight of the neutral prior in genre and platform affinity, so one
+/// rating does not make a strong preference.
+const AFFINITY_PRIOR: f64 = 2.0;
+
+/// Titles two users must both have rated before they count as similar.
+const MIN_COMMON_TITLES: usize = 2;
+
+/// Shrinks similarities built on few common titles.
+const SIMILARITY_SHRINKAGE: f64 = 5.0;
+
+/// Similar users consulted per title.
+const MAX_NEIGHBOURS: usize = 30;
+
+const GENRE_WEIGHT: f64 = 0.4;
+const PLATFORM_WEIGHT: f64 = 0.2;
+const SIMILAR_USERS_WEIGHT: f64 = 0.4;
+
+/// Ratings run 1-5, so a rating is at most this far from a user's mean.
+const MAX_DEVIATION: f64 = 4.0;
+
+/// One user's rating, relative to their own mean.
+struct CentredRating<'a> {
+    title: String,
+    genre: Option<&'a str>,
+    platform: Option<&'a str>,
+    deviation: f64,
+    weight: f64,
+    shared: bool,
+}
+
+pub struct RecommendationService;
+
+impl RecommendationService {
+    /// The user's `Plan to Play` games ranked by the last batch run.
+    pub async fn get_recommendations(
+        pool: &PgPool,
+        user_id: Uuid,
+        query: RecommendationQuery,
+    ) -> Result<Vec<Recommendation>> {
+        let limit = query.limit.unwrap_or(DEFAULT_RECOMMENDATION_LIMIT);
+        if !(1..=MAX_RECOMMENDATION_LIMIT).contains(&limit) {
+            return Err(AppError::Validation(format!(
+                "Limit must be between 1 and {}",
+                MAX_RECOMMENDATION_LIMIT
+            ))
+            .into());
+        }
+
+        recommendation_sql::find_recommendations(pool, user_id, limit)
+            .await
+            .context("Failed to get recommendations")
+    }
+
+    /// Scores every user's backlog and replaces the stored scores. Returns
+    /// how many games were scored.
+    pub async fn refresh(pool: &PgPool) -> Result<u64> {
+        let ratings = recommendation_sql::find_rated_games(pool)
+            .await
+            .context("Failed to get rated games")?;
+        let backlog = recommendation_sql::find_backlog_games(pool)
+            .await
+            .context("Failed to get backlog games")?;
+
+        let recommendations = Self::score(&ratings, &backlog, Utc::now());
+
+        recommendation_sql::replace_recommendations(pool, &recommendations)
+            .await
+            .context("Failed to store recommendations")?;
+
+        Ok(recommendations.len() as u64)
+    }
+
+    /// Blends three signals per backlog game, each from -1 to 1: how the
+    /// user rates its genre and its platform compared with their average,
+    /// favouring recent ratings, and how users who rate like them rated the
+    /// same title. Missing signals are left out of the blend; a game with
+    /// none scores a neutral 50.
+    fn score(ratings: &[RatedGame], backlog: &[BacklogGame], now: DateTime<Utc>) -> Vec<NewRecommendation> {
+        let by_user = Self::centre_ratings(ratings, now);
+
+        // Only shared ratings feed other users' recommendations.
+        let mut by_title: HashMap<&str, Vec<(Uuid, f64)>> = HashMap::new();
+        for (user_id, user_ratings) in &by_user {
+            for rating in user_ratings.iter().filter(|rating| rating.shared) {
+                by_title
+                    .entry(rating.title.as_str())
+                    .or_default()
+                    .push((*user_id, rating.deviation));
+            }
+        }
+
+        let mut backlog_by_user: HashMap<Uuid, Vec<&BacklogGame>> = HashMap::new();
+        for game in backlog {
+            backlog_by_user.entry(game.user_id).or_default().push(game);
+        }
+
+        let mut recommendations = Vec::with_capacity(backlog.len());
+        for (user_id, games) in backlog_by_user {
+            let user_ratings = by_user.get(&user_id).map(Vec::as_slice).unwrap_or_default();
+            let genres = Self::affinities(user_ratings, |rating| rating.genre);
+            let platforms = Self::affinities(user_ratings, |rating| rating.platform);
+            let neighbours = Self::similar_users(user_id, user_ratings, &by_title);
+
+            for game in games {
+                let genre_score = game
+                    .genre
+                    .as_deref()
+                    .and_then(|genre| genres.get(&Self::normalize(genre)).copied());
+                let platform_score = game
+                    .platform
+                    .as_deref()
+                    .and_then(|platform| platforms.get(&Self::normalize(platform)).copied());
+                let raters = by_title.get(Self::normalize(&game.title).as_str()).map(Vec::as_slice);
+                let (similar_users_score, similar_users) = Self::predict(&neighbours, raters);
+
+                let signals = [
+                    (genre_score, GENRE_WEIGHT),
+                    (platform_score, PLATFORM_WEIGHT),
+                    (similar_users_score, SIMILAR_USERS_WEIGHT),
+                ];
+                let weight: f64 = signals.iter().filter(|(score, _)| score.is_some()).map(|(_, w)| w).sum();
+                let blended = if weight > 0.0 {
+                    signals
+                        .iter()
+                        .filter_map(|(score, w)| score.map(|score| score * w))
+                        .sum::<f64>()
+                        / weight
+                } else {
+                    0.0
+                };
+
+                recommendations.push(NewRecommendation {
+                    user_id,
+                    game_id: game.id,
+                    score: ((blended + 1.0) * 50.0).clamp(0.0, 100.0),
+                    genre_score,
+                    platform_score,
+                    similar_users_score,
+                    similar_users,
+                });
+            }
+        }
+
+        recommendations
+    }
+
+    /// Groups ratings by user as deviations from that user's mean, so a
+    /// harsh rater's 3 and a generous rater's 4 can mean the same.
+    fn centre_ratings(ratings: &[RatedGame], now: DateTime<Utc>) -> HashMap<Uuid, Vec<CentredRating<'_>>> {
+        let mut by_user: HashMap<Uuid, Vec<&RatedGame>> = HashMap::new();
+        for rating in ratings {
+            by_user.entry(rating.user_id).or_default().push(rating);
+        }
+
+        by_user
+            .into_iter()
+            .map(|(user_id, user_ratings)| {
+                let mean = user_ratings.iter().map(|rating| rating.rating as f64).sum::<f64>()
+                    / user_ratings.len() as f64;
+
+                // The same title rated on two platforms counts once towards
+                // similarity; keep the most recent.
+                let mut seen: HashSet<String> = HashSet::new();
+                let mut centred: Vec<CentredRating> = Vec::with_capacity(user_ratings.len());
+                let mut by_recency = user_ratings;
+                by_recency.sort_by(|a, b| b.rated_at.cmp(&a.rated_at));
+
+                for rating in by_recency {
+                    let age_days = (now - rating.rated_at).num_seconds().max(0) as f64 / 86_400.0;
+                    let title = Self::normalize(&rating.title);
+                    let first = seen.insert(title.clone());
+
+                    centred.push(CentredRating {
+                        title,
+                        genre: rating.genre.as_deref(),
+                        platform: rating.platform.as_deref(),
+                        deviation: rating.rating as f64 - mean,
+                        weight: 0.5f64.powf(age_days / RATING_HALF_LIFE_DAYS),
+                        shared: rating.shared && first,
+                    });
+                }
+
+                (user_id, centred)
+            })
+            .collect()
+    }
+
+    /// Recency-weighted mean deviation per genre or platform, pulled
+    /// towards zero by a neutral prior and scaled to -1..1.
+    fn affinities<'a>(
+        ratings: &[CentredRating<'a>],
+        key: impl Fn(&CentredRating<'a>) -> Option<&'a str>,
+    ) -> HashMap<String, f64> {
+        let mut sums: HashMap<String, (f64, f64)> = HashMap::new();
+        for rating in ratings {
+            if let Some(value) = key(rating) {
+                let entry = sums.entry(Self::normalize(value)).or_default();
+                entry.0 += rating.weight * rating.deviation;
+                entry.1 += rating.weight;
+            }
+        }
+
+        sums.into_iter()
+            .map(|(value, (total, weight))| (value, total / (weight + AFFINITY_PRIOR) / MAX_DEVIATION))
+            .collect()
+    }
+
+    /// Pearson-style similarity with every user who shares ratings on at
+    /// least `MIN_COMMON_TITLES` of the same titles, shrunk by how few they
+    /// share. Only users with positive similarity are kept.
+    fn similar_users(
+        user_id: Uuid,
+        ratings: &[CentredRating],
+        by_title: &HashMap<&str, Vec<(Uuid, f64)>>,
+    ) -> HashMap<Uuid, f64> {
+        // (dot product, own norm, their norm, common titles)
+        let mut overlaps: HashMap<Uuid, (f64, f64, f64, usize)> = HashMap::new();
+        let mut seen: HashSet<&str> = HashSet::new();
+
+        for rating in ratings {
+            if !seen.insert(rating.title.as_str()) {
+                continue;
+            }
+            let Some(raters) = by_title.get(rating.title.as_str()) else {
+                continue;
+            };
+
+            for &(other_id, deviation) in raters {
+                if other_id == user_id {
+                    continue;
+                }
+                let entry = overlaps.entry(other_id).or_default();
+                entry.0 += rating.deviation * deviation;
+                entry.1 += rating.deviation * rating.deviation;
+                entry.2 += deviation * deviation;
+                entry.3 += 1;
+            }
+        }
+
+        overlaps
+            .into_iter()
+            .filter(|(_, (_, _, _, common))| *common >= MIN_COMMON_TITLES)
+            .filter_map(|(other_id, (dot, own, theirs, common))| {
+                let norm = (own * theirs).sqrt();
+                if norm == 0.0 {
+                    return None;
+                }
+                let similarity = dot / norm * common as f64 / (common as f64 + SIMILARITY_SHRINKAGE);
+                (similarity > 0.0).then_some((other_id, similarity))
+            })
+            .collect()
+    }
+
+    /// Similarity-weighted mean of how the most similar users who rated
+    /// the title deviated from their own average, scaled to -1..1.
+    fn predict(neighbours: &HashMap<Uuid, f64>, raters: Option<&[(Uuid, f64)]>) -> (Option<f64>, i32) {
+        let Some(raters) = raters else {
+            return (None, 0);
+        };
+
+        let mut rated: Vec<(f64, f64)> = raters
+            .iter()
+            .filter_map(|(other_id, deviation)| neighbours.get(other_id).map(|similarity| (*similarity, *deviation)))
+            .collect();
+        if rated.is_empty() {
+            return (None, 0);
+        }
+
+        rated.sort_by(|a, b| b.0.total_cmp(&a.0));
+        rated.truncate(MAX_NEIGHBOURS);
+
+        let weight: f64 = rated.iter().map(|(similarity, _)| similarity).sum();
+        let total: f64 = rated.iter().map(|(similarity, deviation)| similarity * deviation).sum();
+
+        (
+            Some((total / weight / MAX_DEVIATION).clamp(-1.0, 1.0)),
+            rated.len() as i32,
+        )
+    }
+
+    /// Titles, genres and platforms are free text; compare them ignoring
+    /// case and spacing.
+    fn normalize(value: &str) -> String {
+        value.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+    }
+}
@@ -0,0 +1,265 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::sql::review_sql;
+use crate::type::review_type::{
+    BacklogItem, MonthActivity, ReviewItem, YearInReview, REVIEW_MIN_YEAR, REVIEW_TOP_RATED,
+};
+use crate::type::stats_type::GenreCount;
+use sqlx::PgPool;
+
+const MONTH_INITIALS: [&str; 12] = ["J", "F", "M", "A", "M", "J", "J", "A", "S", "O", "N", "D"];
+
+const MONTH_NAMES: [&str; 12] = [
+    "JANUARY", "FEBRUARY", "MARCH", "APRIL", "MAY", "JUNE", "JULY", "AUGUST", "SEPTEMBER",
+    "OCTOBER", "NOVEMBER", "DECEMBER",
+];
+
+/// Card size in pixel-art "pixels"; each is drawn as `PIXEL` SVG units.
+const CARD_COLUMNS: i64 = 60;
+const CARD_ROWS: i64 = 80;
+const PIXEL: i64 = 8;
+
+/// Blocks in the tallest activity bar.
+const BAR_BLOCKS: i64 = 10;
+
+/// Longest title drawn on the card before it is cut with an ellipsis.
+const CARD_TITLE_CHARS: usize = 24;
+
+const FONT: &str = "'Press Start 2P', 'Courier New', monospace";
+
+pub struct ReviewService;
+
+impl ReviewService {
+    pub async fn get_review(pool: &PgPool, user_id: Uuid, year: i32) -> Result<YearInReview> {
+        let (from, until) = Self::year_range(year)?;
+
+        let items = review_sql::find_review_items(pool, user_id, from, until)
+            .await
+            .context("Failed to get items for review")?;
+        let activity = review_sql::count_events_by_month(pool, user_id, from, until)
+            .await
+            .context("Failed to count activity for review")?;
+
+        let in_year = |at: Option<DateTime<Utc>>| at.is_some_and(|at| at >= from && at < until);
+        let finished: Vec<&ReviewItem> = items.iter().filter(|item| in_year(item.finished_at)).collect();
+        let started = items.iter().filter(|item| in_year(item.started_at)).count() as i64;
+
+        let mut top_rated: Vec<&ReviewItem> = finished.iter().copied().filter(|item| item.rating.is_some()).collect();
+        top_rated.sort_by(|a, b| b.rating.cmp(&a.rating).then(a.finished_at.cmp(&b.finished_at)));
+
+        let longest_backlog = finished
+            .iter()
+            .filter_map(|item| {
+                let days = (item.finished_at? - item.added_at).num_days();
+                Some(BacklogItem { item: (*item).clone(), days })
+            })
+            .max_by_key(|backlog| backlog.days);
+
+        let activity_by_month: Vec<MonthActivity> = (1..=12)
+            .map(|month| MonthActivity {
+                month,
+                events: activity
+                    .iter()
+                    .find(|(m, _)| *m == month as i32)
+                    .map_or(0, |(_, events)| *events),
+            })
+            .collect();
+
+        // Ties go to the earlier month.
+        let busiest_month = activity_by_month
+            .iter()
+            .filter(|month| month.events > 0)
+            .fold(None::<&MonthActivity>, |best, month| match best {
+                Some(best) if best.events >= month.events => Some(best),
+                _ => Some(month),
+            })
+            .cloned();
+
+        Ok(YearInReview {
+            year,
+            started,
+            finished: finished.len() as i64,
+            top_rated: top_rated.into_iter().take(REVIEW_TOP_RATED).cloned().collect(),
+            genres: Self::genres(&items),
+            longest_backlog,
+            busiest_month,
+            activity_by_month,
+        })
+    }
+
+    /// `[January 1st, next January 1st)` in UTC; rejects years before
+    /// `REVIEW_MIN_YEAR` or after the current one.
+    fn year_range(year: i32) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+        if !(REVIEW_MIN_YEAR..=Utc::now().year()).contains(&year) {
+            return Err(AppError::Validation(format!(
+                "Year must be between {} and {}",
+                REVIEW_MIN_YEAR,
+                Utc::now().year()
+            ))
+            .into());
+        }
+
+        let start = |year: i32| {
+            Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0)
+                .single()
+                .context("Invalid review year")
+        };
+
+        Ok((start(year)?, start(year + 1)?))
+    }
+
+    fn genres(items: &[ReviewItem]) -> Vec<GenreCount> {
+        let mut counts: HashMap<Option<&str>, i64> = HashMap::new();
+        for item in items {
+            *counts.entry(item.genre.as_deref()).or_default() += 1;
+        }
+
+        let mut genres: Vec<GenreCount> = counts
+            .into_iter()
+            .map(|(genre, count)| GenreCount {
+                genre: genre.map(str::to_string),
+                count,
+            })
+            .collect();
+        genres.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.genre.cmp(&b.genre)));
+        genres
+    }
+
+    /// Renders the review as a pixel-art SVG card. Everything is drawn on a
+    /// grid of square blocks, so it stays crisp at any scale; the starfield
+    /// is seeded by the year, so a card always looks the same.
+    pub fn render_card(review: &YearInReview) -> String {
+        let width = CARD_COLUMNS * PIXEL;
+        let height = CARD_ROWS * PIXEL;
+        let mut svg = Vec::new();
+
+        svg.push(format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" shape-rendering="crispEdges">"#
+        ));
+        svg.push(format!(r##"<rect width="{width}" height="{height}" fill="#1a1c2c"/>"##));
+
+        let mut seed = review.year as u64;
+        for _ in 0..60 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let x = (seed >> 33) as i64 % CARD_COLUMNS;
+            let y = (seed >> 17) as i64 % CARD_ROWS;
+            let color = if seed % 5 == 0 { "#ffcd75" } else { "#566c86" };
+            svg.push(Self::block(x, y, 1, 1, color));
+        }
+
+        // Border with notched corners.
+        svg.push(Self::block(1, 0, CARD_COLUMNS - 2, 1, "#41a6f6"));
+        svg.push(Self::block(1, CARD_ROWS - 1, CARD_COLUMNS - 2, 1, "#41a6f6"));
+        svg.push(Self::block(0, 1, 1, CARD_ROWS - 2, "#41a6f6"));
+        svg.push(Self::block(CARD_COLUMNS - 1, 1, 1, CARD_ROWS - 2, "#41a6f6"));
+
+        svg.push(Self::text(4, 7, 16, "#f4f4f4", "PIXELASTRAL"));
+        svg.push(Self::text(4, 12, 24, "#ffcd75", &format!("{} WRAPPED", review.year)));
+
+        svg.push(Self::text(4, 19, 10, "#94b0c2", "STARTED"));
+        svg.push(Self::text(4, 23, 20, "#a7f070", &review.started.to_string()));
+        svg.push(Self::text(32, 19, 10, "#94b0c2", "FINISHED"));
+        svg.push(Self::text(32, 23, 20, "#a7f070", &review.finished.to_string()));
+
+        // Activity bars, one column of blocks per month.
+        let max_events = review.activity_by_month.iter().map(|month| month.events).max().unwrap_or(0);
+        let baseline = 38;
+        for (index, month) in review.activity_by_month.iter().enumerate() {
+            let x = 4 + index as i64 * 4;
+            let blocks = if max_events == 0 {
+                0
+            } else {
+                (month.events * BAR_BLOCKS + max_events - 1) / max_events
+            };
+            let busiest = review.busiest_month.as_ref().is_some_and(|busiest| busiest.month == month.month);
+            let color = if busiest { "#ffcd75" } else { "#38b764" };
+            for block in 0..blocks {
+                svg.push(Self::block(x, baseline - 1 - block, 3, 1, color));
+            }
+            svg.push(Self::block(x, baseline, 3, 1, "#333c57"));
+            svg.push(Self::text(x, baseline + 3, 8, "#94b0c2", MONTH_INITIALS[index]));
+        }
+
+        let busiest = match &review.busiest_month {
+            Some(month) => format!("BUSIEST: {}", MONTH_NAMES[month.month as usize - 1]),
+            None => "NO ACTIVITY YET".to_string(),
+        };
+        svg.push(Self::text(4, 45, 10, "#f4f4f4", &busiest));
+
+        svg.push(Self::text(4, 51, 10, "#94b0c2", "TOP RATED"));
+        if review.top_rated.is_empty() {
+            svg.push(Self::text(4, 54, 9, "#566c86", "NOTHING RATED"));
+        }
+        for (index, item) in review.top_rated.iter().take(3).enumerate() {
+            let y = 54 + index as i64 * 3;
+            for star in 0..item.rating.unwrap_or(0) as i64 {
+                svg.push(Self::block(4 + star * 2, y - 1, 1, 1, "#ffcd75"));
+            }
+            svg.push(Self::text(15, y, 9, "#f4f4f4", &item.title));
+        }
+
+        let genre = review
+            .genres
+            .iter()
+            .find_map(|genre| genre.genre.as_deref())
+            .unwrap_or("-");
+        svg.push(Self::text(4, 66, 10, "#94b0c2", "TOP GENRE"));
+        svg.push(Self::text(4, 69, 9, "#f4f4f4", genre));
+
+        svg.push(Self::text(4, 73, 10, "#94b0c2", "LONGEST BACKLOG"));
+        let backlog = match &review.longest_backlog {
+            Some(backlog) => format!("{} ({}D)", Self::truncate(&backlog.item.title, CARD_TITLE_CHARS - 6), backlog.days),
+            None => "-".to_string(),
+        };
+        svg.push(Self::text(4, 76, 9, "#f4f4f4", &backlog));
+
+        svg.push("</svg>".to_string());
+        svg.join("\n")
+    }
+
+    fn block(x: i64, y: i64, columns: i64, rows: i64, color: &str) -> String {
+        format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            x * PIXEL,
+            y * PIXEL,
+            columns * PIXEL,
+            rows * PIXEL,
+            color
+        )
+    }
+
+    /// `y` is the baseline row.
+    fn text(x: i64, y: i64, size: i64, color: &str, content: &str) -> String {
+        format!(
+            r#"<text x="{}" y="{}" font-family="{}" font-size="{}" fill="{}">{}</text>"#,
+            x * PIXEL,
+            y * PIXEL,
+            FONT,
+            size,
+            color,
+            Self::escape(&Self::truncate(content, CARD_TITLE_CHARS))
+        )
+    }
+
+    fn truncate(value: &str, max_chars: usize) -> String {
+        if value.chars().count() <= max_chars {
+            return value.to_string();
+        }
+        let mut truncated: String = value.chars().take(max_chars - 1).collect();
+        truncated.push('…');
+        truncated
+    }
+
+    fn escape(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;")
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::sql::{list_sql, profile_sql, public_sql, share_sql};
+use crate::type::public_type::{CreatedShareLink, PublicProfile, ShareLink, SharedList};
+use sqlx::PgPool;
+
+pub struct ShareService;
+
+impl ShareService {
+    /// Creates a read-only link to one of the user's lists. Only the hash of
+    /// the token is stored, so it is returned here and nowhere else.
+    pub async fn create(pool: &PgPool, list_id: Uuid, user_id: Uuid) -> Result<CreatedShareLink> {
+        Self::find_list(pool, list_id, user_id).await?;
+
+        let profile = profile_sql::find_or_create_profile(pool, user_id)
+            .await
+            .context("Failed to get profile")?;
+        if profile.visibility == "private" {
+            return Err(AppError::Validation(
+                "Set profile visibility to unlisted or public to share lists".to_string(),
+            )
+            .into());
+        }
+
+        let mut bytes = [0u8; 32];
+        rand::rng().fill_bytes(&mut bytes);
+        let token = hex::encode(bytes);
+
+        let link = share_sql::create_share_link(pool, user_id, list_id, &Self::hash_token(&token))
+            .await
+            .context("Failed to create share link")?;
+
+        Ok(CreatedShareLink { link, token })
+    }
+
+    pub async fn get_all(pool: &PgPool, list_id: Uuid, user_id: Uuid) -> Result<Vec<ShareLink>> {
+        Self::find_list(pool, list_id, user_id).await?;
+
+        share_sql::find_share_links(pool, list_id, user_id)
+            .await
+            .context("Failed to get share links")
+    }
+
+    pub async fn revoke(pool: &PgPool, id: Uuid, list_id: Uuid, user_id: Uuid) -> Result<ShareLink> {
+        share_sql::revoke_share_link(pool, id, list_id, user_id)
+            .await
+            .context("Failed to revoke share link")?
+            .ok_or_else(|| AppError::NotFound.into())
+    }
+
+    /// The list behind a share token, as anyone with the link sees it.
+    /// Revoked tokens and owners who made their profile private give
+    /// `NotFound`.
+    pub async fn get_shared_list(pool: &PgPool, token: &str) -> Result<SharedList> {
+        let shared = share_sql::find_shared_list(pool, &Self::hash_token(token))
+            .await
+            .context("Failed to find share link")?
+            .filter(|shared| shared.visibility != "private")
+            .ok_or(AppError::NotFound)?;
+
+        let items = public_sql::find_shared_list_items(pool, shared.list_id)
+            .await
+            .context("Failed to get shared list items")?;
+
+        Ok(SharedList {
+            name: shared.name,
+            description: shared.description,
+            owner: PublicProfile {
+                handle: shared.handle,
+                display_name: shared.display_name,
+                bio: shared.bio,
+                avatar_url: shared.avatar_url,
+            },
+            items,
+        })
+    }
+
+    async fn find_list(pool: &PgPool, list_id: Uuid, user_id: Uuid) -> Result<()> {
+        list_sql::find_list_by_id(pool, list_id, user_id)
+            .await
+            .context("Failed to get list")?
+            .ok_or(AppError::NotFound)?;
+
+        Ok(())
+    }
+
+    fn hash_token(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+}
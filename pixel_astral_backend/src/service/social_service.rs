@@ -0,0 +1,109 @@
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::sql::{profile_sql, social_sql, user_sql};
+use crate::type::social_type::{BlockedUser, Follow, FollowUser, FOLLOW_ACCEPTED, FOLLOW_PENDING};
+use sqlx::PgPool;
+
+pub struct SocialService;
+
+impl SocialService {
+    /// Follows `followee_id`, or asks to if their profile is private.
+    /// Following again returns the existing follow. Users who blocked the
+    /// caller look like they do not exist.
+    pub async fn follow(pool: &PgPool, user_id: Uuid, followee_id: Uuid) -> Result<Follow> {
+        if user_id == followee_id {
+            return Err(AppError::Validation("You cannot follow yourself".to_string()).into());
+        }
+
+        Self::find_user(pool, followee_id).await?;
+
+        if social_sql::is_blocked_between(pool, user_id, followee_id)
+            .await
+            .context("Failed to check blocks")?
+        {
+            return Err(AppError::NotFound.into());
+        }
+
+        let profile = profile_sql::find_or_create_profile(pool, followee_id)
+            .await
+            .context("Failed to get profile")?;
+        let status = if profile.visibility == "private" {
+            FOLLOW_PENDING
+        } else {
+            FOLLOW_ACCEPTED
+        };
+
+        social_sql::create_follow(pool, user_id, followee_id, status)
+            .await
+            .context("Failed to follow user")
+    }
+
+    /// Unfollows, or withdraws a pending request.
+    pub async fn unfollow(pool: &PgPool, user_id: Uuid, followee_id: Uuid) -> Result<bool> {
+        social_sql::delete_follow(pool, user_id, followee_id)
+            .await
+            .context("Failed to unfollow user")
+    }
+
+    pub async fn approve(pool: &PgPool, user_id: Uuid, follower_id: Uuid) -> Result<Follow> {
+        social_sql::accept_follow(pool, follower_id, user_id)
+            .await
+            .context("Failed to approve follower")?
+            .ok_or_else(|| AppError::NotFound.into())
+    }
+
+    /// Removes a follower, or declines a pending request.
+    pub async fn remove_follower(pool: &PgPool, user_id: Uuid, follower_id: Uuid) -> Result<bool> {
+        social_sql::delete_follow(pool, follower_id, user_id)
+            .await
+            .context("Failed to remove follower")
+    }
+
+    pub async fn get_following(pool: &PgPool, user_id: Uuid) -> Result<Vec<FollowUser>> {
+        social_sql::find_following(pool, user_id)
+            .await
+            .context("Failed to get followed users")
+    }
+
+    pub async fn get_followers(pool: &PgPool, user_id: Uuid) -> Result<Vec<FollowUser>> {
+        social_sql::find_followers(pool, user_id)
+            .await
+            .context("Failed to get followers")
+    }
+
+    /// Blocks a user and ends any follow between the two, either way.
+    pub async fn block(pool: &PgPool, user_id: Uuid, blocked_id: Uuid) -> Result<()> {
+        if user_id == blocked_id {
+            return Err(AppError::Validation("You cannot block yourself".to_string()).into());
+        }
+
+        Self::find_user(pool, blocked_id).await?;
+
+        social_sql::create_block(pool, user_id, blocked_id)
+            .await
+            .context("Failed to block user")
+    }
+
+    pub async fn unblock(pool: &PgPool, user_id: Uuid, blocked_id: Uuid) -> Result<bool> {
+        social_sql::delete_block(pool, user_id, blocked_id)
+            .await
+            .context("Failed to unblock user")
+    }
+
+    pub async fn get_blocks(pool: &PgPool, user_id: Uuid) -> Result<Vec<BlockedUser>> {
+        social_sql::find_blocks(pool, user_id)
+            .await
+            .context("Failed to get blocked users")
+    }
+
+    async fn find_user(pool: &PgPool, user_id: Uuid) -> Result<()> {
+        user_sql::find_user_by_id(pool, user_id)
+            .await
+            .context("Failed to find user")?
+            .ok_or(AppError::NotFound)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::sql::stats_sql;
+use crate::type::game_type::GAME_STATUSES;
+use crate::type::item_type::ItemKind;
+use crate::type::manga_type::MANGA_STATUSES;
+use crate::type::novel_type::NOVEL_STATUSES;
+use crate::type::stats_type::{
+    CollectionStats, PeriodCount, RatingCount, RatingStats, StatusCount, StreakStats,
+};
+use sqlx::PgPool;
+
+pub struct StatsService;
+
+impl StatsService {
+    /// Dashboard figures for one kind of item, leaving out the trash.
+    pub async fn get_stats(pool: &PgPool, user_id: Uuid, kind: ItemKind) -> Result<CollectionStats> {
+        let item_type = kind.as_str();
+
+        let counts = stats_sql::count_by_status(pool, user_id, item_type)
+            .await
+            .context("Failed to count items by status")?;
+        let by_genre = stats_sql::count_by_genre(pool, user_id, item_type)
+            .await
+            .context("Failed to count items by genre")?;
+        let ratings = stats_sql::count_by_rating(pool, user_id, item_type)
+            .await
+            .context("Failed to count items by rating")?;
+        let completed_by_month = stats_sql::count_completed_by_month(pool, user_id, item_type)
+            .await
+            .context("Failed to count completed items")?;
+        let average_days_to_complete =
+            stats_sql::average_days_to_complete(pool, user_id, item_type, Self::plan_status(kind))
+                .await
+                .context("Failed to get average completion time")?;
+        let (current_days, longest_days) = stats_sql::activity_streaks(pool, user_id, item_type, Utc::now())
+            .await
+            .context("Failed to get activity streaks")?;
+
+        let total = counts.iter().map(|count| count.count).sum();
+
+        Ok(CollectionStats {
+            item_type: kind,
+            total,
+            by_status: Self::by_status(kind, counts),
+            by_genre,
+            ratings: Self::rating_stats(ratings),
+            completed_by_year: Self::by_year(&completed_by_month),
+            completed_by_month,
+            average_days_to_complete,
+            streaks: StreakStats {
+                current_days,
+                longest_days,
+            },
+        })
+    }
+
+    fn statuses(kind: ItemKind) -> &'static [&'static str] {
+        match kind {
+            ItemKind::Game => GAME_STATUSES,
+            ItemKind::Manga => MANGA_STATUSES,
+            ItemKind::Novel => NOVEL_STATUSES,
+        }
+    }
+
+    fn plan_status(kind: ItemKind) -> &'static str {
+        match kind {
+            ItemKind::Game => "Plan to Play",
+            ItemKind::Manga | ItemKind::Novel => "Plan to Read",
+        }
+    }
+
+    /// Every known status in its usual order, then any others found in the
+    /// data.
+    fn by_status(kind: ItemKind, counts: Vec<StatusCount>) -> Vec<StatusCount> {
+        let mut by_status: Vec<StatusCount> = Self::statuses(kind)
+            .iter()
+            .map(|status| StatusCount {
+                status: status.to_string(),
+                count: 0,
+            })
+            .collect();
+
+        for count in counts {
+            match by_status.iter_mut().find(|known| known.status == count.status) {
+                Some(known) => known.count = count.count,
+                None => by_status.push(count),
+            }
+        }
+
+        by_status
+    }
+
+    fn rating_stats(counts: Vec<RatingCount>) -> RatingStats {
+        let rated: i64 = counts.iter().map(|count| count.count).sum();
+        let sum: i64 = counts
+            .iter()
+            .map(|count| i64::from(count.rating) * count.count)
+            .sum();
+
+        let distribution = (1..=5)
+            .map(|rating| RatingCount {
+                rating,
+                count: counts
+                    .iter()
+                    .find(|count| count.rating == rating)
+                    .map_or(0, |count| count.count),
+            })
+            .collect();
+
+        RatingStats {
+            average: (rated > 0).then(|| sum as f64 / rated as f64),
+            rated,
+            distribution,
+        }
+    }
+
+    /// Sums `YYYY-MM` periods into `YYYY`.
+    fn by_year(months: &[PeriodCount]) -> Vec<PeriodCount> {
+        let mut years: BTreeMap<&str, i64> = BTreeMap::new();
+
+        for month in months {
+            let year = month.period.split('-').next().unwrap_or(&month.period);
+            *years.entry(year).or_default() += month.count;
+        }
+
+        years
+            .into_iter()
+            .map(|(period, count)| PeriodCount {
+                period: period.to_string(),
+                count,
+            })
+            .collect()
+    }
+}
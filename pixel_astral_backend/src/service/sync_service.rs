@@ -0,0 +1,237 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::service::game_service::GameService;
+use crate::service::history_service::HistoryService;
+use crate::sql::{game_sql, manga_sql, novel_sql, sync_sql};
+use crate::type::game_type::Game;
+use crate::type::history_type::ItemAction;
+use crate::type::item_type::ItemKind;
+use crate::type::sync_type::{
+    GameMutation, SyncDeletion, SyncMutationResult, SyncPushRequest, SyncPushResponse, SyncResponse,
+    SyncStatus, MAX_SYNC_MUTATIONS,
+};
+use sqlx::{PgConnection, PgPool};
+
+pub struct SyncService;
+
+impl SyncService {
+    /// Changes since `since` (everything when `None`). Trashed items are
+    /// reported in `deleted` rather than with the live ones.
+    pub async fn pull(
+        pool: &PgPool,
+        user_id: Uuid,
+        since: Option<i64>,
+    ) -> Result<SyncResponse> {
+        let cursor = sync_sql::current_sync_cursor(pool)
+            .await
+            .context("Failed to get sync cursor")?;
+        let since = since.unwrap_or(0);
+
+        if since < 0 || since > cursor {
+            return Err(AppError::Validation("Unknown sync cursor".to_string()).into());
+        }
+
+        let mut deleted = Vec::new();
+
+        let (games, trashed): (Vec<_>, Vec<_>) = game_sql::find_games_changed(pool, user_id, since, cursor)
+            .await
+            .context("Failed to get changed games")?
+            .into_iter()
+            .partition(|game| game.deleted_at.is_none());
+        deleted.extend(trashed.into_iter().filter_map(|game| Self::deletion(ItemKind::Game, game.id, game.deleted_at)));
+
+        let (manga, trashed): (Vec<_>, Vec<_>) = manga_sql::find_manga_changed(pool, user_id, since, cursor)
+            .await
+            .context("Failed to get changed manga")?
+            .into_iter()
+            .partition(|manga| manga.deleted_at.is_none());
+        deleted.extend(trashed.into_iter().filter_map(|manga| Self::deletion(ItemKind::Manga, manga.id, manga.deleted_at)));
+
+        let (novels, trashed): (Vec<_>, Vec<_>) = novel_sql::find_novels_changed(pool, user_id, since, cursor)
+            .await
+            .context("Failed to get changed novels")?
+            .into_iter()
+            .partition(|novel| novel.deleted_at.is_none());
+        deleted.extend(trashed.into_iter().filter_map(|novel| Self::deletion(ItemKind::Novel, novel.id, novel.deleted_at)));
+
+        deleted.extend(
+            sync_sql::find_tombstones(pool, user_id, since, cursor)
+                .await
+                .context("Failed to get deleted items")?,
+        );
+
+        Ok(SyncResponse {
+            cursor,
+            games,
+            manga,
+            novels,
+            deleted,
+        })
+    }
+
+    /// Applies offline changes one by one, each in its own transaction, so
+    /// a conflict on one game does not hold back the others.
+    pub async fn push(
+        pool: &PgPool,
+        user_id: Uuid,
+        request: SyncPushRequest,
+    ) -> Result<SyncPushResponse> {
+        if request.games.len() > MAX_SYNC_MUTATIONS {
+            return Err(AppError::Validation(format!(
+                "At most {} mutations per request",
+                MAX_SYNC_MUTATIONS
+            ))
+            .into());
+        }
+
+        let mut results = Vec::with_capacity(request.games.len());
+
+        for mutation in request.games {
+            let id = mutation.id();
+
+            if let GameMutation::Upsert { game, .. } = &mutation {
+                if let Err(err) = GameService::validate_create(game) {
+                    results.push(Self::rejected(id, err));
+                    continue;
+                }
+            }
+
+            let mut tx = pool.begin().await.context("Failed to start transaction")?;
+            let result = match Self::apply_game(&mut tx, user_id, mutation).await {
+                Ok(result) => result,
+                Err(err) => Self::rejected(id, err),
+            };
+
+            if result.status == SyncStatus::Applied {
+                tx.commit().await.context("Failed to commit sync mutation")?;
+            } else {
+                tx.rollback().await.context("Failed to roll back sync mutation")?;
+            }
+
+            results.push(result);
+        }
+
+        Ok(SyncPushResponse { results })
+    }
+
+    async fn apply_game(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        mutation: GameMutation,
+    ) -> Result<SyncMutationResult> {
+        let id = mutation.id();
+        // Another user's game, or a shared one, is not this user's to sync;
+        // creating it then fails because the ID is taken.
+        let current = game_sql::find_game_for_update(&mut *conn, id)
+            .await
+            .context("Failed to get game")?
+            .filter(|game| game.user_id == user_id && game.collection_id.is_none());
+
+        match (mutation, current) {
+            // Created offline.
+            (GameMutation::Upsert { base_version: None, game, .. }, None) => {
+                let game = game_sql::create_game_with_id(&mut *conn, id, user_id, game)
+                    .await
+                    .map_err(Self::map_create_error)?;
+
+                HistoryService::record(conn, user_id, ItemKind::Game, id, ItemAction::Create, None, &game).await?;
+                Ok(Self::applied(id, Some(game)))
+            }
+            // Purged on the server while the client was editing it.
+            (GameMutation::Upsert { .. }, None) => Ok(Self::conflict(id, "Game was deleted", None)),
+            (GameMutation::Upsert { .. }, Some(current)) if current.deleted_at.is_some() => {
+                Ok(Self::conflict(id, "Game is in the trash", Some(current)))
+            }
+            (GameMutation::Upsert { base_version, game, .. }, Some(current)) => {
+                if base_version != Some(current.version) {
+                    return Ok(Self::conflict(id, "Game was changed on the server", Some(current)));
+                }
+
+                let game = game_sql::replace_game(&mut *conn, id, game)
+                    .await
+                    .map_err(|err| GameService::map_sql_error(err, "Failed to replace game"))?
+                    .ok_or(AppError::NotFound)?;
+
+                HistoryService::record(conn, user_id, ItemKind::Game, id, ItemAction::Update, Some(&current), &game)
+                    .await?;
+                Ok(Self::applied(id, Some(game)))
+            }
+            // Deleting something already gone is a no-op.
+            (GameMutation::Delete { .. }, None) => Ok(Self::applied(id, None)),
+            (GameMutation::Delete { .. }, Some(current)) if current.deleted_at.is_some() => {
+                Ok(Self::applied(id, None))
+            }
+            (GameMutation::Delete { base_version, .. }, Some(current)) => {
+                if base_version.is_some_and(|version| version != current.version) {
+                    return Ok(Self::conflict(id, "Game was changed on the server", Some(current)));
+                }
+
+                let game = game_sql::delete_game(&mut *conn, id)
+                    .await
+                    .context("Failed to delete game")?
+                    .ok_or(AppError::NotFound)?;
+
+                HistoryService::record(conn, user_id, ItemKind::Game, id, ItemAction::Delete, Some(&current), &game)
+                    .await?;
+                Ok(Self::applied(id, None))
+            }
+        }
+    }
+
+    /// A client ID that is already taken (by another user's item) cannot be
+    /// used.
+    fn map_create_error(err: sqlx::Error) -> anyhow::Error {
+        match &err {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                AppError::Conflict("ID is already in use".to_string()).into()
+            }
+            _ => GameService::map_sql_error(err, "Failed to create game"),
+        }
+    }
+
+    fn deletion(kind: ItemKind, item_id: Uuid, deleted_at: Option<DateTime<Utc>>) -> Option<SyncDeletion> {
+        Some(SyncDeletion {
+            item_type: kind.as_str().to_string(),
+            item_id,
+            deleted_at: deleted_at?,
+        })
+    }
+
+    fn applied(id: Uuid, game: Option<Game>) -> SyncMutationResult {
+        SyncMutationResult {
+            id,
+            status: SyncStatus::Applied,
+            error: None,
+            game,
+        }
+    }
+
+    fn conflict(id: Uuid, message: &str, game: Option<Game>) -> SyncMutationResult {
+        SyncMutationResult {
+            id,
+            status: SyncStatus::Conflict,
+            error: Some(message.to_string()),
+            game,
+        }
+    }
+
+    fn rejected(id: Uuid, err: anyhow::Error) -> SyncMutationResult {
+        let message = match err.downcast_ref::<AppError>() {
+            Some(app_err) => app_err.to_string(),
+            None => {
+                tracing::error!("Sync mutation failed: {:#}", err);
+                "Internal server error".to_string()
+            }
+        };
+
+        SyncMutationResult {
+            id,
+            status: SyncStatus::Rejected,
+            error: Some(message),
+            game: None,
+        }
+    }
+}
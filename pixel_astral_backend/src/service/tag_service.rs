@@ -0,0 +1,179 @@
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::service::game_service::GameService;
+use crate::sql::{item_sql, tag_sql};
+use crate::type::item_type::ItemKind;
+use crate::type::tag_type::{CreateTagRequest, Tag, UpdateTagRequest, MAX_TAG_NAME_LENGTH};
+use sqlx::PgPool;
+
+pub struct TagService;
+
+impl TagService {
+    pub async fn create(
+        pool: &PgPool,
+        user_id: Uuid,
+        mut request: CreateTagRequest,
+    ) -> Result<Tag> {
+        request.name = Self::validate_name(&request.name)?;
+        Self::validate_color(request.color.as_deref())?;
+
+        tag_sql::create_tag(pool, user_id, request)
+            .await
+            .map_err(|err| Self::map_sql_error(err, "Failed to create tag"))
+    }
+
+    pub async fn get_all_by_user(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<Tag>> {
+        tag_sql::find_all_tags_by_user(pool, user_id)
+            .await
+            .context("Failed to get tags")
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        mut request: UpdateTagRequest,
+    ) -> Result<Option<Tag>> {
+        if let Some(name) = &request.name {
+            request.name = Some(Self::validate_name(name)?);
+        }
+        Self::validate_color(request.color.as_deref())?;
+
+        tag_sql::update_tag(pool, id, user_id, request)
+            .await
+            .map_err(|err| Self::map_sql_error(err, "Failed to update tag"))
+    }
+
+    pub async fn delete(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool> {
+        tag_sql::delete_tag(pool, id, user_id)
+            .await
+            .context("Failed to delete tag")
+    }
+
+    /// The user's tags on one item; `None` if the user has no such item.
+    /// Shared games count if the user can see them.
+    pub async fn get_item_tags(
+        pool: &PgPool,
+        user_id: Uuid,
+        kind: ItemKind,
+        item_id: Uuid,
+    ) -> Result<Option<Vec<Tag>>> {
+        let exists = match kind {
+            ItemKind::Game => GameService::get_by_id(pool, item_id, user_id).await?.is_some(),
+            ItemKind::Manga | ItemKind::Novel => item_sql::item_exists(pool, kind, item_id, user_id)
+                .await
+                .context("Failed to find item")?,
+        };
+        if !exists {
+            return Ok(None);
+        }
+
+        tag_sql::find_tags_for_item(pool, user_id, kind, item_id)
+            .await
+            .map(Some)
+            .context("Failed to get item tags")
+    }
+
+    /// Replaces the user's tags on one item. Games go through
+    /// [`GameService::set_tags`] so collection editors can tag shared games.
+    pub async fn set_item_tags(
+        pool: &PgPool,
+        user_id: Uuid,
+        kind: ItemKind,
+        item_id: Uuid,
+        mut tag_ids: Vec<Uuid>,
+    ) -> Result<Option<Vec<Tag>>> {
+        tag_ids.sort();
+        tag_ids.dedup();
+
+        let found = match kind {
+            ItemKind::Game => GameService::set_tags(pool, item_id, user_id, &tag_ids).await?,
+            ItemKind::Manga | ItemKind::Novel => Self::set_owned_item_tags(pool, user_id, kind, item_id, &tag_ids).await?,
+        };
+        if !found {
+            return Ok(None);
+        }
+
+        tag_sql::find_tags_for_item(pool, user_id, kind, item_id)
+            .await
+            .map(Some)
+            .context("Failed to get item tags")
+    }
+
+    async fn set_owned_item_tags(
+        pool: &PgPool,
+        user_id: Uuid,
+        kind: ItemKind,
+        item_id: Uuid,
+        tag_ids: &[Uuid],
+    ) -> Result<bool> {
+        if !item_sql::item_exists(pool, kind, item_id, user_id)
+            .await
+            .context("Failed to find item")?
+        {
+            return Ok(false);
+        }
+
+        let updated = tag_sql::set_item_tags(pool, user_id, kind, item_id, tag_ids)
+            .await
+            .context("Failed to set item tags")?;
+
+        if !updated {
+            return Err(AppError::Validation("Unknown tag".to_string()).into());
+        }
+
+        Ok(true)
+    }
+
+    fn validate_name(name: &str) -> Result<String> {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err(AppError::Validation("Tag name is required".to_string()).into());
+        }
+
+        if name.chars().count() > MAX_TAG_NAME_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Tag name must be at most {} characters",
+                MAX_TAG_NAME_LENGTH
+            ))
+            .into());
+        }
+
+        Ok(name.to_string())
+    }
+
+    fn validate_color(color: Option<&str>) -> Result<()> {
+        let Some(color) = color else {
+            return Ok(());
+        };
+
+        let valid = color.len() == 7
+            && color.starts_with('#')
+            && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+
+        if !valid {
+            return Err(AppError::Validation("Color must look like #rrggbb".to_string()).into());
+        }
+
+        Ok(())
+    }
+
+    fn map_sql_error(err: sqlx::Error, context: &'static str) -> anyhow::Error {
+        match &err {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                AppError::Conflict("A tag with that name already exists".to_string()).into()
+            }
+            _ => anyhow::Error::new(err).context(context),
+        }
+    }
+}
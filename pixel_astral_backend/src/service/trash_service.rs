@@ -0,0 +1,33 @@
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+
+use crate::blob_store::BlobStore;
+use crate::service::cover_service::CoverService;
+use crate::sql::{game_sql, manga_sql, novel_sql};
+use sqlx::PgPool;
+
+pub struct TrashService;
+
+impl TrashService {
+    /// Permanently deletes games, manga and novels that have been in the
+    /// trash for longer than `retention_days`, along with their cover files.
+    /// Returns how many were removed.
+    pub async fn purge_expired(pool: &PgPool, store: &dyn BlobStore, retention_days: i64) -> Result<u64> {
+        let cutoff = Utc::now() - Duration::days(retention_days);
+
+        let games = game_sql::purge_games_trashed_before(pool, cutoff)
+            .await
+            .context("Failed to purge trashed games")?;
+        let manga = manga_sql::purge_manga_trashed_before(pool, cutoff)
+            .await
+            .context("Failed to purge trashed manga")?;
+        let novels = novel_sql::purge_novels_trashed_before(pool, cutoff)
+            .await
+            .context("Failed to purge trashed novels")?;
+
+        let covers = [games, manga, novels].concat();
+        CoverService::delete_files(store, &covers).await;
+
+        Ok(covers.len() as u64)
+    }
+}
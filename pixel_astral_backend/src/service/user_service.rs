@@ -1,59 +1,404 @@
 use anyhow::{Context, Result};
-use bcrypt::{hash, verify, DEFAULT_COST};
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::sql::user_sql;
-use crate::type::user_type::{AuthResponse, Claims, CreateUserRequest, LoginRequest, UserResponse};
+use crate::blob_store::BlobStore;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::mailer::{Email, Mailer};
+use crate::service::cover_service::CoverService;
+use crate::service::password_service::PasswordService;
+use crate::sql::{profile_sql, user_sql};
+use crate::type::user_type::{
+    AuthResponse, ChangeEmailRequest, ChangePasswordRequest, Claims, CreateUserRequest,
+    DeleteAccountRequest, LoginRequest, ResetPasswordRequest, User, UserResponse,
+    TOKEN_PURPOSE_CHANGE_EMAIL, TOKEN_PURPOSE_PASSWORD_RESET, TOKEN_PURPOSE_VERIFY_EMAIL,
+};
 use sqlx::PgPool;
 
+/// Failed attempts allowed before an email is locked out.
+const MAX_FAILED_LOGINS: i32 = 5;
+/// Lockout applied on the first lock; doubles with every further failure.
+const BASE_LOCKOUT_SECONDS: i64 = 60;
+const MAX_LOCKOUT_SECONDS: i64 = 24 * 60 * 60;
+
+const VERIFY_EMAIL_TOKEN_HOURS: i64 = 24;
+const PASSWORD_RESET_TOKEN_MINUTES: i64 = 60;
+const SESSION_DAYS: i64 = 7;
+const MAX_EMAIL_LENGTH: usize = 254;
+const MAX_EMAIL_LOCAL_PART_LENGTH: usize = 64;
+
 pub struct UserService;
 
 impl UserService {
     pub async fn signup(
         pool: &PgPool,
+        config: &Config,
+        passwords: &PasswordService,
+        mailer: Arc<dyn Mailer>,
         request: CreateUserRequest,
-    ) -> Result<AuthResponse> {
-        let password_hash = hash(&request.password, DEFAULT_COST)
-            .context("Failed to hash password")?;
+    ) -> Result<UserResponse> {
+        let email = Self::normalize_email(&request.email)?;
+        passwords.validate(&request.password)?;
+
+        let password_hash = passwords.hash(&request.password)?;
+
+        let user = user_sql::create_user(pool, &email, &password_hash)
+            .await
+            .map_err(Self::map_email_conflict)?;
 
-        let user = user_sql::create_user(pool, &request.email, &password_hash)
+        profile_sql::find_or_create_profile(pool, user.id)
             .await
-            .context("Failed to create user")?;
+            .context("Failed to create profile")?;
 
-        let token = Self::generate_token(user.id)?;
+        Self::send_verification_email(pool, config, mailer, &user).await?;
 
-        Ok(AuthResponse {
-            user: user.into(),
-            token,
-        })
+        Ok(user.into())
+    }
+
+    /// Consumes a link sent by `signup`/`resend_verification` or by
+    /// `change_email`; both arrive at the same endpoint.
+    pub async fn verify_email(
+        pool: &PgPool,
+        token: &str,
+    ) -> Result<UserResponse> {
+        let token_hash = Self::hash_token(token);
+
+        if let Some(token) = user_sql::consume_user_token(pool, TOKEN_PURPOSE_VERIFY_EMAIL, &token_hash)
+            .await
+            .context("Failed to consume verification token")?
+        {
+            user_sql::mark_email_verified(pool, token.user_id)
+                .await
+                .context("Failed to verify email")?;
+
+            return Self::get_user(pool, token.user_id).await;
+        }
+
+        let token = user_sql::consume_user_token(pool, TOKEN_PURPOSE_CHANGE_EMAIL, &token_hash)
+            .await
+            .context("Failed to consume verification token")?
+            .ok_or_else(|| AppError::Validation("Invalid or expired token".to_string()))?;
+
+        let user = user_sql::apply_pending_email(pool, token.user_id)
+            .await
+            .map_err(Self::map_email_conflict)?
+            .ok_or_else(|| AppError::Validation("No email change is pending".to_string()))?;
+
+        Ok(user.into())
+    }
+
+    /// Re-sends the verification link. Succeeds silently for unknown or
+    /// already verified addresses so it can't be used to probe accounts.
+    pub async fn resend_verification(
+        pool: &PgPool,
+        config: &Config,
+        mailer: Arc<dyn Mailer>,
+        email: &str,
+    ) -> Result<()> {
+        let user = user_sql::find_user_by_email(pool, email.trim())
+            .await
+            .context("Failed to find user")?;
+
+        match user {
+            Some(user) if user.email_verified_at.is_none() => {
+                Self::send_verification_email(pool, config, mailer, &user).await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Emails a password reset link. Like `resend_verification`, the result
+    /// is the same whether or not the address belongs to an account.
+    pub async fn forgot_password(
+        pool: &PgPool,
+        config: &Config,
+        mailer: Arc<dyn Mailer>,
+        email: &str,
+    ) -> Result<()> {
+        let Some(user) = user_sql::find_user_by_email(pool, email.trim())
+            .await
+            .context("Failed to find user")?
+        else {
+            return Ok(());
+        };
+
+        let token = Self::issue_token(
+            pool,
+            user.id,
+            TOKEN_PURPOSE_PASSWORD_RESET,
+            Duration::minutes(PASSWORD_RESET_TOKEN_MINUTES),
+        )
+        .await?;
+
+        Self::send_in_background(mailer, Email {
+            to: user.email,
+            subject: "Reset your PixelAstral password".to_string(),
+            body: format!(
+                "Someone asked to reset the password for your PixelAstral account.\n\n\
+                 Reset it here within the next hour:\n{}/reset-password?token={}\n\n\
+                 If this wasn't you, you can ignore this email.",
+                config.frontend_url, token
+            ),
+        });
+
+        Ok(())
+    }
+
+    pub async fn reset_password(
+        pool: &PgPool,
+        passwords: &PasswordService,
+        request: ResetPasswordRequest,
+    ) -> Result<()> {
+        passwords.validate(&request.password)?;
+
+        let token = user_sql::consume_user_token(pool, TOKEN_PURPOSE_PASSWORD_RESET, &Self::hash_token(&request.token))
+            .await
+            .context("Failed to consume reset token")?
+            .ok_or_else(|| AppError::Validation("Invalid or expired token".to_string()))?;
+
+        let password_hash = passwords.hash(&request.password)?;
+
+        user_sql::update_password(pool, token.user_id, &password_hash)
+            .await
+            .context("Failed to update password")?;
+
+        // Receiving the reset link proves ownership of the inbox.
+        user_sql::mark_email_verified(pool, token.user_id)
+            .await
+            .context("Failed to verify email")?;
+
+        user_sql::revoke_sessions(pool, token.user_id, None)
+            .await
+            .context("Failed to revoke sessions")?;
+
+        Ok(())
     }
 
     pub async fn login(
         pool: &PgPool,
+        passwords: &PasswordService,
         request: LoginRequest,
     ) -> Result<AuthResponse> {
-        let user = user_sql::find_user_by_email(pool, &request.email)
+        // Attempts are tracked per email rather than per user so unknown
+        // addresses lock out exactly like real ones.
+        let attempt_key = Self::normalize_email(&request.email)
+            .unwrap_or_else(|_| request.email.trim().to_lowercase());
+
+        let attempt = user_sql::find_login_attempt(pool, &attempt_key)
             .await
-            .context("Failed to find user")?
-            .ok_or_else(|| anyhow::anyhow!("Invalid email or password"))?;
+            .context("Failed to load login attempts")?;
 
-        let is_valid = verify(&request.password, &user.password_hash)
-            .context("Failed to verify password")?;
+        let is_locked = attempt
+            .as_ref()
+            .and_then(|attempt| attempt.locked_until)
+            .is_some_and(|locked_until| locked_until > Utc::now());
 
-        if !is_valid {
-            return Err(anyhow::anyhow!("Invalid email or password"));
+        let user = user_sql::find_user_by_email(pool, &attempt_key)
+            .await
+            .context("Failed to find user")?;
+
+        let password_hash = user
+            .as_ref()
+            .map(|user| user.password_hash.as_str())
+            .unwrap_or(passwords.dummy_hash());
+
+        let is_valid = passwords.verify(&request.password, password_hash)?;
+
+        if is_locked {
+            return Err(AppError::TooManyAttempts.into());
+        }
+
+        let user = match user {
+            Some(user) if is_valid => user,
+            _ => {
+                Self::record_failed_login(pool, &attempt_key).await?;
+                return Err(AppError::Unauthorized.into());
+            }
+        };
+
+        if user.email_verified_at.is_none() {
+            return Err(AppError::Forbidden("Email address not verified".to_string()).into());
+        }
+
+        if attempt.is_some() {
+            user_sql::clear_login_attempts(pool, &attempt_key)
+                .await
+                .context("Failed to reset login attempts")?;
         }
 
-        let token = Self::generate_token(user.id)?;
+        if passwords.needs_rehash(&user.password_hash) {
+            Self::rehash_password(pool, passwords, user.id, &request.password).await;
+        }
+
+        let profile = profile_sql::find_or_create_profile(pool, user.id)
+            .await
+            .context("Failed to load profile")?;
+
+        let token = Self::generate_token(pool, user.id).await?;
 
         Ok(AuthResponse {
             user: user.into(),
+            profile,
             token,
         })
     }
 
+    /// Changes the password and signs out every other session, keeping the
+    /// one that made the request.
+    pub async fn change_password(
+        pool: &PgPool,
+        passwords: &PasswordService,
+        user_id: Uuid,
+        session_id: Uuid,
+        request: ChangePasswordRequest,
+    ) -> Result<()> {
+        let user = Self::find_user(pool, user_id).await?;
+        Self::check_password(passwords, &user, &request.current_password)?;
+        passwords.validate(&request.new_password)?;
+
+        let password_hash = passwords.hash(&request.new_password)?;
+
+        user_sql::update_password(pool, user.id, &password_hash)
+            .await
+            .context("Failed to update password")?;
+
+        user_sql::revoke_sessions(pool, user.id, Some(session_id))
+            .await
+            .context("Failed to revoke sessions")?;
+
+        Ok(())
+    }
+
+    /// Records the new address as pending and emails it a verification link;
+    /// the current address stays in use until that link is followed.
+    pub async fn change_email(
+        pool: &PgPool,
+        config: &Config,
+        passwords: &PasswordService,
+        mailer: Arc<dyn Mailer>,
+        user_id: Uuid,
+        request: ChangeEmailRequest,
+    ) -> Result<UserResponse> {
+        let user = Self::find_user(pool, user_id).await?;
+        Self::check_password(passwords, &user, &request.password)?;
+
+        let new_email = Self::normalize_email(&request.new_email)?;
+
+        if new_email == user.email {
+            return Err(AppError::Validation("New email matches the current one".to_string()).into());
+        }
+
+        if user_sql::find_user_by_email(pool, &new_email)
+            .await
+            .context("Failed to find user")?
+            .is_some()
+        {
+            return Err(AppError::Conflict("Email is already registered".to_string()).into());
+        }
+
+        let user = user_sql::set_pending_email(pool, user.id, &new_email)
+            .await
+            .context("Failed to store pending email")?
+            .ok_or(AppError::NotFound)?;
+
+        let token = Self::issue_token(
+            pool,
+            user.id,
+            TOKEN_PURPOSE_CHANGE_EMAIL,
+            Duration::hours(VERIFY_EMAIL_TOKEN_HOURS),
+        )
+        .await?;
+
+        Self::send_in_background(mailer.clone(), Email {
+            to: new_email.clone(),
+            subject: "Confirm your new PixelAstral email".to_string(),
+            body: format!(
+                "Confirm this address for your PixelAstral account:\n{}/verify?token={}",
+                config.frontend_url, token
+            ),
+        });
+
+        Self::send_in_background(mailer, Email {
+            to: user.email.clone(),
+            subject: "Your PixelAstral email is being changed".to_string(),
+            body: format!(
+                "A change of your account email to {} was requested. \
+                 If this wasn't you, change your password right away.",
+                new_email
+            ),
+        });
+
+        Ok(user.into())
+    }
+
+    /// Deletes the account, or schedules deletion after the configured grace
+    /// period. Returns `None` once the account is gone, along with the cover
+    /// files of its items.
+    pub async fn delete_account(
+        pool: &PgPool,
+        store: &dyn BlobStore,
+        config: &Config,
+        passwords: &PasswordService,
+        user_id: Uuid,
+        request: DeleteAccountRequest,
+    ) -> Result<Option<UserResponse>> {
+        let user = Self::find_user(pool, user_id).await?;
+        Self::check_password(passwords, &user, &request.password)?;
+
+        if request.immediate || config.account_deletion_grace_days <= 0 {
+            let covers = user_sql::delete_user(pool, user.id)
+                .await
+                .context("Failed to delete user")?
+                .unwrap_or_default();
+
+            user_sql::clear_login_attempts(pool, &user.email)
+                .await
+                .context("Failed to reset login attempts")?;
+
+            CoverService::delete_files(store, &covers).await;
+
+            return Ok(None);
+        }
+
+        let scheduled_at = Utc::now() + Duration::days(config.account_deletion_grace_days);
+
+        let user = user_sql::schedule_user_deletion(pool, user.id, Some(scheduled_at))
+            .await
+            .context("Failed to schedule deletion")?
+            .ok_or(AppError::NotFound)?;
+
+        Ok(Some(user.into()))
+    }
+
+    pub async fn cancel_account_deletion(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<UserResponse> {
+        let user = user_sql::schedule_user_deletion(pool, user_id, None)
+            .await
+            .context("Failed to cancel deletion")?
+            .ok_or(AppError::NotFound)?;
+
+        Ok(user.into())
+    }
+
+    /// Removes accounts whose deletion grace period has ended, with the
+    /// cover files of their items.
+    pub async fn purge_deleted_accounts(pool: &PgPool, store: &dyn BlobStore) -> Result<u64> {
+        let (count, covers) = user_sql::delete_users_due_for_deletion(pool)
+            .await
+            .context("Failed to purge deleted accounts")?;
+
+        CoverService::delete_files(store, &covers).await;
+
+        Ok(count)
+    }
+
     pub async fn get_user(
         pool: &PgPool,
         user_id: Uuid,
@@ -66,15 +411,173 @@ impl UserService {
         Ok(user.into())
     }
 
-    fn generate_token(user_id: Uuid) -> Result<String> {
-        let expiration = Utc::now()
-            .checked_add_signed(Duration::days(7))
-            .expect("valid timestamp")
-            .timestamp() as usize;
+    /// Trims and lowercases an email address after a basic syntax check.
+    ///
+    /// This is intentionally permissive (no quoted local parts or IP
+    /// literals); deliverability is proven by the verification email.
+    pub fn normalize_email(email: &str) -> Result<String> {
+        let email = email.trim().to_lowercase();
+        let invalid = || AppError::Validation("Invalid email address".to_string());
+
+        let (local, domain) = email.split_once('@').ok_or_else(invalid)?;
+
+        let is_valid = email.len() <= MAX_EMAIL_LENGTH
+            && !local.is_empty()
+            && local.len() <= MAX_EMAIL_LOCAL_PART_LENGTH
+            && !local.starts_with('.')
+            && !local.ends_with('.')
+            && !local.contains("..")
+            && local.chars().all(|c| c.is_alphanumeric() || "!#$%&'*+-/=?^_`{|}~.".contains(c))
+            && domain.contains('.')
+            && domain.split('.').all(|label| {
+                !label.is_empty()
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+            });
+
+        if !is_valid {
+            return Err(invalid().into());
+        }
+
+        Ok(email)
+    }
+
+    async fn rehash_password(pool: &PgPool, passwords: &PasswordService, user_id: Uuid, password: &str) {
+        let result = match passwords.hash(password) {
+            Ok(password_hash) => user_sql::update_password(pool, user_id, &password_hash)
+                .await
+                .context("Failed to store rehashed password"),
+            Err(err) => Err(err),
+        };
+
+        if let Err(err) = result {
+            tracing::warn!("Failed to upgrade password hash for {}: {:#}", user_id, err);
+        }
+    }
+
+    async fn find_user(pool: &PgPool, user_id: Uuid) -> Result<User> {
+        user_sql::find_user_by_id(pool, user_id)
+            .await
+            .context("Failed to find user")?
+            .ok_or_else(|| AppError::NotFound.into())
+    }
+
+    fn check_password(passwords: &PasswordService, user: &User, password: &str) -> Result<()> {
+        if !passwords.verify(password, &user.password_hash)? {
+            return Err(AppError::Forbidden("Current password is incorrect".to_string()).into());
+        }
+
+        Ok(())
+    }
+
+    fn map_email_conflict(err: sqlx::Error) -> anyhow::Error {
+        match &err {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                AppError::Conflict("Email is already registered".to_string()).into()
+            }
+            _ => anyhow::Error::new(err).context("Failed to save email"),
+        }
+    }
+
+    async fn send_verification_email(
+        pool: &PgPool,
+        config: &Config,
+        mailer: Arc<dyn Mailer>,
+        user: &User,
+    ) -> Result<()> {
+        let token = Self::issue_token(
+            pool,
+            user.id,
+            TOKEN_PURPOSE_VERIFY_EMAIL,
+            Duration::hours(VERIFY_EMAIL_TOKEN_HOURS),
+        )
+        .await?;
+
+        Self::send_in_background(mailer, Email {
+            to: user.email.clone(),
+            subject: "Verify your PixelAstral email".to_string(),
+            body: format!(
+                "Welcome to PixelAstral!\n\nConfirm your email address here:\n{}/verify?token={}",
+                config.frontend_url, token
+            ),
+        });
+
+        Ok(())
+    }
+
+    /// Creates a random single-use token and stores only its hash, returning
+    /// the raw value for the email link.
+    async fn issue_token(
+        pool: &PgPool,
+        user_id: Uuid,
+        purpose: &str,
+        ttl: Duration,
+    ) -> Result<String> {
+        let mut bytes = [0u8; 32];
+        rand::rng().fill_bytes(&mut bytes);
+        let token = hex::encode(bytes);
+
+        user_sql::create_user_token(pool, user_id, purpose, &Self::hash_token(&token), Utc::now() + ttl)
+            .await
+            .context("Failed to store token")?;
+
+        Ok(token)
+    }
+
+    fn hash_token(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+
+    /// Sends outside the request so SMTP latency neither slows responses nor
+    /// reveals whether an email was actually sent.
+    fn send_in_background(mailer: Arc<dyn Mailer>, email: Email) {
+        tokio::spawn(async move {
+            if let Err(err) = mailer.send(email).await {
+                tracing::warn!("Failed to send email: {:#}", err);
+            }
+        });
+    }
+
+    async fn record_failed_login(pool: &PgPool, attempt_key: &str) -> Result<()> {
+        let attempt = user_sql::record_failed_login(pool, attempt_key)
+            .await
+            .context("Failed to record login attempt")?;
+
+        if attempt.failed_count >= MAX_FAILED_LOGINS {
+            let locked_until = Utc::now() + Self::lockout_duration(attempt.failed_count);
+            user_sql::lock_login(pool, attempt_key, locked_until)
+                .await
+                .context("Failed to lock login")?;
+        }
+
+        Ok(())
+    }
+
+    fn lockout_duration(failed_count: i32) -> Duration {
+        let doublings = (failed_count - MAX_FAILED_LOGINS).clamp(0, 20) as u32;
+        let seconds = BASE_LOCKOUT_SECONDS
+            .saturating_mul(1_i64 << doublings)
+            .min(MAX_LOCKOUT_SECONDS);
+
+        Duration::seconds(seconds)
+    }
+
+    /// Starts a session and returns a JWT bound to it, so the token stops
+    /// working as soon as the session is revoked.
+    async fn generate_token(pool: &PgPool, user_id: Uuid) -> Result<String> {
+        let expires_at = Utc::now()
+            .checked_add_signed(Duration::days(SESSION_DAYS))
+            .expect("valid timestamp");
+
+        let session = user_sql::create_session(pool, user_id, expires_at)
+            .await
+            .context("Failed to create session")?;
 
         let claims = Claims {
             sub: user_id.to_string(),
-            exp: expiration,
+            sid: session.id.to_string(),
+            exp: expires_at.timestamp() as usize,
         };
 
         let secret = std::env::var("SUPABASE_API")
@@ -84,7 +587,8 @@ impl UserService {
             .context("Failed to generate token")
     }
 
-    pub fn verify_token(token: &str) -> Result<Uuid> {
+    /// Returns the user and session IDs carried by a token's signature-checked claims.
+    pub fn verify_token(token: &str) -> Result<(Uuid, Uuid)> {
         let secret = std::env::var("SUPABASE_API")
             .context("JWT secret not configured")?;
 
@@ -98,6 +602,24 @@ impl UserService {
         let user_id = Uuid::parse_str(&token_data.claims.sub)
             .context("Invalid user ID in token")?;
 
-        Ok(user_id)
+        let session_id = Uuid::parse_str(&token_data.claims.sid)
+            .context("Invalid session ID in token")?;
+
+        Ok((user_id, session_id))
+    }
+
+    /// Like `verify_token`, but also requires the session to still be active.
+    pub async fn authenticate(
+        pool: &PgPool,
+        token: &str,
+    ) -> Result<(Uuid, Uuid)> {
+        let (user_id, session_id) = Self::verify_token(token)?;
+
+        user_sql::find_active_session(pool, session_id, user_id)
+            .await
+            .context("Failed to load session")?
+            .ok_or(AppError::Unauthorized)?;
+
+        Ok((user_id, session_id))
     }
 }
@@ -0,0 +1,62 @@
+use crate::type::social_type::{Activity, NewActivity};
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn create_activity<'e>(
+    executor: impl PgExecutor<'e>,
+    activity: NewActivity,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO activities (user_id, event_id, item_type, item_id, verb, title, rating)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        activity.user_id,
+        activity.event_id,
+        activity.item_type.as_str(),
+        activity.item_id,
+        activity.verb.as_str(),
+        activity.title,
+        activity.rating
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Activity of the users `user_id` follows (accepted follows only), newest
+/// first, strictly before `before` when given. Items that are trashed or
+/// marked `private` are left out.
+pub async fn find_feed(
+    pool: &PgPool,
+    user_id: Uuid,
+    before: Option<(DateTime<Utc>, Uuid)>,
+    limit: i64,
+) -> Result<Vec<Activity>, sqlx::Error> {
+    let (before_at, before_id) = before.unzip();
+
+    sqlx::query_as!(
+        Activity,
+        r#"
+        SELECT a.id, a.user_id, p.handle AS "handle?", p.display_name AS "display_name?", a.item_type, a.item_id, a.verb, a.title, a.rating, a.created_at
+        FROM follows f
+        JOIN activities a ON a.user_id = f.followee_id
+        JOIN collection_items i ON i.item_type = a.item_type AND i.id = a.item_id
+        LEFT JOIN item_visibility v ON v.item_type = a.item_type AND v.item_id = a.item_id
+        LEFT JOIN user_profiles p ON p.user_id = a.user_id
+        WHERE f.follower_id = $1 AND f.status = 'accepted'
+          AND COALESCE(v.visibility, 'unlisted') <> 'private'
+          AND ($2::timestamptz IS NULL OR (a.created_at, a.id) < ($2, $3::uuid))
+        ORDER BY a.created_at DESC, a.id DESC
+        LIMIT $4
+        "#,
+        user_id,
+        before_at,
+        before_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
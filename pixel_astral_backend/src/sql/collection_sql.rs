@@ -0,0 +1,355 @@
+use crate::type::collection_type::{CollectionInvitation, CollectionMember, SharedCollection};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+/// Creates the collection with `user_id` as its only owner.
+pub async fn create_collection(
+    pool: &PgPool,
+    user_id: Uuid,
+    name: &str,
+) -> Result<SharedCollection, sqlx::Error> {
+    sqlx::query_as!(
+        SharedCollection,
+        r#"
+        WITH collection AS (
+            INSERT INTO shared_collections (name)
+            VALUES ($2)
+            RETURNING id, name, created_at, updated_at
+        ), owner AS (
+            INSERT INTO collection_members (collection_id, user_id, role)
+            SELECT id, $1, 'owner' FROM collection
+        )
+        SELECT id AS "id!", name AS "name!", 'owner' AS "role!", 1::bigint AS "member_count!", created_at AS "created_at!", updated_at AS "updated_at?"
+        FROM collection
+        "#,
+        user_id,
+        name
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Collections `user_id` is a member of, by name.
+pub async fn find_collections_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<SharedCollection>, sqlx::Error> {
+    sqlx::query_as!(
+        SharedCollection,
+        r#"
+        SELECT c.id, c.name, m.role,
+            (SELECT COUNT(*) FROM collection_members cm WHERE cm.collection_id = c.id) AS "member_count!",
+            c.created_at, c.updated_at
+        FROM shared_collections c
+        JOIN collection_members m ON m.collection_id = c.id AND m.user_id = $1
+        ORDER BY lower(c.name), c.id
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// `None` unless `user_id` is a member.
+pub async fn find_collection(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<SharedCollection>, sqlx::Error> {
+    sqlx::query_as!(
+        SharedCollection,
+        r#"
+        SELECT c.id, c.name, m.role,
+            (SELECT COUNT(*) FROM collection_members cm WHERE cm.collection_id = c.id) AS "member_count!",
+            c.created_at, c.updated_at
+        FROM shared_collections c
+        JOIN collection_members m ON m.collection_id = c.id AND m.user_id = $2
+        WHERE c.id = $1
+        "#,
+        id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn update_collection(
+    pool: &PgPool,
+    id: Uuid,
+    name: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE shared_collections
+        SET name = $2, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        "#,
+        id,
+        name
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Deletes the collection with its games, members and invitations.
+pub async fn delete_collection(
+    pool: &PgPool,
+    id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM shared_collections
+        WHERE id = $1
+        "#,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// `user_id`'s role in the collection; `None` if not a member.
+pub async fn find_member_role<'e>(
+    executor: impl PgExecutor<'e>,
+    collection_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT role
+        FROM collection_members
+        WHERE collection_id = $1 AND user_id = $2
+        "#,
+        collection_id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Owners first, then by join date.
+pub async fn find_members(
+    pool: &PgPool,
+    collection_id: Uuid,
+) -> Result<Vec<CollectionMember>, sqlx::Error> {
+    sqlx::query_as!(
+        CollectionMember,
+        r#"
+        SELECT m.user_id, p.handle AS "handle?", p.display_name AS "display_name?", p.avatar_url AS "avatar_url?", m.role, m.joined_at
+        FROM collection_members m
+        LEFT JOIN user_profiles p ON p.user_id = m.user_id
+        WHERE m.collection_id = $1
+        ORDER BY m.role = 'owner' DESC, m.joined_at, m.user_id
+        "#,
+        collection_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Locks the owner rows for the rest of the transaction, so two owners
+/// cannot demote or remove each other at the same time and leave none.
+pub async fn find_owners_for_update<'e>(
+    executor: impl PgExecutor<'e>,
+    collection_id: Uuid,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT user_id
+        FROM collection_members
+        WHERE collection_id = $1 AND role = 'owner'
+        FOR UPDATE
+        "#,
+        collection_id
+    )
+    .fetch_all(executor)
+    .await
+}
+
+/// Adds a member; someone who already is one keeps their role.
+pub async fn create_member<'e>(
+    executor: impl PgExecutor<'e>,
+    collection_id: Uuid,
+    user_id: Uuid,
+    role: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO collection_members (collection_id, user_id, role)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (collection_id, user_id) DO NOTHING
+        "#,
+        collection_id,
+        user_id,
+        role
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn update_member_role<'e>(
+    executor: impl PgExecutor<'e>,
+    collection_id: Uuid,
+    user_id: Uuid,
+    role: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE collection_members
+        SET role = $3
+        WHERE collection_id = $1 AND user_id = $2
+        "#,
+        collection_id,
+        user_id,
+        role
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn delete_member<'e>(
+    executor: impl PgExecutor<'e>,
+    collection_id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM collection_members
+        WHERE collection_id = $1 AND user_id = $2
+        "#,
+        collection_id,
+        user_id
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Pass exactly one of `email` (lowercased) and `invitee_id`.
+pub async fn create_invitation(
+    pool: &PgPool,
+    collection_id: Uuid,
+    invited_by: Uuid,
+    email: Option<&str>,
+    invitee_id: Option<Uuid>,
+    role: &str,
+) -> Result<CollectionInvitation, sqlx::Error> {
+    sqlx::query_as!(
+        CollectionInvitation,
+        r#"
+        WITH invitation AS (
+            INSERT INTO collection_invitations (collection_id, invited_by, email, invitee_id, role)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, collection_id, invited_by, email, invitee_id, role, created_at
+        )
+        SELECT i.id AS "id!", i.collection_id AS "collection_id!", c.name AS "collection_name!",
+            i.invited_by AS "invited_by!", i.email AS "email?", i.invitee_id AS "invitee_id?",
+            i.role AS "role!", i.created_at AS "created_at!"
+        FROM invitation i
+        JOIN shared_collections c ON c.id = i.collection_id
+        "#,
+        collection_id,
+        invited_by,
+        email,
+        invitee_id,
+        role
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Open invitations to the collection, newest first.
+pub async fn find_invitations_by_collection(
+    pool: &PgPool,
+    collection_id: Uuid,
+) -> Result<Vec<CollectionInvitation>, sqlx::Error> {
+    sqlx::query_as!(
+        CollectionInvitation,
+        r#"
+        SELECT i.id, i.collection_id, c.name AS collection_name, i.invited_by, i.email, i.invitee_id, i.role, i.created_at
+        FROM collection_invitations i
+        JOIN shared_collections c ON c.id = i.collection_id
+        WHERE i.collection_id = $1
+        ORDER BY i.created_at DESC
+        "#,
+        collection_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Invitations addressed to `user_id`, or to their email once it is
+/// verified, newest first.
+pub async fn find_invitations_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<CollectionInvitation>, sqlx::Error> {
+    sqlx::query_as!(
+        CollectionInvitation,
+        r#"
+        SELECT i.id, i.collection_id, c.name AS collection_name, i.invited_by, i.email, i.invitee_id, i.role, i.created_at
+        FROM collection_invitations i
+        JOIN shared_collections c ON c.id = i.collection_id
+        JOIN users u ON u.id = $1
+        WHERE i.invitee_id = u.id
+            OR (i.email = lower(u.email) AND u.email_verified_at IS NOT NULL)
+        ORDER BY i.created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Deletes an invitation addressed to `user_id` (as in
+/// `find_invitations_for_user`) and returns it, to accept or decline it.
+pub async fn take_invitation_for_user<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<CollectionInvitation>, sqlx::Error> {
+    sqlx::query_as!(
+        CollectionInvitation,
+        r#"
+        DELETE FROM collection_invitations i
+        USING shared_collections c, users u
+        WHERE i.id = $1
+            AND c.id = i.collection_id
+            AND u.id = $2
+            AND (i.invitee_id = u.id OR (i.email = lower(u.email) AND u.email_verified_at IS NOT NULL))
+        RETURNING i.id, i.collection_id, c.name AS collection_name, i.invited_by, i.email, i.invitee_id, i.role, i.created_at
+        "#,
+        id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Withdraws an invitation to the collection.
+pub async fn delete_invitation(
+    pool: &PgPool,
+    id: Uuid,
+    collection_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM collection_invitations
+        WHERE id = $1 AND collection_id = $2
+        "#,
+        id,
+        collection_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,84 @@
+use crate::type::cover_type::Cover;
+use crate::type::game_type::Game;
+use crate::type::manga_type::Manga;
+use crate::type::novel_type::Novel;
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+// Covers are written by ID alone: callers lock the row and check access
+// through the item's service first.
+
+pub async fn update_game_cover<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    cover: &Cover,
+) -> Result<Option<Game>, sqlx::Error> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        UPDATE games
+        SET cover_url = $1,
+            cover_medium_url = $2,
+            cover_small_url = $3,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $4 AND deleted_at IS NULL
+        RETURNING id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
+        "#,
+        cover.cover_url,
+        cover.cover_medium_url,
+        cover.cover_small_url,
+        id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+pub async fn update_manga_cover<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    cover: &Cover,
+) -> Result<Option<Manga>, sqlx::Error> {
+    sqlx::query_as!(
+        Manga,
+        r#"
+        UPDATE manga
+        SET cover_url = $1,
+            cover_medium_url = $2,
+            cover_small_url = $3,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $4 AND deleted_at IS NULL
+        RETURNING id, user_id, title, genre, rating, status, notes, url, chapter, page, publication_status, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        "#,
+        cover.cover_url,
+        cover.cover_medium_url,
+        cover.cover_small_url,
+        id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+pub async fn update_novel_cover<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    cover: &Cover,
+) -> Result<Option<Novel>, sqlx::Error> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        UPDATE novels
+        SET cover_url = $1,
+            cover_medium_url = $2,
+            cover_small_url = $3,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $4 AND deleted_at IS NULL
+        RETURNING id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        "#,
+        cover.cover_url,
+        cover.cover_medium_url,
+        cover.cover_small_url,
+        id
+    )
+    .fetch_optional(executor)
+    .await
+}
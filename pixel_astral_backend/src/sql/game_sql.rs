@@ -1,71 +1,223 @@
+use crate::type::cover_type::Cover;
 use crate::type::game_type::{CreateGameRequest, Game, UpdateGameRequest};
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
 
-pub async fn create_game(
-    pool: &PgPool,
+/// With a `collection_id`, `user_id` is the member adding the game.
+pub async fn create_game<'e>(
+    executor: impl PgExecutor<'e>,
     user_id: Uuid,
+    collection_id: Option<Uuid>,
     request: CreateGameRequest,
 ) -> Result<Game, sqlx::Error> {
     sqlx::query_as!(
         Game,
         r#"
-        INSERT INTO games (user_id, title, genre, rating, status, notes)
-        VALUES ($1, $2, $3, $4, $5, $6)
-        RETURNING id, user_id, title, genre, rating, status, notes, created_at, updated_at
+        INSERT INTO games (
+            user_id, collection_id, title, genre, rating, status, notes,
+            platform, hours_played, completion_percent, started_at, finished_at
+        )
+        VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
+            CASE WHEN $6 = 'Completed' THEN COALESCE($12, CURRENT_TIMESTAMP) ELSE $12 END
+        )
+        RETURNING id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
         "#,
         user_id,
+        collection_id,
         request.title,
         request.genre,
         request.rating,
         request.status,
-        request.notes
+        request.notes,
+        request.platform,
+        request.hours_played,
+        request.completion_percent,
+        request.started_at,
+        request.finished_at
     )
-    .fetch_one(pool)
+    .fetch_one(executor)
     .await
 }
 
+/// Like `create_game`, with an ID chosen by the client (offline sync).
+pub async fn create_game_with_id<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    user_id: Uuid,
+    request: CreateGameRequest,
+) -> Result<Game, sqlx::Error> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        INSERT INTO games (
+            id, user_id, title, genre, rating, status, notes,
+            platform, hours_played, completion_percent, started_at, finished_at
+        )
+        VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
+            CASE WHEN $6 = 'Completed' THEN COALESCE($12, CURRENT_TIMESTAMP) ELSE $12 END
+        )
+        RETURNING id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
+        "#,
+        id,
+        user_id,
+        request.title,
+        request.genre,
+        request.rating,
+        request.status,
+        request.notes,
+        request.platform,
+        request.hours_played,
+        request.completion_percent,
+        request.started_at,
+        request.finished_at
+    )
+    .fetch_one(executor)
+    .await
+}
+
+/// Lists the user's own games, not shared ones. With `tag_ids`, only games
+/// carrying every tag are returned; with `list_id`, only games in that
+/// list, ordered by position.
 pub async fn find_all_games_by_user(
     pool: &PgPool,
     user_id: Uuid,
+    tag_ids: &[Uuid],
+    list_id: Option<Uuid>,
 ) -> Result<Vec<Game>, sqlx::Error> {
     sqlx::query_as!(
         Game,
         r#"
-        SELECT id, user_id, title, genre, rating, status, notes, created_at, updated_at
+        SELECT g.id, g.user_id, g.collection_id, g.title, g.genre, g.rating, g.status, g.notes, g.platform, g.hours_played, g.completion_percent, g.started_at, g.finished_at, g.cover_url, g.cover_medium_url, g.cover_small_url, g.created_at, g.updated_at, g.deleted_at, g.version
+        FROM games g
+        LEFT JOIN list_items li
+            ON li.list_id = $3 AND li.item_type = 'game' AND li.item_id = g.id
+        WHERE g.user_id = $1 AND g.collection_id IS NULL AND g.deleted_at IS NULL
+          AND ($3::uuid IS NULL OR li.list_id IS NOT NULL)
+          AND (
+              cardinality($2::uuid[]) = 0
+              OR (
+                  SELECT COUNT(*)
+                  FROM item_tags it
+                  WHERE it.item_type = 'game' AND it.item_id = g.id AND it.tag_id = ANY($2)
+              ) = cardinality($2::uuid[])
+          )
+        ORDER BY li.position, g.created_at DESC
+        "#,
+        user_id,
+        tag_ids,
+        list_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Live games of a shared collection, newest first.
+pub async fn find_games_by_collection(
+    pool: &PgPool,
+    collection_id: Uuid,
+) -> Result<Vec<Game>, sqlx::Error> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        SELECT id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
         FROM games
-        WHERE user_id = $1
+        WHERE collection_id = $1 AND deleted_at IS NULL
         ORDER BY created_at DESC
         "#,
-        user_id
+        collection_id
     )
     .fetch_all(pool)
     .await
 }
 
-pub async fn find_game_by_id(
+/// Rows written with `since <= sync_xid < until`, including ones in the
+/// trash, oldest change first.
+pub async fn find_games_changed(
     pool: &PgPool,
-    id: Uuid,
     user_id: Uuid,
-) -> Result<Option<Game>, sqlx::Error> {
+    since: i64,
+    until: i64,
+) -> Result<Vec<Game>, sqlx::Error> {
     sqlx::query_as!(
         Game,
         r#"
-        SELECT id, user_id, title, genre, rating, status, notes, created_at, updated_at
+        SELECT id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
         FROM games
-        WHERE id = $1 AND user_id = $2
+        WHERE user_id = $1 AND collection_id IS NULL AND sync_xid >= $2 AND sync_xid < $3
+        ORDER BY sync_xid, id
         "#,
-        id,
-        user_id
+        user_id,
+        since,
+        until
     )
-    .fetch_optional(pool)
+    .fetch_all(pool)
     .await
 }
 
-pub async fn update_game(
+/// Rows are yielded as they arrive from the cursor, oldest first, for exports.
+pub fn stream_games_by_user(
     pool: &PgPool,
-    id: Uuid,
     user_id: Uuid,
+) -> BoxStream<'_, Result<Game, sqlx::Error>> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        SELECT id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
+        FROM games
+        WHERE user_id = $1 AND collection_id IS NULL AND deleted_at IS NULL
+        ORDER BY created_at, id
+        "#,
+        user_id
+    )
+    .fetch(pool)
+}
+
+/// Looks the game up by ID alone; the service checks that the caller may
+/// see it.
+pub async fn find_game_by_id<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+) -> Result<Option<Game>, sqlx::Error> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        SELECT id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
+        FROM games
+        WHERE id = $1 AND deleted_at IS NULL
+        "#,
+        id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// [`find_game_by_id`], including games in the trash.
+pub async fn find_game_with_trashed<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+) -> Result<Option<Game>, sqlx::Error> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        SELECT id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
+        FROM games
+        WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Applies a partial update. Each nullable field takes two parameters:
+/// whether it was sent, and the value (`NULL` to clear it).
+pub async fn update_game<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
     request: UpdateGameRequest,
 ) -> Result<Option<Game>, sqlx::Error> {
     sqlx::query_as!(
@@ -73,41 +225,281 @@ pub async fn update_game(
         r#"
         UPDATE games
         SET title = COALESCE($1, title),
-            genre = COALESCE($2, genre),
-            rating = COALESCE($3, rating),
-            status = COALESCE($4, status),
-            notes = COALESCE($5, notes),
+            genre = CASE WHEN $2 THEN $3 ELSE genre END,
+            rating = CASE WHEN $4 THEN $5 ELSE rating END,
+            status = COALESCE($6, status),
+            notes = CASE WHEN $7 THEN $8 ELSE notes END,
+            platform = CASE WHEN $9 THEN $10 ELSE platform END,
+            hours_played = CASE WHEN $11 THEN $12 ELSE hours_played END,
+            completion_percent = CASE WHEN $13 THEN $14 ELSE completion_percent END,
+            started_at = CASE WHEN $15 THEN $16 ELSE started_at END,
+            finished_at = CASE
+                WHEN $17 THEN $18
+                WHEN $6 = 'Completed' AND finished_at IS NULL THEN CURRENT_TIMESTAMP
+                ELSE finished_at
+            END,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $19 AND deleted_at IS NULL
+        RETURNING id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
+        "#,
+        request.title.into_option(),
+        request.genre.is_set(),
+        request.genre.into_option(),
+        request.rating.is_set(),
+        request.rating.into_option(),
+        request.status.into_option(),
+        request.notes.is_set(),
+        request.notes.into_option(),
+        request.platform.is_set(),
+        request.platform.into_option(),
+        request.hours_played.is_set(),
+        request.hours_played.into_option(),
+        request.completion_percent.is_set(),
+        request.completion_percent.into_option(),
+        request.started_at.is_set(),
+        request.started_at.into_option(),
+        request.finished_at.is_set(),
+        request.finished_at.into_option(),
+        id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Overwrites every editable field, clearing the ones left out. A
+/// `Completed` game without `finished_at` keeps its stored date, or gets now.
+pub async fn replace_game<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    request: CreateGameRequest,
+) -> Result<Option<Game>, sqlx::Error> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        UPDATE games
+        SET title = $1,
+            genre = $2,
+            rating = $3,
+            status = $4,
+            notes = $5,
+            platform = $6,
+            hours_played = $7,
+            completion_percent = $8,
+            started_at = $9,
+            finished_at = CASE
+                WHEN $4 = 'Completed' THEN COALESCE($10, finished_at, CURRENT_TIMESTAMP)
+                ELSE $10
+            END,
             updated_at = CURRENT_TIMESTAMP
-        WHERE id = $6 AND user_id = $7
-        RETURNING id, user_id, title, genre, rating, status, notes, created_at, updated_at
+        WHERE id = $11 AND deleted_at IS NULL
+        RETURNING id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
         "#,
         request.title,
         request.genre,
         request.rating,
         request.status,
         request.notes,
-        id,
+        request.platform,
+        request.hours_played,
+        request.completion_percent,
+        request.started_at,
+        request.finished_at,
+        id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Locks the row for the rest of the transaction, whether or not it is in
+/// the trash, so history can diff against the state being replaced. Like
+/// the writes below, it goes by ID alone: lock first, check access, then
+/// write.
+pub async fn find_game_for_update<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+) -> Result<Option<Game>, sqlx::Error> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        SELECT id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
+        FROM games
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Writes every editable field of `version` back, including `NULL`s. Covers
+/// are left alone.
+pub async fn revert_game<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    version: Game,
+) -> Result<Option<Game>, sqlx::Error> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        UPDATE games
+        SET title = $1,
+            genre = $2,
+            rating = $3,
+            status = $4,
+            notes = $5,
+            platform = $6,
+            hours_played = $7,
+            completion_percent = $8,
+            started_at = $9,
+            finished_at = $10,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $11 AND deleted_at IS NULL
+        RETURNING id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
+        "#,
+        version.title,
+        version.genre,
+        version.rating,
+        version.status,
+        version.notes,
+        version.platform,
+        version.hours_played,
+        version.completion_percent,
+        version.started_at,
+        version.finished_at,
+        id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Moves the game to the trash. It is hidden from every other query
+/// until restored, and purged for good after the retention period.
+pub async fn delete_game<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+) -> Result<Option<Game>, sqlx::Error> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        UPDATE games
+        SET deleted_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND deleted_at IS NULL
+        RETURNING id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
+        "#,
+        id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+pub async fn find_trashed_games_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Game>, sqlx::Error> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        SELECT id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
+        FROM games
+        WHERE user_id = $1 AND collection_id IS NULL AND deleted_at IS NOT NULL
+        ORDER BY deleted_at DESC
+        "#,
         user_id
     )
-    .fetch_optional(pool)
+    .fetch_all(pool)
     .await
 }
 
-pub async fn delete_game(
+pub async fn find_trashed_games_by_collection(
     pool: &PgPool,
+    collection_id: Uuid,
+) -> Result<Vec<Game>, sqlx::Error> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        SELECT id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
+        FROM games
+        WHERE collection_id = $1 AND deleted_at IS NOT NULL
+        ORDER BY deleted_at DESC
+        "#,
+        collection_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn restore_game<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+) -> Result<Option<Game>, sqlx::Error> {
+    sqlx::query_as!(
+        Game,
+        r#"
+        UPDATE games
+        SET deleted_at = NULL
+        WHERE id = $1 AND deleted_at IS NOT NULL
+        RETURNING id, user_id, collection_id, title, genre, rating, status, notes, platform, hours_played, completion_percent, started_at, finished_at, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at, version
+        "#,
+        id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Permanently deletes a game that is already in the trash.
+/// Returns the deleted row's cover, whose files the caller removes.
+pub async fn purge_game<'e>(
+    executor: impl PgExecutor<'e>,
     id: Uuid,
+) -> Result<Option<Cover>, sqlx::Error> {
+    sqlx::query_as!(
+        Cover,
+        r#"
+        DELETE FROM games
+        WHERE id = $1 AND deleted_at IS NOT NULL
+        RETURNING cover_url, cover_medium_url, cover_small_url
+        "#,
+        id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Permanently deletes the user's trash. Returns the deleted rows' covers,
+/// whose files the caller removes.
+pub async fn purge_trashed_games_by_user(
+    pool: &PgPool,
     user_id: Uuid,
-) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query!(
+) -> Result<Vec<Cover>, sqlx::Error> {
+    sqlx::query_as!(
+        Cover,
         r#"
         DELETE FROM games
-        WHERE id = $1 AND user_id = $2
+        WHERE user_id = $1 AND collection_id IS NULL AND deleted_at IS NOT NULL
+        RETURNING cover_url, cover_medium_url, cover_small_url
         "#,
-        id,
         user_id
     )
-    .execute(pool)
-    .await?;
+    .fetch_all(pool)
+    .await
+}
 
-    Ok(result.rows_affected() > 0)
+/// Permanently deletes games of every user that were trashed before `cutoff`.
+/// Returns the deleted rows' covers, whose files the caller removes.
+pub async fn purge_games_trashed_before(
+    pool: &PgPool,
+    cutoff: DateTime<Utc>,
+) -> Result<Vec<Cover>, sqlx::Error> {
+    sqlx::query_as!(
+        Cover,
+        r#"
+        DELETE FROM games
+        WHERE deleted_at < $1
+        RETURNING cover_url, cover_medium_url, cover_small_url
+        "#,
+        cutoff
+    )
+    .fetch_all(pool)
+    .await
 }
@@ -0,0 +1,111 @@
+use crate::type::event_type::ItemNotification;
+use crate::type::history_type::{FieldChange, ItemEvent, NewItemEvent};
+use crate::type::item_type::ItemKind;
+use sqlx::types::Json;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+/// Returns the new event's ID.
+pub async fn create_item_event<'e>(
+    executor: impl PgExecutor<'e>,
+    event: &NewItemEvent,
+) -> Result<Uuid, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO item_events (user_id, item_type, item_id, action, changes, snapshot, reverted_event_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id
+        "#,
+        event.user_id,
+        event.item_type.as_str(),
+        event.item_id,
+        event.action.as_str(),
+        Json(&event.changes) as _,
+        &event.snapshot,
+        event.reverted_event_id
+    )
+    .fetch_one(executor)
+    .await
+}
+
+/// Newest first, whoever made them; callers check access to the item.
+pub async fn find_item_events(
+    pool: &PgPool,
+    kind: ItemKind,
+    item_id: Uuid,
+) -> Result<Vec<ItemEvent>, sqlx::Error> {
+    sqlx::query_as!(
+        ItemEvent,
+        r#"
+        SELECT id, user_id, item_type, item_id, action, changes AS "changes: Json<Vec<FieldChange>>", snapshot, reverted_event_id, created_at
+        FROM item_events
+        WHERE item_type = $1 AND item_id = $2
+        ORDER BY created_at DESC, id
+        "#,
+        kind.as_str(),
+        item_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn find_item_event<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    kind: ItemKind,
+    item_id: Uuid,
+) -> Result<Option<ItemEvent>, sqlx::Error> {
+    sqlx::query_as!(
+        ItemEvent,
+        r#"
+        SELECT id, user_id, item_type, item_id, action, changes AS "changes: Json<Vec<FieldChange>>", snapshot, reverted_event_id, created_at
+        FROM item_events
+        WHERE id = $1 AND item_type = $2 AND item_id = $3
+        "#,
+        id,
+        kind.as_str(),
+        item_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+pub async fn item_event_exists(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM item_events WHERE id = $1 AND user_id = $2) AS "exists!""#,
+        id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// The user's events after `after_id`, oldest first, for resuming an event
+/// stream.
+pub async fn find_item_notifications_after(
+    pool: &PgPool,
+    user_id: Uuid,
+    after_id: Uuid,
+    limit: i64,
+) -> Result<Vec<ItemNotification>, sqlx::Error> {
+    sqlx::query_as!(
+        ItemNotification,
+        r#"
+        SELECT e.id, e.user_id AS "user_id!", e.item_type, e.item_id, e.action, e.created_at
+        FROM item_events e
+        JOIN item_events last ON last.id = $2 AND last.user_id = $1
+        WHERE e.user_id = $1 AND (e.created_at, e.id) > (last.created_at, last.id)
+        ORDER BY e.created_at, e.id
+        LIMIT $3
+        "#,
+        user_id,
+        after_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
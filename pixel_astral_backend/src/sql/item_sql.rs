@@ -0,0 +1,84 @@
+use crate::type::item_type::ItemKind;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Whether the user owns an item of the given kind with this ID. Games in
+/// shared collections do not count.
+pub async fn item_exists(
+    pool: &PgPool,
+    kind: ItemKind,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let exists = match kind {
+        ItemKind::Game => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS (SELECT 1 FROM games WHERE id = $1 AND user_id = $2 AND collection_id IS NULL AND deleted_at IS NULL) AS "exists!""#,
+                id,
+                user_id
+            )
+            .fetch_one(pool)
+            .await?
+        }
+        ItemKind::Manga => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS (SELECT 1 FROM manga WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL) AS "exists!""#,
+                id,
+                user_id
+            )
+            .fetch_one(pool)
+            .await?
+        }
+        ItemKind::Novel => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS (SELECT 1 FROM novels WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL) AS "exists!""#,
+                id,
+                user_id
+            )
+            .fetch_one(pool)
+            .await?
+        }
+    };
+
+    Ok(exists)
+}
+
+/// [`item_exists`], counting items in the trash too.
+pub async fn item_owned(
+    pool: &PgPool,
+    kind: ItemKind,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let exists = match kind {
+        ItemKind::Game => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS (SELECT 1 FROM games WHERE id = $1 AND user_id = $2 AND collection_id IS NULL) AS "exists!""#,
+                id,
+                user_id
+            )
+            .fetch_one(pool)
+            .await?
+        }
+        ItemKind::Manga => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS (SELECT 1 FROM manga WHERE id = $1 AND user_id = $2) AS "exists!""#,
+                id,
+                user_id
+            )
+            .fetch_one(pool)
+            .await?
+        }
+        ItemKind::Novel => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS (SELECT 1 FROM novels WHERE id = $1 AND user_id = $2) AS "exists!""#,
+                id,
+                user_id
+            )
+            .fetch_one(pool)
+            .await?
+        }
+    };
+
+    Ok(exists)
+}
@@ -0,0 +1,336 @@
+use crate::type::item_type::ItemKind;
+use crate::type::list_type::{CreateListRequest, CustomList, ListItem, UpdateListRequest};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+pub async fn create_list(
+    pool: &PgPool,
+    user_id: Uuid,
+    request: CreateListRequest,
+) -> Result<CustomList, sqlx::Error> {
+    sqlx::query_as!(
+        CustomList,
+        r#"
+        INSERT INTO lists (user_id, name, description)
+        VALUES ($1, $2, $3)
+        RETURNING id, user_id, name, description, created_at, updated_at
+        "#,
+        user_id,
+        request.name,
+        request.description
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn find_all_lists_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<CustomList>, sqlx::Error> {
+    sqlx::query_as!(
+        CustomList,
+        r#"
+        SELECT id, user_id, name, description, created_at, updated_at
+        FROM lists
+        WHERE user_id = $1
+        ORDER BY created_at
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn find_list_by_id(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<CustomList>, sqlx::Error> {
+    sqlx::query_as!(
+        CustomList,
+        r#"
+        SELECT id, user_id, name, description, created_at, updated_at
+        FROM lists
+        WHERE id = $1 AND user_id = $2
+        "#,
+        id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn update_list(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+    request: UpdateListRequest,
+) -> Result<Option<CustomList>, sqlx::Error> {
+    sqlx::query_as!(
+        CustomList,
+        r#"
+        UPDATE lists
+        SET name = COALESCE($1, name),
+            description = COALESCE($2, description),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3 AND user_id = $4
+        RETURNING id, user_id, name, description, created_at, updated_at
+        "#,
+        request.name,
+        request.description,
+        id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn delete_list(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM lists
+        WHERE id = $1 AND user_id = $2
+        "#,
+        id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Items in the trash keep their slot but are left out, so positions in the
+/// result can have gaps until the item is restored or purged.
+pub async fn find_list_items(
+    pool: &PgPool,
+    list_id: Uuid,
+) -> Result<Vec<ListItem>, sqlx::Error> {
+    sqlx::query_as!(
+        ListItem,
+        r#"
+        SELECT li.list_id, li.item_type, li.item_id, li.position, li.added_at
+        FROM list_items li
+        WHERE li.list_id = $1
+          AND NOT EXISTS (
+              SELECT 1 FROM games g
+              WHERE li.item_type = 'game' AND g.id = li.item_id AND g.deleted_at IS NOT NULL
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM manga m
+              WHERE li.item_type = 'manga' AND m.id = li.item_id AND m.deleted_at IS NOT NULL
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM novels n
+              WHERE li.item_type = 'novel' AND n.id = li.item_id AND n.deleted_at IS NOT NULL
+          )
+        ORDER BY li.position
+        "#,
+        list_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Locks the list row so concurrent reorders of the same list serialise, and
+/// returns its current item count. `None` if the user does not own the list.
+async fn lock_list(
+    tx: &mut Transaction<'_, Postgres>,
+    list_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<i32>, sqlx::Error> {
+    let locked = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM lists
+        WHERE id = $1 AND user_id = $2
+        FOR UPDATE
+        "#,
+        list_id,
+        user_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    if locked.is_none() {
+        return Ok(None);
+    }
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*)::int AS "count!" FROM list_items WHERE list_id = $1"#,
+        list_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(Some(count))
+}
+
+/// Inserts the item at `position` (clamped to the list bounds), shifting the
+/// items after it down by one. Appends when `position` is `None`.
+pub async fn add_list_item(
+    pool: &PgPool,
+    list_id: Uuid,
+    user_id: Uuid,
+    kind: ItemKind,
+    item_id: Uuid,
+    position: Option<i32>,
+) -> Result<Option<ListItem>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let Some(count) = lock_list(&mut tx, list_id, user_id).await? else {
+        return Ok(None);
+    };
+    let position = position.unwrap_or(count).clamp(0, count);
+
+    sqlx::query!(
+        r#"
+        UPDATE list_items
+        SET position = position + 1
+        WHERE list_id = $1 AND position >= $2
+        "#,
+        list_id,
+        position
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let item = sqlx::query_as!(
+        ListItem,
+        r#"
+        INSERT INTO list_items (list_id, item_type, item_id, position)
+        VALUES ($1, $2, $3, $4)
+        RETURNING list_id, item_type, item_id, position, added_at
+        "#,
+        list_id,
+        kind.as_str(),
+        item_id,
+        position
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Some(item))
+}
+
+/// Moves an item to `position` (clamped to the list bounds) and shifts the
+/// items in between to keep positions contiguous.
+pub async fn move_list_item(
+    pool: &PgPool,
+    list_id: Uuid,
+    user_id: Uuid,
+    kind: ItemKind,
+    item_id: Uuid,
+    position: i32,
+) -> Result<Option<ListItem>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let Some(count) = lock_list(&mut tx, list_id, user_id).await? else {
+        return Ok(None);
+    };
+
+    let current = sqlx::query_scalar!(
+        r#"
+        SELECT position FROM list_items
+        WHERE list_id = $1 AND item_type = $2 AND item_id = $3
+        "#,
+        list_id,
+        kind.as_str(),
+        item_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(current) = current else {
+        return Ok(None);
+    };
+    let target = position.clamp(0, count - 1);
+
+    // Items between the old and new slot move one step towards the gap.
+    sqlx::query!(
+        r#"
+        UPDATE list_items
+        SET position = position + CASE WHEN $2 < $3 THEN 1 ELSE -1 END
+        WHERE list_id = $1
+          AND position BETWEEN LEAST($2, $3) AND GREATEST($2, $3)
+          AND position <> $3
+        "#,
+        list_id,
+        target,
+        current
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let item = sqlx::query_as!(
+        ListItem,
+        r#"
+        UPDATE list_items
+        SET position = $4
+        WHERE list_id = $1 AND item_type = $2 AND item_id = $3
+        RETURNING list_id, item_type, item_id, position, added_at
+        "#,
+        list_id,
+        kind.as_str(),
+        item_id,
+        target
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Some(item))
+}
+
+pub async fn remove_list_item(
+    pool: &PgPool,
+    list_id: Uuid,
+    user_id: Uuid,
+    kind: ItemKind,
+    item_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    if lock_list(&mut tx, list_id, user_id).await?.is_none() {
+        return Ok(false);
+    }
+
+    let removed = sqlx::query_scalar!(
+        r#"
+        DELETE FROM list_items
+        WHERE list_id = $1 AND item_type = $2 AND item_id = $3
+        RETURNING position
+        "#,
+        list_id,
+        kind.as_str(),
+        item_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(position) = removed else {
+        return Ok(false);
+    };
+
+    sqlx::query!(
+        r#"
+        UPDATE list_items
+        SET position = position - 1
+        WHERE list_id = $1 AND position > $2
+        "#,
+        list_id,
+        position
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
@@ -0,0 +1,20 @@
+pub mod activity_sql;
+pub mod collection_sql;
+pub mod cover_sql;
+pub mod game_sql;
+pub mod history_sql;
+pub mod item_sql;
+pub mod list_sql;
+pub mod manga_sql;
+pub mod movie_sql;
+pub mod novel_sql;
+pub mod profile_sql;
+pub mod public_sql;
+pub mod recommendation_sql;
+pub mod review_sql;
+pub mod share_sql;
+pub mod social_sql;
+pub mod stats_sql;
+pub mod sync_sql;
+pub mod tag_sql;
+pub mod user_sql;
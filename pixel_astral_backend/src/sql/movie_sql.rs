@@ -0,0 +1,113 @@
+use crate::type::movie_type::{CreateMovieRequest, Movie, UpdateMovieRequest};
+use futures::stream::BoxStream;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn create_movie<'e>(
+    executor: impl PgExecutor<'e>,
+    user_id: Uuid,
+    request: CreateMovieRequest,
+) -> Result<Movie, sqlx::Error> {
+    sqlx::query_as!(
+        Movie,
+        r#"
+        INSERT INTO movies (user_id, title, genre, rating, status, notes)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, user_id, title, genre, rating, status, notes, created_at, updated_at
+        "#,
+        user_id,
+        request.title,
+        request.genre,
+        request.rating,
+        request.status,
+        request.notes
+    )
+    .fetch_one(executor)
+    .await
+}
+
+pub async fn find_all_movies_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Movie>, sqlx::Error> {
+    sqlx::query_as!(
+        Movie,
+        r#"
+        SELECT id, user_id, title, genre, rating, status, notes, created_at, updated_at
+        FROM movies
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Rows are yielded as they arrive from the cursor, oldest first, for exports.
+pub fn stream_movies_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> BoxStream<'_, Result<Movie, sqlx::Error>> {
+    sqlx::query_as!(
+        Movie,
+        r#"
+        SELECT id, user_id, title, genre, rating, status, notes, created_at, updated_at
+        FROM movies
+        WHERE user_id = $1
+        ORDER BY created_at, id
+        "#,
+        user_id
+    )
+    .fetch(pool)
+}
+
+pub async fn find_movie_by_id(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<Movie>, sqlx::Error> {
+    sqlx::query_as!(
+        Movie,
+        r#"
+        SELECT id, user_id, title, genre, rating, status, notes, created_at, updated_at
+        FROM movies
+        WHERE id = $1 AND user_id = $2
+        "#,
+        id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn update_movie<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    user_id: Uuid,
+    request: UpdateMovieRequest,
+) -> Result<Option<Movie>, sqlx::Error> {
+    sqlx::query_as!(
+        Movie,
+        r#"
+        UPDATE movies
+        SET title = COALESCE($1, title),
+            genre = COALESCE($2, genre),
+            rating = COALESCE($3, rating),
+            status = COALESCE($4, status),
+            notes = COALESCE($5, notes),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $6 AND user_id = $7
+        RETURNING id, user_id, title, genre, rating, status, notes, created_at, updated_at
+        "#,
+        request.title,
+        request.genre,
+        request.rating,
+        request.status,
+        request.notes,
+        id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+}
@@ -0,0 +1,360 @@
+use crate::type::cover_type::Cover;
+use crate::type::novel_type::{CreateNovelRequest, Novel, NovelProgressRequest, UpdateNovelRequest};
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn create_novel<'e>(
+    executor: impl PgExecutor<'e>,
+    user_id: Uuid,
+    request: CreateNovelRequest,
+) -> Result<Novel, sqlx::Error> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        INSERT INTO novels (user_id, title, genre, rating, status, notes, url, chapter, page)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, COALESCE($8, 0), COALESCE($9, 0))
+        RETURNING id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        "#,
+        user_id,
+        request.title,
+        request.genre,
+        request.rating,
+        request.status,
+        request.notes,
+        request.url,
+        request.chapter,
+        request.page
+    )
+    .fetch_one(executor)
+    .await
+}
+
+pub async fn find_all_novels_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Novel>, sqlx::Error> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        SELECT id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        FROM novels
+        WHERE user_id = $1 AND deleted_at IS NULL
+        ORDER BY created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Rows written with `since <= sync_xid < until`, including ones in the
+/// trash, oldest change first.
+pub async fn find_novels_changed(
+    pool: &PgPool,
+    user_id: Uuid,
+    since: i64,
+    until: i64,
+) -> Result<Vec<Novel>, sqlx::Error> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        SELECT id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        FROM novels
+        WHERE user_id = $1 AND sync_xid >= $2 AND sync_xid < $3
+        ORDER BY sync_xid, id
+        "#,
+        user_id,
+        since,
+        until
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Rows are yielded as they arrive from the cursor, oldest first, for exports.
+pub fn stream_novels_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> BoxStream<'_, Result<Novel, sqlx::Error>> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        SELECT id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        FROM novels
+        WHERE user_id = $1 AND deleted_at IS NULL
+        ORDER BY created_at, id
+        "#,
+        user_id
+    )
+    .fetch(pool)
+}
+
+pub async fn find_novel_by_id(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<Novel>, sqlx::Error> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        SELECT id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        FROM novels
+        WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
+        "#,
+        id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn update_novel<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    user_id: Uuid,
+    request: UpdateNovelRequest,
+) -> Result<Option<Novel>, sqlx::Error> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        UPDATE novels
+        SET title = COALESCE($1, title),
+            genre = COALESCE($2, genre),
+            rating = COALESCE($3, rating),
+            status = COALESCE($4, status),
+            notes = COALESCE($5, notes),
+            url = COALESCE($6, url),
+            chapter = COALESCE($7, chapter),
+            page = COALESCE($8, page),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $9 AND user_id = $10 AND deleted_at IS NULL
+        RETURNING id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        "#,
+        request.title,
+        request.genre,
+        request.rating,
+        request.status,
+        request.notes,
+        request.url,
+        request.chapter,
+        request.page,
+        id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Adds the progress deltas in place, clamped to zero and the `INTEGER`
+/// maximum (summed as `BIGINT` so large deltas cannot overflow), and moves a
+/// `Plan to Read` entry to `Reading` on its first forward progress.
+pub async fn advance_novel_progress<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    user_id: Uuid,
+    request: NovelProgressRequest,
+) -> Result<Option<Novel>, sqlx::Error> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        UPDATE novels
+        SET chapter = LEAST(GREATEST(chapter::bigint + $1::integer, 0), 2147483647)::integer,
+            page = LEAST(GREATEST(page::bigint + $2::integer, 0), 2147483647)::integer,
+            status = CASE
+                WHEN status = 'Plan to Read' AND ($1 > 0 OR $2 > 0) THEN 'Reading'
+                ELSE status
+            END,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3 AND user_id = $4 AND deleted_at IS NULL
+        RETURNING id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        "#,
+        request.chapters,
+        request.pages,
+        id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Locks the row for the rest of the transaction, whether or not it is in
+/// the trash, so history can diff against the state being replaced.
+pub async fn find_novel_for_update<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<Novel>, sqlx::Error> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        SELECT id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        FROM novels
+        WHERE id = $1 AND user_id = $2
+        FOR UPDATE
+        "#,
+        id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Writes every editable field of `version` back, including `NULL`s. Covers
+/// are left alone.
+pub async fn revert_novel<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    user_id: Uuid,
+    version: Novel,
+) -> Result<Option<Novel>, sqlx::Error> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        UPDATE novels
+        SET title = $1,
+            genre = $2,
+            rating = $3,
+            status = $4,
+            notes = $5,
+            url = $6,
+            chapter = $7,
+            page = $8,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $9 AND user_id = $10 AND deleted_at IS NULL
+        RETURNING id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        "#,
+        version.title,
+        version.genre,
+        version.rating,
+        version.status,
+        version.notes,
+        version.url,
+        version.chapter,
+        version.page,
+        id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Moves the novel to the trash. It is hidden from every other query
+/// until restored, and purged for good after the retention period.
+pub async fn delete_novel<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<Novel>, sqlx::Error> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        UPDATE novels
+        SET deleted_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
+        RETURNING id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        "#,
+        id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+pub async fn find_trashed_novels_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Novel>, sqlx::Error> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        SELECT id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        FROM novels
+        WHERE user_id = $1 AND deleted_at IS NOT NULL
+        ORDER BY deleted_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn restore_novel<'e>(
+    executor: impl PgExecutor<'e>,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<Novel>, sqlx::Error> {
+    sqlx::query_as!(
+        Novel,
+        r#"
+        UPDATE novels
+        SET deleted_at = NULL
+        WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL
+        RETURNING id, user_id, title, genre, rating, status, notes, url, chapter, page, cover_url, cover_medium_url, cover_small_url, created_at, updated_at, deleted_at
+        "#,
+        id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Permanently deletes a novel that is already in the trash.
+/// Returns the deleted row's cover, whose files the caller removes.
+pub async fn purge_novel(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<Cover>, sqlx::Error> {
+    sqlx::query_as!(
+        Cover,
+        r#"
+        DELETE FROM novels
+        WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL
+        RETURNING cover_url, cover_medium_url, cover_small_url
+        "#,
+        id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Permanently deletes the user's trash. Returns the deleted rows' covers,
+/// whose files the caller removes.
+pub async fn purge_trashed_novels_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Cover>, sqlx::Error> {
+    sqlx::query_as!(
+        Cover,
+        r#"
+        DELETE FROM novels
+        WHERE user_id = $1 AND deleted_at IS NOT NULL
+        RETURNING cover_url, cover_medium_url, cover_small_url
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Permanently deletes novels of every user that were trashed before `cutoff`.
+/// Returns the deleted rows' covers, whose files the caller removes.
+pub async fn purge_novels_trashed_before(
+    pool: &PgPool,
+    cutoff: DateTime<Utc>,
+) -> Result<Vec<Cover>, sqlx::Error> {
+    sqlx::query_as!(
+        Cover,
+        r#"
+        DELETE FROM novels
+        WHERE deleted_at < $1
+        RETURNING cover_url, cover_medium_url, cover_small_url
+        "#,
+        cutoff
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,98 @@
+use crate::type::profile_type::{UpdateProfileRequest, UserProfile};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Returns the user's profile, creating one with default settings if the
+/// user has none yet.
+pub async fn find_or_create_profile(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<UserProfile, sqlx::Error> {
+    sqlx::query_as!(
+        UserProfile,
+        r#"
+        INSERT INTO user_profiles (user_id)
+        VALUES ($1)
+        ON CONFLICT (user_id) DO UPDATE SET user_id = EXCLUDED.user_id
+        RETURNING user_id, display_name, handle, bio, avatar_url, theme, default_view, locale, visibility, created_at, updated_at
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Read-only counterpart of [`find_or_create_profile`]; `None` until the
+/// user first opens or edits their profile.
+pub async fn find_profile_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Option<UserProfile>, sqlx::Error> {
+    sqlx::query_as!(
+        UserProfile,
+        r#"
+        SELECT user_id, display_name, handle, bio, avatar_url, theme, default_view, locale, visibility, created_at, updated_at
+        FROM user_profiles
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn find_profile_by_handle(
+    pool: &PgPool,
+    handle: &str,
+) -> Result<Option<UserProfile>, sqlx::Error> {
+    sqlx::query_as!(
+        UserProfile,
+        r#"
+        SELECT user_id, display_name, handle, bio, avatar_url, theme, default_view, locale, visibility, created_at, updated_at
+        FROM user_profiles
+        WHERE lower(handle) = lower($1)
+        "#,
+        handle
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn update_profile(
+    pool: &PgPool,
+    user_id: Uuid,
+    request: UpdateProfileRequest,
+) -> Result<Option<UserProfile>, sqlx::Error> {
+    sqlx::query_as!(
+        UserProfile,
+        r#"
+        UPDATE user_profiles
+        SET display_name = CASE WHEN $2 THEN $3 ELSE display_name END,
+            handle = CASE WHEN $4 THEN $5 ELSE handle END,
+            bio = CASE WHEN $6 THEN $7 ELSE bio END,
+            avatar_url = CASE WHEN $8 THEN $9 ELSE avatar_url END,
+            theme = COALESCE($10, theme),
+            default_view = COALESCE($11, default_view),
+            locale = COALESCE($12, locale),
+            visibility = COALESCE($13, visibility),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE user_id = $1
+        RETURNING user_id, display_name, handle, bio, avatar_url, theme, default_view, locale, visibility, created_at, updated_at
+        "#,
+        user_id,
+        request.display_name.is_set(),
+        request.display_name.into_option(),
+        request.handle.is_set(),
+        request.handle.into_option(),
+        request.bio.is_set(),
+        request.bio.into_option(),
+        request.avatar_url.is_set(),
+        request.avatar_url.into_option(),
+        request.theme,
+        request.default_view,
+        request.locale,
+        request.visibility
+    )
+    .fetch_optional(pool)
+    .await
+}
@@ -0,0 +1,123 @@
+use crate::type::item_type::ItemKind;
+use crate::type::public_type::{ItemVisibility, PublicGame, PublicItem};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Live games of the user's own collection without a narrower override.
+/// The caller checks that the profile itself is public.
+pub async fn find_public_games(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<PublicGame>, sqlx::Error> {
+    sqlx::query_as!(
+        PublicGame,
+        r#"
+        SELECT g.id, g.title, g.genre, g.rating, g.status, g.platform, g.hours_played, g.completion_percent, g.started_at, g.finished_at, g.cover_url, g.cover_medium_url, g.cover_small_url
+        FROM games g
+        LEFT JOIN item_visibility v ON v.item_type = 'game' AND v.item_id = g.id
+        WHERE g.user_id = $1 AND g.collection_id IS NULL AND g.deleted_at IS NULL
+          AND COALESCE(v.visibility, 'public') = 'public'
+        ORDER BY g.created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Live items of a list, in list order, leaving out the ones marked
+/// `private`.
+pub async fn find_shared_list_items(
+    pool: &PgPool,
+    list_id: Uuid,
+) -> Result<Vec<PublicItem>, sqlx::Error> {
+    sqlx::query_as!(
+        PublicItem,
+        r#"
+        SELECT
+            i.item_type AS "item_type!",
+            i.id AS "id!",
+            i.title AS "title!",
+            i.genre,
+            i.rating,
+            i.status AS "status!",
+            i.cover_url,
+            i.cover_medium_url,
+            i.cover_small_url,
+            li.position
+        FROM list_items li
+        JOIN collection_items i ON i.item_type = li.item_type AND i.id = li.item_id
+        LEFT JOIN item_visibility v ON v.item_type = li.item_type AND v.item_id = li.item_id
+        WHERE li.list_id = $1
+          AND COALESCE(v.visibility, 'unlisted') <> 'private'
+        ORDER BY li.position
+        "#,
+        list_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn find_item_visibilities(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<ItemVisibility>, sqlx::Error> {
+    sqlx::query_as!(
+        ItemVisibility,
+        r#"
+        SELECT item_type, item_id, visibility, updated_at
+        FROM item_visibility
+        WHERE user_id = $1
+        ORDER BY updated_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn set_item_visibility(
+    pool: &PgPool,
+    user_id: Uuid,
+    kind: ItemKind,
+    item_id: Uuid,
+    visibility: &str,
+) -> Result<ItemVisibility, sqlx::Error> {
+    sqlx::query_as!(
+        ItemVisibility,
+        r#"
+        INSERT INTO item_visibility (user_id, item_type, item_id, visibility)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (item_type, item_id) DO UPDATE
+            SET visibility = EXCLUDED.visibility, updated_at = CURRENT_TIMESTAMP
+        RETURNING item_type, item_id, visibility, updated_at
+        "#,
+        user_id,
+        kind.as_str(),
+        item_id,
+        visibility
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn clear_item_visibility(
+    pool: &PgPool,
+    user_id: Uuid,
+    kind: ItemKind,
+    item_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM item_visibility
+        WHERE user_id = $1 AND item_type = $2 AND item_id = $3
+        "#,
+        user_id,
+        kind.as_str(),
+        item_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
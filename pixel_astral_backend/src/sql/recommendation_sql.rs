@@ -0,0 +1,103 @@
+use crate::type::recommendation_type::{BacklogGame, NewRecommendation, RatedGame, Recommendation};
+use sqlx::types::Json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Rows per insert when storing scores.
+const INSERT_BATCH: usize = 1000;
+
+/// Every user's rated games outside shared collections and the trash.
+/// `rated_at` falls back from `finished_at` to the last update.
+pub async fn find_rated_games(pool: &PgPool) -> Result<Vec<RatedGame>, sqlx::Error> {
+    sqlx::query_as!(
+        RatedGame,
+        r#"
+        SELECT g.user_id, g.title, g.genre, g.platform, g.rating AS "rating!",
+            COALESCE(g.finished_at, g.updated_at AT TIME ZONE 'UTC', g.created_at AT TIME ZONE 'UTC') AS "rated_at!",
+            COALESCE(v.visibility, 'unlisted') <> 'private' AS "shared!"
+        FROM games g
+        LEFT JOIN item_visibility v ON v.item_type = 'game' AND v.item_id = g.id
+        WHERE g.rating IS NOT NULL AND g.collection_id IS NULL AND g.deleted_at IS NULL
+        "#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Every user's `Plan to Play` games outside shared collections and the
+/// trash.
+pub async fn find_backlog_games(pool: &PgPool) -> Result<Vec<BacklogGame>, sqlx::Error> {
+    sqlx::query_as!(
+        BacklogGame,
+        r#"
+        SELECT id, user_id, title, genre, platform
+        FROM games
+        WHERE status = 'Plan to Play' AND collection_id IS NULL AND deleted_at IS NULL
+        "#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Swaps in a whole new set of scores in one transaction, so readers see
+/// either the previous run or this one. Games deleted while the job ran
+/// are skipped.
+pub async fn replace_recommendations(
+    pool: &PgPool,
+    recommendations: &[NewRecommendation],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!("DELETE FROM game_recommendations")
+        .execute(&mut *tx)
+        .await?;
+
+    for batch in recommendations.chunks(INSERT_BATCH) {
+        sqlx::query!(
+            r#"
+            INSERT INTO game_recommendations (
+                user_id, game_id, score, genre_score, platform_score, similar_users_score, similar_users
+            )
+            SELECT r.user_id, r.game_id, r.score, r.genre_score, r.platform_score, r.similar_users_score, r.similar_users
+            FROM jsonb_to_recordset($1) AS r(
+                user_id UUID, game_id UUID, score DOUBLE PRECISION, genre_score DOUBLE PRECISION,
+                platform_score DOUBLE PRECISION, similar_users_score DOUBLE PRECISION, similar_users INTEGER
+            )
+            JOIN games g ON g.id = r.game_id
+            "#,
+            Json(batch) as _
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await
+}
+
+/// The user's `Plan to Play` games, best score first; games without a score
+/// yet follow, oldest first.
+pub async fn find_recommendations(
+    pool: &PgPool,
+    user_id: Uuid,
+    limit: i64,
+) -> Result<Vec<Recommendation>, sqlx::Error> {
+    sqlx::query_as!(
+        Recommendation,
+        r#"
+        SELECT g.id AS game_id, g.title, g.genre, g.platform, g.cover_small_url,
+            g.created_at AT TIME ZONE 'UTC' AS "added_at!",
+            r.score AS "score?", r.genre_score AS "genre_score?", r.platform_score AS "platform_score?",
+            r.similar_users_score AS "similar_users_score?", r.similar_users AS "similar_users?",
+            r.computed_at AS "computed_at?"
+        FROM games g
+        LEFT JOIN game_recommendations r ON r.user_id = g.user_id AND r.game_id = g.id
+        WHERE g.user_id = $1 AND g.status = 'Plan to Play' AND g.collection_id IS NULL AND g.deleted_at IS NULL
+        ORDER BY r.score DESC NULLS LAST, g.created_at, g.id
+        LIMIT $2
+        "#,
+        user_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
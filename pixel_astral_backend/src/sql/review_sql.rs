@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::type::review_type::ReviewItem;
+
+/// Live items of any kind started or finished in `[from, until)`. Only
+/// items whose status is `Completed` count as finished.
+pub async fn find_review_items(
+    pool: &PgPool,
+    user_id: Uuid,
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Vec<ReviewItem>, sqlx::Error> {
+    sqlx::query_as!(
+        ReviewItem,
+        r#"
+        WITH items AS (
+            SELECT
+                i.item_type,
+                i.id,
+                i.title,
+                i.genre,
+                i.rating,
+                i.added_at,
+                COALESCE(
+                    i.started_at,
+                    (
+                        SELECT MIN(e.created_at)
+                        FROM item_events e
+                        CROSS JOIN LATERAL jsonb_array_elements(e.changes) c
+                        WHERE e.item_type = i.item_type AND e.item_id = i.id
+                            AND c->>'field' = 'status' AND c->>'new' IN ('Playing', 'Reading')
+                    )
+                ) AS started_at,
+                CASE WHEN i.status = 'Completed' THEN COALESCE(
+                    i.finished_at,
+                    (
+                        SELECT MAX(e.created_at)
+                        FROM item_events e
+                        CROSS JOIN LATERAL jsonb_array_elements(e.changes) c
+                        WHERE e.item_type = i.item_type AND e.item_id = i.id
+                            AND c->>'field' = 'status' AND c->>'new' = 'Completed'
+                    )
+                ) END AS finished_at
+            FROM collection_items i
+            WHERE i.user_id = $1
+        )
+        SELECT
+            item_type AS "item_type!",
+            id AS "id!",
+            title AS "title!",
+            genre,
+            rating,
+            added_at AS "added_at!",
+            started_at,
+            finished_at
+        FROM items
+        WHERE (started_at >= $2 AND started_at < $3)
+            OR (finished_at >= $2 AND finished_at < $3)
+        ORDER BY COALESCE(finished_at, started_at)
+        "#,
+        user_id,
+        from,
+        until
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// `(month, events)` for the months of `[from, until)` with any history
+/// events, months numbered 1-12 in UTC.
+pub async fn count_events_by_month(
+    pool: &PgPool,
+    user_id: Uuid,
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Vec<(i32, i64)>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            EXTRACT(MONTH FROM created_at AT TIME ZONE 'UTC')::int AS "month!",
+            COUNT(*) AS "events!"
+        FROM item_events
+        WHERE user_id = $1 AND created_at >= $2 AND created_at < $3
+        GROUP BY 1
+        "#,
+        user_id,
+        from,
+        until
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| (row.month, row.events)).collect())
+}
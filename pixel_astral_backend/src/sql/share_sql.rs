@@ -0,0 +1,96 @@
+use crate::type::public_type::{ShareLink, SharedListOwner};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub async fn create_share_link(
+    pool: &PgPool,
+    user_id: Uuid,
+    list_id: Uuid,
+    token_hash: &str,
+) -> Result<ShareLink, sqlx::Error> {
+    sqlx::query_as!(
+        ShareLink,
+        r#"
+        INSERT INTO share_links (user_id, list_id, token_hash)
+        VALUES ($1, $2, $3)
+        RETURNING id, list_id, created_at, revoked_at
+        "#,
+        user_id,
+        list_id,
+        token_hash
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn find_share_links(
+    pool: &PgPool,
+    list_id: Uuid,
+    user_id: Uuid,
+) -> Result<Vec<ShareLink>, sqlx::Error> {
+    sqlx::query_as!(
+        ShareLink,
+        r#"
+        SELECT id, list_id, created_at, revoked_at
+        FROM share_links
+        WHERE list_id = $1 AND user_id = $2
+        ORDER BY created_at DESC
+        "#,
+        list_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Revoking twice keeps the first `revoked_at`.
+pub async fn revoke_share_link(
+    pool: &PgPool,
+    id: Uuid,
+    list_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<ShareLink>, sqlx::Error> {
+    sqlx::query_as!(
+        ShareLink,
+        r#"
+        UPDATE share_links
+        SET revoked_at = COALESCE(revoked_at, CURRENT_TIMESTAMP)
+        WHERE id = $1 AND list_id = $2 AND user_id = $3
+        RETURNING id, list_id, created_at, revoked_at
+        "#,
+        id,
+        list_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// The list and owner behind a token that has not been revoked.
+pub async fn find_shared_list(
+    pool: &PgPool,
+    token_hash: &str,
+) -> Result<Option<SharedListOwner>, sqlx::Error> {
+    sqlx::query_as!(
+        SharedListOwner,
+        r#"
+        SELECT
+            l.id AS list_id,
+            l.user_id,
+            l.name,
+            l.description,
+            COALESCE(p.visibility, 'private') AS "visibility!",
+            p.handle AS "handle?",
+            p.display_name AS "display_name?",
+            p.bio AS "bio?",
+            p.avatar_url AS "avatar_url?"
+        FROM share_links s
+        JOIN lists l ON l.id = s.list_id
+        LEFT JOIN user_profiles p ON p.user_id = l.user_id
+        WHERE s.token_hash = $1 AND s.revoked_at IS NULL
+        "#,
+        token_hash
+    )
+    .fetch_optional(pool)
+    .await
+}
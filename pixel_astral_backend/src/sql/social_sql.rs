@@ -0,0 +1,199 @@
+use crate::type::social_type::{BlockedUser, Follow, FollowUser};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Creates the follow with `status`, or returns the existing one unchanged.
+pub async fn create_follow(
+    pool: &PgPool,
+    follower_id: Uuid,
+    followee_id: Uuid,
+    status: &str,
+) -> Result<Follow, sqlx::Error> {
+    sqlx::query_as!(
+        Follow,
+        r#"
+        INSERT INTO follows (follower_id, followee_id, status, accepted_at)
+        VALUES ($1, $2, $3, CASE WHEN $3 = 'accepted' THEN CURRENT_TIMESTAMP END)
+        ON CONFLICT (follower_id, followee_id) DO UPDATE SET follower_id = EXCLUDED.follower_id
+        RETURNING follower_id, followee_id, status, created_at, accepted_at
+        "#,
+        follower_id,
+        followee_id,
+        status
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Removes a follow or a pending request.
+pub async fn delete_follow(
+    pool: &PgPool,
+    follower_id: Uuid,
+    followee_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM follows
+        WHERE follower_id = $1 AND followee_id = $2
+        "#,
+        follower_id,
+        followee_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Accepts a pending request; `None` if there is none.
+pub async fn accept_follow(
+    pool: &PgPool,
+    follower_id: Uuid,
+    followee_id: Uuid,
+) -> Result<Option<Follow>, sqlx::Error> {
+    sqlx::query_as!(
+        Follow,
+        r#"
+        UPDATE follows
+        SET status = 'accepted', accepted_at = CURRENT_TIMESTAMP
+        WHERE follower_id = $1 AND followee_id = $2 AND status = 'pending'
+        RETURNING follower_id, followee_id, status, created_at, accepted_at
+        "#,
+        follower_id,
+        followee_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Users `user_id` follows or has asked to follow, newest first.
+pub async fn find_following(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<FollowUser>, sqlx::Error> {
+    sqlx::query_as!(
+        FollowUser,
+        r#"
+        SELECT f.followee_id AS user_id, p.handle AS "handle?", p.display_name AS "display_name?", p.avatar_url AS "avatar_url?", f.status, f.created_at
+        FROM follows f
+        LEFT JOIN user_profiles p ON p.user_id = f.followee_id
+        WHERE f.follower_id = $1
+        ORDER BY f.created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Users following `user_id` or waiting for approval, newest first.
+pub async fn find_followers(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<FollowUser>, sqlx::Error> {
+    sqlx::query_as!(
+        FollowUser,
+        r#"
+        SELECT f.follower_id AS user_id, p.handle AS "handle?", p.display_name AS "display_name?", p.avatar_url AS "avatar_url?", f.status, f.created_at
+        FROM follows f
+        LEFT JOIN user_profiles p ON p.user_id = f.follower_id
+        WHERE f.followee_id = $1
+        ORDER BY f.created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Whether either user has blocked the other.
+pub async fn is_blocked_between(
+    pool: &PgPool,
+    user_id: Uuid,
+    other_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM blocks
+            WHERE (blocker_id = $1 AND blocked_id = $2) OR (blocker_id = $2 AND blocked_id = $1)
+        ) AS "blocked!"
+        "#,
+        user_id,
+        other_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Blocks `blocked_id` and drops follows in both directions, atomically.
+pub async fn create_block(
+    pool: &PgPool,
+    blocker_id: Uuid,
+    blocked_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO blocks (blocker_id, blocked_id)
+        VALUES ($1, $2)
+        ON CONFLICT (blocker_id, blocked_id) DO NOTHING
+        "#,
+        blocker_id,
+        blocked_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM follows
+        WHERE (follower_id = $1 AND followee_id = $2) OR (follower_id = $2 AND followee_id = $1)
+        "#,
+        blocker_id,
+        blocked_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await
+}
+
+pub async fn delete_block(
+    pool: &PgPool,
+    blocker_id: Uuid,
+    blocked_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM blocks
+        WHERE blocker_id = $1 AND blocked_id = $2
+        "#,
+        blocker_id,
+        blocked_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn find_blocks(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<BlockedUser>, sqlx::Error> {
+    sqlx::query_as!(
+        BlockedUser,
+        r#"
+        SELECT b.blocked_id AS user_id, p.handle AS "handle?", p.display_name AS "display_name?", b.created_at
+        FROM blocks b
+        LEFT JOIN user_profiles p ON p.user_id = b.blocked_id
+        WHERE b.blocker_id = $1
+        ORDER BY b.created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
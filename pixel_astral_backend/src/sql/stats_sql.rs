@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::type::stats_type::{GenreCount, PeriodCount, RatingCount, StatusCount};
+
+// Every query reads `collection_items`, which only holds items that are not
+// in the trash. `item_type` is the `ItemKind` string.
+
+pub async fn count_by_status(
+    pool: &PgPool,
+    user_id: Uuid,
+    item_type: &str,
+) -> Result<Vec<StatusCount>, sqlx::Error> {
+    sqlx::query_as!(
+        StatusCount,
+        r#"
+        SELECT status AS "status!", COUNT(*) AS "count!"
+        FROM collection_items
+        WHERE user_id = $1 AND item_type = $2
+        GROUP BY status
+        "#,
+        user_id,
+        item_type
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn count_by_genre(
+    pool: &PgPool,
+    user_id: Uuid,
+    item_type: &str,
+) -> Result<Vec<GenreCount>, sqlx::Error> {
+    sqlx::query_as!(
+        GenreCount,
+        r#"
+        SELECT genre, COUNT(*) AS "count!"
+        FROM collection_items
+        WHERE user_id = $1 AND item_type = $2
+        GROUP BY genre
+        ORDER BY COUNT(*) DESC, genre
+        "#,
+        user_id,
+        item_type
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn count_by_rating(
+    pool: &PgPool,
+    user_id: Uuid,
+    item_type: &str,
+) -> Result<Vec<RatingCount>, sqlx::Error> {
+    sqlx::query_as!(
+        RatingCount,
+        r#"
+        SELECT rating AS "rating!", COUNT(*) AS "count!"
+        FROM collection_items
+        WHERE user_id = $1 AND item_type = $2 AND rating IS NOT NULL
+        GROUP BY rating
+        ORDER BY rating
+        "#,
+        user_id,
+        item_type
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Completed items by UTC month. The completion time is `finished_at` where
+/// the kind has it, otherwise the last time the history shows the status
+/// becoming `Completed`; items with neither are left out.
+pub async fn count_completed_by_month(
+    pool: &PgPool,
+    user_id: Uuid,
+    item_type: &str,
+) -> Result<Vec<PeriodCount>, sqlx::Error> {
+    sqlx::query_as!(
+        PeriodCount,
+        r#"
+        WITH completions AS (
+            SELECT COALESCE(
+                i.finished_at,
+                (
+                    SELECT MAX(e.created_at)
+                    FROM item_events e
+                    CROSS JOIN LATERAL jsonb_array_elements(e.changes) c
+                    WHERE e.item_type = i.item_type AND e.item_id = i.id
+                        AND c->>'field' = 'status' AND c->>'new' = 'Completed'
+                )
+            ) AS completed_at
+            FROM collection_items i
+            WHERE i.user_id = $1 AND i.item_type = $2 AND i.status = 'Completed'
+        )
+        SELECT
+            to_char(date_trunc('month', completed_at AT TIME ZONE 'UTC'), 'YYYY-MM') AS "period!",
+            COUNT(*) AS "count!"
+        FROM completions
+        WHERE completed_at IS NOT NULL
+        GROUP BY 1
+        ORDER BY 1
+        "#,
+        user_id,
+        item_type
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Mean days between the status becoming `plan_status` and the next time it
+/// becomes `Completed`, over every such transition in the history.
+pub async fn average_days_to_complete(
+    pool: &PgPool,
+    user_id: Uuid,
+    item_type: &str,
+    plan_status: &str,
+) -> Result<Option<f64>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        WITH status_changes AS (
+            SELECT e.item_id, e.created_at, c->>'new' AS status
+            FROM item_events e
+            CROSS JOIN LATERAL jsonb_array_elements(e.changes) c
+            JOIN collection_items i ON i.item_type = e.item_type AND i.id = e.item_id
+            WHERE e.user_id = $1 AND e.item_type = $2 AND c->>'field' = 'status'
+        ),
+        spans AS (
+            SELECT done.created_at - MAX(planned.created_at) AS duration
+            FROM status_changes done
+            JOIN status_changes planned
+                ON planned.item_id = done.item_id
+                AND planned.status = $3
+                AND planned.created_at < done.created_at
+            WHERE done.status = 'Completed'
+            GROUP BY done.item_id, done.created_at
+        )
+        SELECT (AVG(EXTRACT(EPOCH FROM duration)) / 86400)::float8 AS "days"
+        FROM spans
+        "#,
+        user_id,
+        item_type,
+        plan_status
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// `(current, longest)` runs of consecutive UTC days with at least one
+/// history event. The current run must reach `today` or the day before.
+pub async fn activity_streaks(
+    pool: &PgPool,
+    user_id: Uuid,
+    item_type: &str,
+    now: DateTime<Utc>,
+) -> Result<(i64, i64), sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        WITH days AS (
+            SELECT DISTINCT (created_at AT TIME ZONE 'UTC')::date AS day
+            FROM item_events
+            WHERE user_id = $1 AND item_type = $2
+        ),
+        runs AS (
+            SELECT COUNT(*) AS length, MAX(day) AS last_day
+            FROM (SELECT day, day - ROW_NUMBER() OVER (ORDER BY day)::int AS run FROM days) d
+            GROUP BY run
+        )
+        SELECT
+            COALESCE(MAX(length) FILTER (WHERE last_day >= ($3 AT TIME ZONE 'UTC')::date - 1), 0) AS "current!",
+            COALESCE(MAX(length), 0) AS "longest!"
+        FROM runs
+        "#,
+        user_id,
+        item_type,
+        now
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok((row.current, row.longest))
+}
@@ -0,0 +1,36 @@
+use crate::type::sync_type::SyncDeletion;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// The oldest transaction still running. Every change written by an older
+/// transaction is already visible, so it is safe to hand out as a cursor.
+pub async fn current_sync_cursor(pool: &PgPool) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT pg_snapshot_xmin(pg_current_snapshot())::text::bigint AS "cursor!""#
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Purged items of any kind with `since <= sync_xid < until`.
+pub async fn find_tombstones(
+    pool: &PgPool,
+    user_id: Uuid,
+    since: i64,
+    until: i64,
+) -> Result<Vec<SyncDeletion>, sqlx::Error> {
+    sqlx::query_as!(
+        SyncDeletion,
+        r#"
+        SELECT item_type, item_id, deleted_at
+        FROM sync_tombstones
+        WHERE user_id = $1 AND sync_xid >= $2 AND sync_xid < $3
+        ORDER BY sync_xid
+        "#,
+        user_id,
+        since,
+        until
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,204 @@
+use crate::type::item_type::ItemKind;
+use crate::type::tag_type::{CreateTagRequest, ItemTagLink, Tag, UpdateTagRequest};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+pub async fn create_tag(
+    pool: &PgPool,
+    user_id: Uuid,
+    request: CreateTagRequest,
+) -> Result<Tag, sqlx::Error> {
+    sqlx::query_as!(
+        Tag,
+        r#"
+        INSERT INTO tags (user_id, name, color)
+        VALUES ($1, $2, $3)
+        RETURNING id, user_id, name, color, created_at, updated_at
+        "#,
+        user_id,
+        request.name,
+        request.color
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn find_all_tags_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Tag>, sqlx::Error> {
+    sqlx::query_as!(
+        Tag,
+        r#"
+        SELECT id, user_id, name, color, created_at, updated_at
+        FROM tags
+        WHERE user_id = $1
+        ORDER BY lower(name)
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn update_tag(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+    request: UpdateTagRequest,
+) -> Result<Option<Tag>, sqlx::Error> {
+    sqlx::query_as!(
+        Tag,
+        r#"
+        UPDATE tags
+        SET name = COALESCE($1, name),
+            color = COALESCE($2, color),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3 AND user_id = $4
+        RETURNING id, user_id, name, color, created_at, updated_at
+        "#,
+        request.name,
+        request.color,
+        id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn delete_tag(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM tags
+        WHERE id = $1 AND user_id = $2
+        "#,
+        id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn find_tags_for_item(
+    pool: &PgPool,
+    user_id: Uuid,
+    kind: ItemKind,
+    item_id: Uuid,
+) -> Result<Vec<Tag>, sqlx::Error> {
+    sqlx::query_as!(
+        Tag,
+        r#"
+        SELECT t.id, t.user_id, t.name, t.color, t.created_at, t.updated_at
+        FROM tags t
+        JOIN item_tags it ON it.tag_id = t.id
+        WHERE t.user_id = $1 AND it.item_type = $2 AND it.item_id = $3
+        ORDER BY lower(t.name)
+        "#,
+        user_id,
+        kind.as_str(),
+        item_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn find_item_tag_links_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<ItemTagLink>, sqlx::Error> {
+    sqlx::query_as!(
+        ItemTagLink,
+        r#"
+        SELECT it.tag_id, it.item_type, it.item_id
+        FROM item_tags it
+        JOIN tags t ON t.id = it.tag_id
+        WHERE t.user_id = $1
+        ORDER BY it.item_type, it.item_id, it.tag_id
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Replaces the item's tags. Returns `false` without changing anything if
+/// any of `tag_ids` does not belong to the user.
+pub async fn set_item_tags(
+    pool: &PgPool,
+    user_id: Uuid,
+    kind: ItemKind,
+    item_id: Uuid,
+    tag_ids: &[Uuid],
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    if !replace_item_tags(&mut tx, user_id, kind, item_id, tag_ids).await? {
+        return Ok(false);
+    }
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// [`set_item_tags`] on a caller-managed connection, for use inside a
+/// larger transaction. The item is addressed by ID alone, so callers check
+/// access first; only the user's own tags are replaced, leaving other
+/// collection members' tags on a shared game alone.
+pub async fn replace_item_tags(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    kind: ItemKind,
+    item_id: Uuid,
+    tag_ids: &[Uuid],
+) -> Result<bool, sqlx::Error> {
+    let owned = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) AS "count!"
+        FROM tags
+        WHERE user_id = $1 AND id = ANY($2)
+        "#,
+        user_id,
+        tag_ids
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if owned as usize != tag_ids.len() {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM item_tags
+        WHERE item_type = $1 AND item_id = $2
+            AND tag_id IN (SELECT id FROM tags WHERE user_id = $3)
+        "#,
+        kind.as_str(),
+        item_id,
+        user_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO item_tags (tag_id, item_type, item_id)
+        SELECT tag_id, $2, $3
+        FROM UNNEST($1::uuid[]) AS tag_id
+        "#,
+        tag_ids,
+        kind.as_str(),
+        item_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(true)
+}
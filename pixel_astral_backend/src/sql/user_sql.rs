@@ -1,5 +1,7 @@
-use crate::type::user_type::User;
-use sqlx::PgPool;
+use crate::type::cover_type::Cover;
+use crate::type::user_type::{LoginAttempt, Session, User, UserToken};
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
 
 pub async fn create_user(
@@ -12,7 +14,7 @@ pub async fn create_user(
         r#"
         INSERT INTO users (email, password_hash)
         VALUES ($1, $2)
-        RETURNING id, email, password_hash, created_at, updated_at
+        RETURNING id, email, password_hash, email_verified_at, pending_email, deletion_scheduled_at, created_at, updated_at
         "#,
         email,
         password_hash
@@ -28,9 +30,9 @@ pub async fn find_user_by_email(
     sqlx::query_as!(
         User,
         r#"
-        SELECT id, email, password_hash, created_at, updated_at
+        SELECT id, email, password_hash, email_verified_at, pending_email, deletion_scheduled_at, created_at, updated_at
         FROM users
-        WHERE email = $1
+        WHERE lower(email) = lower($1)
         "#,
         email
     )
@@ -45,12 +47,443 @@ pub async fn find_user_by_id(
     sqlx::query_as!(
         User,
         r#"
-        SELECT id, email, password_hash, created_at, updated_at
+        SELECT id, email, password_hash, email_verified_at, pending_email, deletion_scheduled_at, created_at, updated_at
+        FROM users
+        WHERE id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn find_login_attempt(
+    pool: &PgPool,
+    email: &str,
+) -> Result<Option<LoginAttempt>, sqlx::Error> {
+    sqlx::query_as!(
+        LoginAttempt,
+        r#"
+        SELECT email, failed_count, locked_until, last_failed_at
+        FROM login_attempts
+        WHERE email = $1
+        "#,
+        email
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn record_failed_login(
+    pool: &PgPool,
+    email: &str,
+) -> Result<LoginAttempt, sqlx::Error> {
+    sqlx::query_as!(
+        LoginAttempt,
+        r#"
+        INSERT INTO login_attempts (email, failed_count, last_failed_at)
+        VALUES ($1, 1, CURRENT_TIMESTAMP)
+        ON CONFLICT (email) DO UPDATE
+        SET failed_count = CASE
+                WHEN login_attempts.last_failed_at < CURRENT_TIMESTAMP - INTERVAL '1 day' THEN 1
+                ELSE login_attempts.failed_count + 1
+            END,
+            last_failed_at = CURRENT_TIMESTAMP
+        RETURNING email, failed_count, locked_until, last_failed_at
+        "#,
+        email
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn lock_login(
+    pool: &PgPool,
+    email: &str,
+    locked_until: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE login_attempts
+        SET locked_until = $2
+        WHERE email = $1
+        "#,
+        email,
+        locked_until
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn clear_login_attempts(
+    pool: &PgPool,
+    email: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM login_attempts
+        WHERE email = $1
+        "#,
+        email
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn mark_email_verified(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET email_verified_at = COALESCE(email_verified_at, CURRENT_TIMESTAMP),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        "#,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn update_password(
+    pool: &PgPool,
+    user_id: Uuid,
+    password_hash: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET password_hash = $2,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        "#,
+        user_id,
+        password_hash
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Stores a new token, discarding any unused token issued earlier for the
+/// same purpose so only the latest link works.
+pub async fn create_user_token(
+    pool: &PgPool,
+    user_id: Uuid,
+    purpose: &str,
+    token_hash: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<UserToken, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM user_tokens
+        WHERE user_id = $1 AND purpose = $2 AND used_at IS NULL
+        "#,
+        user_id,
+        purpose
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let token = sqlx::query_as!(
+        UserToken,
+        r#"
+        INSERT INTO user_tokens (user_id, purpose, token_hash, expires_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, user_id, purpose, token_hash, expires_at, used_at, created_at
+        "#,
+        user_id,
+        purpose,
+        token_hash,
+        expires_at
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(token)
+}
+
+/// Marks a token as used and returns it, or `None` if it is unknown, expired
+/// or was already used. The single `UPDATE` makes concurrent use impossible.
+pub async fn consume_user_token(
+    pool: &PgPool,
+    purpose: &str,
+    token_hash: &str,
+) -> Result<Option<UserToken>, sqlx::Error> {
+    sqlx::query_as!(
+        UserToken,
+        r#"
+        UPDATE user_tokens
+        SET used_at = CURRENT_TIMESTAMP
+        WHERE purpose = $1
+          AND token_hash = $2
+          AND used_at IS NULL
+          AND expires_at > CURRENT_TIMESTAMP
+        RETURNING id, user_id, purpose, token_hash, expires_at, used_at, created_at
+        "#,
+        purpose,
+        token_hash
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn set_pending_email(
+    pool: &PgPool,
+    user_id: Uuid,
+    pending_email: &str,
+) -> Result<Option<User>, sqlx::Error> {
+    sqlx::query_as!(
+        User,
+        r#"
+        UPDATE users
+        SET pending_email = $2,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING id, email, password_hash, email_verified_at, pending_email, deletion_scheduled_at, created_at, updated_at
+        "#,
+        user_id,
+        pending_email
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Swaps in the pending email once its verification link is used.
+pub async fn apply_pending_email(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Option<User>, sqlx::Error> {
+    sqlx::query_as!(
+        User,
+        r#"
+        UPDATE users
+        SET email = pending_email,
+            pending_email = NULL,
+            email_verified_at = CURRENT_TIMESTAMP,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND pending_email IS NOT NULL
+        RETURNING id, email, password_hash, email_verified_at, pending_email, deletion_scheduled_at, created_at, updated_at
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn schedule_user_deletion(
+    pool: &PgPool,
+    user_id: Uuid,
+    deletion_scheduled_at: Option<DateTime<Utc>>,
+) -> Result<Option<User>, sqlx::Error> {
+    sqlx::query_as!(
+        User,
+        r#"
+        UPDATE users
+        SET deletion_scheduled_at = $2,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING id, email, password_hash, email_verified_at, pending_email, deletion_scheduled_at, created_at, updated_at
+        "#,
+        user_id,
+        deletion_scheduled_at
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Deletes the user; collection tables cascade through their foreign keys,
+/// apart from shared games, which [`reassign_shared_games`] keeps. Returns
+/// the covers of the items that went with the user, or `None` if there was
+/// no such user.
+pub async fn delete_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Option<Vec<Cover>>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    reassign_shared_games(&mut *tx, &[user_id]).await?;
+    let covers = find_covers_by_users(&mut *tx, &[user_id]).await?;
+
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM users
+        WHERE id = $1
+        "#,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((result.rows_affected() > 0).then_some(covers))
+}
+
+/// Returns how many accounts were deleted and the covers of the items that
+/// went with them.
+pub async fn delete_users_due_for_deletion(
+    pool: &PgPool,
+) -> Result<(u64, Vec<Cover>), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let user_ids = sqlx::query_scalar!(
+        r#"
+        SELECT id
         FROM users
+        WHERE deletion_scheduled_at <= CURRENT_TIMESTAMP
+        FOR UPDATE
+        "#
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    reassign_shared_games(&mut *tx, &user_ids).await?;
+    let covers = find_covers_by_users(&mut *tx, &user_ids).await?;
+
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM users
+        WHERE id = ANY($1)
+        "#,
+        &user_ids
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((result.rows_affected(), covers))
+}
+
+/// Covers of every item the users own, trashed or not, whose files must go
+/// once the accounts are deleted.
+async fn find_covers_by_users<'e>(
+    executor: impl PgExecutor<'e>,
+    user_ids: &[Uuid],
+) -> Result<Vec<Cover>, sqlx::Error> {
+    sqlx::query_as!(
+        Cover,
+        r#"
+        SELECT cover_url, cover_medium_url, cover_small_url
+        FROM games
+        WHERE user_id = ANY($1) AND cover_url IS NOT NULL
+        UNION ALL
+        SELECT cover_url, cover_medium_url, cover_small_url
+        FROM manga
+        WHERE user_id = ANY($1) AND cover_url IS NOT NULL
+        UNION ALL
+        SELECT cover_url, cover_medium_url, cover_small_url
+        FROM novels
+        WHERE user_id = ANY($1) AND cover_url IS NOT NULL
+        "#,
+        user_ids
+    )
+    .fetch_all(executor)
+    .await
+}
+
+/// Hands the users' shared games to another member of each collection, so
+/// they outlive the accounts: an owner if there is one, otherwise the
+/// longest-standing member. Games in collections nobody else is left in go
+/// with the users.
+async fn reassign_shared_games<'e>(
+    executor: impl PgExecutor<'e>,
+    user_ids: &[Uuid],
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE games g
+        SET user_id = heir.user_id
+        FROM (
+            SELECT DISTINCT ON (collection_id) collection_id, user_id
+            FROM collection_members
+            WHERE user_id <> ALL($1)
+            ORDER BY collection_id, role = 'owner' DESC, joined_at, user_id
+        ) heir
+        WHERE g.collection_id = heir.collection_id AND g.user_id = ANY($1)
+        "#,
+        user_ids
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn create_session(
+    pool: &PgPool,
+    user_id: Uuid,
+    expires_at: DateTime<Utc>,
+) -> Result<Session, sqlx::Error> {
+    sqlx::query_as!(
+        Session,
+        r#"
+        INSERT INTO sessions (user_id, expires_at)
+        VALUES ($1, $2)
+        RETURNING id, user_id, expires_at, revoked_at, created_at
+        "#,
+        user_id,
+        expires_at
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn find_active_session(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<Session>, sqlx::Error> {
+    sqlx::query_as!(
+        Session,
+        r#"
+        SELECT id, user_id, expires_at, revoked_at, created_at
+        FROM sessions
         WHERE id = $1
+          AND user_id = $2
+          AND revoked_at IS NULL
+          AND expires_at > CURRENT_TIMESTAMP
         "#,
+        id,
         user_id
     )
     .fetch_optional(pool)
     .await
 }
+
+/// Revokes every active session of the user except `keep`, if given.
+pub async fn revoke_sessions(
+    pool: &PgPool,
+    user_id: Uuid,
+    keep: Option<Uuid>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE sessions
+        SET revoked_at = CURRENT_TIMESTAMP
+        WHERE user_id = $1
+          AND revoked_at IS NULL
+          AND ($2::uuid IS NULL OR id <> $2)
+        "#,
+        user_id,
+        keep
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What a member may do in a shared collection. Ordered so a check can ask
+/// for "at least" a role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CollectionRole {
+    /// Reads the games and their history.
+    Viewer,
+    /// Also adds, changes, trashes and restores games.
+    Editor,
+    /// Also purges games from the trash, renames or deletes the collection
+    /// and manages members and invitations.
+    Owner,
+}
+
+impl CollectionRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CollectionRole::Viewer => "viewer",
+            CollectionRole::Editor => "editor",
+            CollectionRole::Owner => "owner",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "viewer" => Some(CollectionRole::Viewer),
+            "editor" => Some(CollectionRole::Editor),
+            "owner" => Some(CollectionRole::Owner),
+            _ => None,
+        }
+    }
+}
+
+/// A collection as seen by one member.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct SharedCollection {
+    pub id: Uuid,
+    pub name: String,
+    /// The caller's role: `owner`, `editor` or `viewer`.
+    pub role: String,
+    pub member_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct CollectionMember {
+    pub user_id: Uuid,
+    pub handle: Option<String>,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    /// `owner`, `editor` or `viewer`
+    pub role: String,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// An open invitation. Exactly one of `email` and `invitee_id` is set.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct CollectionInvitation {
+    pub id: Uuid,
+    pub collection_id: Uuid,
+    pub collection_name: String,
+    pub invited_by: Uuid,
+    pub email: Option<String>,
+    pub invitee_id: Option<Uuid>,
+    /// Role given on accepting: `owner`, `editor` or `viewer`.
+    pub role: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateCollectionRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateCollectionRequest {
+    pub name: String,
+}
+
+/// Invites someone by `email` or by profile `handle`; send exactly one.
+/// An email invitation is matched to whichever account verifies that
+/// address, so people without an account yet can be invited.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct InviteMemberRequest {
+    pub email: Option<String>,
+    pub handle: Option<String>,
+    /// `owner`, `editor` or `viewer` (default `editor`).
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetMemberRoleRequest {
+    /// `owner`, `editor` or `viewer`
+    pub role: String,
+}
+
+pub const MAX_COLLECTION_NAME_LENGTH: usize = 100;
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Cover {
+    pub cover_url: Option<String>,
+    pub cover_medium_url: Option<String>,
+    pub cover_small_url: Option<String>,
+}
+
+pub const MAX_COVER_BYTES: usize = 5 * 1024 * 1024;
+pub const MAX_COVER_DIMENSION: u32 = 8192;
+
+/// Longest edge, in pixels, of the generated thumbnails.
+pub const COVER_MEDIUM_SIZE: u32 = 480;
+pub const COVER_SMALL_SIZE: u32 = 160;
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A committed row of `item_events`, as sent over `NOTIFY` and the event
+/// stream. The full diff stays behind `GET .../:id/history`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ItemNotification {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub user_id: Uuid,
+    pub item_type: String,
+    pub item_id: Uuid,
+    /// The history action: `create`, `update`, `delete`, `restore` or `revert`.
+    pub action: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ItemNotification {
+    /// SSE event name: whether the item appeared, changed or went away.
+    pub fn event_name(&self) -> &'static str {
+        match self.action.as_str() {
+            "create" | "restore" => "created",
+            "delete" => "deleted",
+            _ => "updated",
+        }
+    }
+}
+
+/// What the hub fans out to the streams of this instance.
+#[derive(Debug, Clone)]
+pub enum HubMessage {
+    Event(ItemNotification),
+    /// The listener lost its connection, so events may have been missed.
+    /// Streams close and clients resume with `Last-Event-ID`.
+    Interrupted,
+}
+
+/// What a single stream yields.
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    Item(ItemNotification),
+    /// `Last-Event-ID` is unknown or too far behind to replay; the client
+    /// should refetch (or sync) instead.
+    Reset,
+}
+
+/// `NOTIFY` channel written by the `item_events` trigger.
+pub const ITEM_EVENTS_CHANNEL: &str = "item_events";
+
+/// Most events replayed for a `Last-Event-ID` before asking for a reset.
+pub const MAX_REPLAY_EVENTS: i64 = 500;
+
+/// Comment lines sent on idle streams so proxies keep them open.
+pub const EVENT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
@@ -0,0 +1,190 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::type::game_type::Game;
+use crate::type::import_type::ImportKind;
+use crate::type::item_type::ItemKind;
+use crate::type::manga_type::Manga;
+use crate::type::movie_type::Movie;
+use crate::type::novel_type::Novel;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExportQuery {
+    /// `json` (default) or `csv`
+    pub format: Option<ExportFormat>,
+}
+
+/// One collection item per CSV row. Field order is the column order and
+/// must stay in sync with `EXPORT_CSV_COLUMNS`; columns that do not apply to
+/// an item type are left empty.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ExportCsvRow {
+    pub item_type: String,
+    pub id: Option<Uuid>,
+    pub title: String,
+    pub genre: Option<String>,
+    pub rating: Option<i32>,
+    pub status: String,
+    pub notes: Option<String>,
+    pub platform: Option<String>,
+    pub hours_played: Option<f64>,
+    pub completion_percent: Option<i32>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub url: Option<String>,
+    pub chapter: Option<i32>,
+    pub page: Option<i32>,
+    pub publication_status: Option<String>,
+    pub cover_url: Option<String>,
+    pub cover_medium_url: Option<String>,
+    pub cover_small_url: Option<String>,
+    /// Tag names joined with `EXPORT_TAG_SEPARATOR`.
+    pub tags: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<Game> for ExportCsvRow {
+    fn from(game: Game) -> Self {
+        Self {
+            item_type: ItemKind::Game.to_string(),
+            id: Some(game.id),
+            title: game.title,
+            genre: game.genre,
+            rating: game.rating,
+            status: game.status,
+            notes: game.notes,
+            platform: game.platform,
+            hours_played: game.hours_played,
+            completion_percent: game.completion_percent,
+            started_at: game.started_at,
+            finished_at: game.finished_at,
+            cover_url: game.cover_url,
+            cover_medium_url: game.cover_medium_url,
+            cover_small_url: game.cover_small_url,
+            created_at: Some(game.created_at),
+            updated_at: game.updated_at,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<Manga> for ExportCsvRow {
+    fn from(manga: Manga) -> Self {
+        Self {
+            item_type: ItemKind::Manga.to_string(),
+            id: Some(manga.id),
+            title: manga.title,
+            genre: manga.genre,
+            rating: manga.rating,
+            status: manga.status,
+            notes: manga.notes,
+            url: manga.url,
+            chapter: Some(manga.chapter),
+            page: Some(manga.page),
+            publication_status: manga.publication_status,
+            cover_url: manga.cover_url,
+            cover_medium_url: manga.cover_medium_url,
+            cover_small_url: manga.cover_small_url,
+            created_at: Some(manga.created_at),
+            updated_at: manga.updated_at,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<Novel> for ExportCsvRow {
+    fn from(novel: Novel) -> Self {
+        Self {
+            item_type: ItemKind::Novel.to_string(),
+            id: Some(novel.id),
+            title: novel.title,
+            genre: novel.genre,
+            rating: novel.rating,
+            status: novel.status,
+            notes: novel.notes,
+            url: novel.url,
+            chapter: Some(novel.chapter),
+            page: Some(novel.page),
+            cover_url: novel.cover_url,
+            cover_medium_url: novel.cover_medium_url,
+            cover_small_url: novel.cover_small_url,
+            created_at: Some(novel.created_at),
+            updated_at: novel.updated_at,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<Movie> for ExportCsvRow {
+    fn from(movie: Movie) -> Self {
+        Self {
+            item_type: ImportKind::Movie.as_str().to_string(),
+            id: Some(movie.id),
+            title: movie.title,
+            genre: movie.genre,
+            rating: movie.rating,
+            status: movie.status,
+            notes: movie.notes,
+            created_at: Some(movie.created_at),
+            updated_at: movie.updated_at,
+            ..Self::default()
+        }
+    }
+}
+
+/// Bumped whenever the JSON envelope or CSV columns change incompatibly.
+pub const EXPORT_VERSION: u32 = 1;
+
+pub const EXPORT_CSV_COLUMNS: &[&str] = &[
+    "item_type",
+    "id",
+    "title",
+    "genre",
+    "rating",
+    "status",
+    "notes",
+    "platform",
+    "hours_played",
+    "completion_percent",
+    "started_at",
+    "finished_at",
+    "url",
+    "chapter",
+    "page",
+    "publication_status",
+    "cover_url",
+    "cover_medium_url",
+    "cover_small_url",
+    "tags",
+    "created_at",
+    "updated_at",
+];
+
+pub const EXPORT_TAG_SEPARATOR: &str = ";";
@@ -2,21 +2,42 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use sqlx::FromRow;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::type::patch_type::Patch;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Game {
     pub id: Uuid,
+    /// The owner, or for a shared game the member who added it.
     pub user_id: Uuid,
+    /// Set for games in a shared collection.
+    pub collection_id: Option<Uuid>,
     pub title: String,
     pub genre: Option<String>,
     pub rating: Option<i32>,
     pub status: String,
     pub notes: Option<String>,
+    pub platform: Option<String>,
+    pub hours_played: Option<f64>,
+    pub completion_percent: Option<i32>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub cover_url: Option<String>,
+    pub cover_medium_url: Option<String>,
+    pub cover_small_url: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    /// Set while the item is in the trash.
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Incremented on every write; served as the `ETag`. Defaults for
+    /// history snapshots taken before the column existed.
+    #[serde(default)]
+    pub version: i32,
 }
 
+/// Body of create and of `PUT`, which replaces every field: optional fields
+/// left out are cleared.
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateGameRequest {
     pub title: String,
@@ -24,15 +45,131 @@ pub struct CreateGameRequest {
     pub rating: Option<i32>,
     pub status: String,
     pub notes: Option<String>,
+    pub platform: Option<String>,
+    pub hours_played: Option<f64>,
+    pub completion_percent: Option<i32>,
+    pub started_at: Option<DateTime<Utc>>,
+    /// Defaults to now (on `PUT`, to the stored value if any) when `status`
+    /// is `Completed`.
+    pub finished_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+/// Partial update: omitted fields are kept, `null` clears a field and a
+/// value replaces it. `title` and `status` cannot be cleared.
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+#[serde(default)]
 pub struct UpdateGameRequest {
-    pub title: Option<String>,
-    pub genre: Option<String>,
-    pub rating: Option<i32>,
-    pub status: Option<String>,
-    pub notes: Option<String>,
+    #[schema(value_type = Option<String>)]
+    pub title: Patch<String>,
+    #[schema(value_type = Option<String>)]
+    pub genre: Patch<String>,
+    #[schema(value_type = Option<i32>)]
+    pub rating: Patch<i32>,
+    #[schema(value_type = Option<String>)]
+    pub status: Patch<String>,
+    #[schema(value_type = Option<String>)]
+    pub notes: Patch<String>,
+    #[schema(value_type = Option<String>)]
+    pub platform: Patch<String>,
+    #[schema(value_type = Option<f64>)]
+    pub hours_played: Patch<f64>,
+    #[schema(value_type = Option<i32>)]
+    pub completion_percent: Patch<i32>,
+    #[schema(value_type = Option<DateTime<Utc>>)]
+    pub started_at: Patch<DateTime<Utc>>,
+    /// Set to now when `status` moves to `Completed`, unless sent or
+    /// already set.
+    #[schema(value_type = Option<DateTime<Utc>>)]
+    pub finished_at: Patch<DateTime<Utc>>,
+}
+
+/// Query filters for listing games.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct GameFilter {
+    /// Comma-separated tag IDs; only games carrying all of them are returned.
+    pub tags: Option<String>,
+    /// Only games in this list, in list order.
+    pub list: Option<Uuid>,
+}
+
+/// `all_or_nothing` rolls back every operation if any fails; `best_effort`
+/// keeps the ones that succeeded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkMode {
+    #[default]
+    AllOrNothing,
+    BestEffort,
+}
+
+/// The same changes applied to every game in `ids`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkGameUpdate {
+    pub ids: Vec<Uuid>,
+    #[serde(default)]
+    pub changes: Option<UpdateGameRequest>,
+    /// Replaces the tags on each game when present.
+    pub tag_ids: Option<Vec<Uuid>>,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkGameRequest {
+    #[serde(default)]
+    pub mode: BulkMode,
+    #[serde(default)]
+    pub create: Vec<CreateGameRequest>,
+    #[serde(default)]
+    pub update: Vec<BulkGameUpdate>,
+    #[serde(default)]
+    pub delete: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkOperation {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkGameResult {
+    pub operation: BulkOperation,
+    /// Position in the request's `create` array; `None` for updates and deletes.
+    pub index: Option<usize>,
+    pub id: Option<Uuid>,
+    /// HTTP status the operation would have had on its own.
+    pub status: u16,
+    pub error: Option<String>,
+    pub game: Option<Game>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkGameResponse {
+    /// `false` when `all_or_nothing` rolled everything back.
+    pub committed: bool,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkGameResult>,
+}
+
+/// Upper bound on creates + updated IDs + deletes in one bulk request.
+pub const MAX_BULK_GAME_OPERATIONS: usize = 500;
+
 pub const GAME_STATUSES: &[&str] = &["Playing", "Completed", "Plan to Play", "Dropped"];
+
+pub const GAME_PLATFORMS: &[&str] = &[
+    "PC",
+    "Mac",
+    "Linux",
+    "Steam Deck",
+    "PlayStation 5",
+    "PlayStation 4",
+    "Xbox Series X|S",
+    "Xbox One",
+    "Nintendo Switch",
+    "iOS",
+    "Android",
+    "Retro",
+    "Other",
+];
@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::types::Json;
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::type::item_type::ItemKind;
+
+/// What happened to an item, stored as `action` in `item_events`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemAction {
+    Create,
+    Update,
+    Delete,
+    Restore,
+    Revert,
+}
+
+impl ItemAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemAction::Create => "create",
+            ItemAction::Update => "update",
+            ItemAction::Delete => "delete",
+            ItemAction::Restore => "restore",
+            ItemAction::Revert => "revert",
+        }
+    }
+}
+
+/// One field that differs between two versions of an item. `old` is `null`
+/// for fields set on creation.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ItemEvent {
+    pub id: Uuid,
+    /// Who made the change; in a shared collection, the member. `null` once
+    /// that member's account is deleted.
+    pub user_id: Option<Uuid>,
+    pub item_type: String,
+    pub item_id: Uuid,
+    /// One of `create`, `update`, `delete`, `restore` or `revert`.
+    pub action: String,
+    #[schema(value_type = Vec<FieldChange>)]
+    pub changes: Json<Vec<FieldChange>>,
+    /// The full item as it was right after this event; reverting to the
+    /// event restores these values.
+    #[schema(value_type = Object)]
+    pub snapshot: Value,
+    /// For reverts, the event whose version was restored.
+    pub reverted_event_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct NewItemEvent {
+    pub user_id: Uuid,
+    pub item_type: ItemKind,
+    pub item_id: Uuid,
+    pub action: ItemAction,
+    pub changes: Vec<FieldChange>,
+    pub snapshot: Value,
+    pub reverted_event_id: Option<Uuid>,
+}
+
+/// Fields left out of diffs: identity and bookkeeping columns, and the cover
+/// thumbnails, which follow `cover_url`. A cover change shows up as a
+/// `cover_url` change, but a revert leaves the cover alone since replaced
+/// files are deleted.
+pub const UNTRACKED_FIELDS: &[&str] = &[
+    "id",
+    "user_id",
+    "collection_id",
+    "created_at",
+    "updated_at",
+    "version",
+    "cover_medium_url",
+    "cover_small_url",
+];
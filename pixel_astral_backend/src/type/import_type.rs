@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::type::export_type::ExportFormat;
+
+/// Where the uploaded file came from. `generic` is our own export or any
+/// CSV/JSON file read through `mapping`; the others are the export files of
+/// other trackers, parsed offline.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportSource {
+    #[default]
+    Generic,
+    /// Steam `GetOwnedGames` JSON (games)
+    Steam,
+    /// MyAnimeList XML export (manga)
+    MyAnimeList,
+    /// Goodreads library CSV (novels)
+    Goodreads,
+    /// Letterboxd `watched.csv`, `ratings.csv` or `diary.csv` (movies)
+    Letterboxd,
+}
+
+/// The collection an imported row is written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImportKind {
+    Game,
+    Manga,
+    Novel,
+    Movie,
+}
+
+impl ImportKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportKind::Game => "game",
+            ImportKind::Manga => "manga",
+            ImportKind::Novel => "novel",
+            ImportKind::Movie => "movie",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "game" => Some(ImportKind::Game),
+            "manga" => Some(ImportKind::Manga),
+            "novel" => Some(ImportKind::Novel),
+            "movie" => Some(ImportKind::Movie),
+            _ => None,
+        }
+    }
+}
+
+/// A source row with values keyed by field name (see `IMPORT_FIELDS`).
+/// Empty values are left out.
+pub type ImportRow = HashMap<String, String>;
+
+/// What to do with a row that matches an item already in the collection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateStrategy {
+    #[default]
+    Skip,
+    Update,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ImportQuery {
+    #[serde(default)]
+    pub source: ImportSource,
+    /// `json` or `csv` for the generic source; inferred from `Content-Type`
+    /// when omitted.
+    pub format: Option<ExportFormat>,
+    /// Validate and preview without writing anything.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// JSON object mapping fields to source column names for the generic
+    /// source, e.g. `{"title":"Name","hours_played":"Playtime"}`. Unmapped
+    /// fields are read from a column of the same name.
+    pub mapping: Option<String>,
+    #[serde(default)]
+    pub on_duplicate: DuplicateStrategy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportAction {
+    Create,
+    Update,
+    Skip,
+    Invalid,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportRowResult {
+    /// 1-based data row (CSV header and JSON envelope not counted).
+    pub row: usize,
+    pub action: ImportAction,
+    /// `game`, `manga`, `novel` or `movie`
+    pub item_type: Option<String>,
+    pub title: Option<String>,
+    pub platform: Option<String>,
+    /// The matched item for updates and duplicate skips, or the new item
+    /// once a create has been applied.
+    pub item_id: Option<Uuid>,
+    pub reason: Option<String>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportResponse {
+    pub dry_run: bool,
+    /// `false` for dry runs and when invalid rows blocked the import.
+    pub applied: bool,
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub invalid: usize,
+    pub rows: Vec<ImportRowResult>,
+}
+
+/// Fields that can be read from an import row and targeted by `mapping`.
+/// `item_type` defaults to `game`; fields that do not apply to the row's
+/// item type are ignored.
+pub const IMPORT_FIELDS: &[&str] = &[
+    "item_type",
+    "title",
+    "genre",
+    "rating",
+    "status",
+    "notes",
+    "platform",
+    "hours_played",
+    "completion_percent",
+    "started_at",
+    "finished_at",
+    "url",
+    "chapter",
+    "page",
+    "publication_status",
+];
+
+pub const MAX_IMPORT_BYTES: usize = 10 * 1024 * 1024;
+
+pub const MAX_IMPORT_ROWS: usize = 5000;
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// The collection an item belongs to, stored as `item_type` in tables that
+/// reference items of any kind (tags, lists, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemKind {
+    Game,
+    Manga,
+    Novel,
+}
+
+impl ItemKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemKind::Game => "game",
+            ItemKind::Manga => "manga",
+            ItemKind::Novel => "novel",
+        }
+    }
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ItemKind {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "game" => Ok(ItemKind::Game),
+            "manga" => Ok(ItemKind::Manga),
+            "novel" => Ok(ItemKind::Novel),
+            other => Err(anyhow::anyhow!("Unknown item type: {}", other)),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+use crate::type::item_type::ItemKind;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CustomList {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ListItem {
+    pub list_id: Uuid,
+    pub item_type: String,
+    pub item_id: Uuid,
+    /// Zero-based, contiguous within the list (items in the trash are
+    /// hidden but keep their slot).
+    pub position: i32,
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListWithItems {
+    #[serde(flatten)]
+    pub list: CustomList,
+    pub items: Vec<ListItem>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateListRequest {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateListRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddListItemRequest {
+    pub item_type: ItemKind,
+    pub item_id: Uuid,
+    /// Insert before the item currently at this position; appends when omitted.
+    pub position: Option<i32>,
+}
+
+/// Drag-to-reorder: the item moves to `position` and the items in between
+/// shift by one to close the gap.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MoveListItemRequest {
+    pub position: i32,
+}
+
+pub const MAX_LIST_NAME_LENGTH: usize = 80;
@@ -0,0 +1,22 @@
+pub mod collection_type;
+pub mod cover_type;
+pub mod event_type;
+pub mod export_type;
+pub mod game_type;
+pub mod history_type;
+pub mod import_type;
+pub mod item_type;
+pub mod list_type;
+pub mod manga_type;
+pub mod movie_type;
+pub mod novel_type;
+pub mod patch_type;
+pub mod profile_type;
+pub mod public_type;
+pub mod recommendation_type;
+pub mod review_type;
+pub mod social_type;
+pub mod stats_type;
+pub mod sync_type;
+pub mod tag_type;
+pub mod user_type;
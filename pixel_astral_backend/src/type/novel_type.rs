@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Novel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub title: String,
+    pub genre: Option<String>,
+    pub rating: Option<i32>,
+    pub status: String,
+    pub notes: Option<String>,
+    pub url: Option<String>,
+    pub chapter: i32,
+    pub page: i32,
+    pub cover_url: Option<String>,
+    pub cover_medium_url: Option<String>,
+    pub cover_small_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Set while the item is in the trash.
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateNovelRequest {
+    pub title: String,
+    pub genre: Option<String>,
+    pub rating: Option<i32>,
+    pub status: String,
+    pub notes: Option<String>,
+    pub url: Option<String>,
+    pub chapter: Option<i32>,
+    pub page: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateNovelRequest {
+    pub title: Option<String>,
+    pub genre: Option<String>,
+    pub rating: Option<i32>,
+    pub status: Option<String>,
+    pub notes: Option<String>,
+    pub url: Option<String>,
+    pub chapter: Option<i32>,
+    pub page: Option<i32>,
+}
+
+/// Relative progress: the deltas are added to the stored values in a single
+/// statement, so reports from two devices both count.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct NovelProgressRequest {
+    #[serde(default)]
+    pub chapters: i32,
+    #[serde(default)]
+    pub pages: i32,
+}
+
+pub const NOVEL_STATUSES: &[&str] = &["Reading", "Completed", "Plan to Read", "Dropped"];
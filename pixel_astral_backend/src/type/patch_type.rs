@@ -0,0 +1,118 @@
+use serde::{Deserialize, Deserializer};
+
+/// A field in a partial update. Use with `#[serde(default)]` so that a
+/// missing key stays `Absent` while an explicit `null` becomes `Null`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Patch<T> {
+    /// Not sent: keep the stored value.
+    #[default]
+    Absent,
+    /// Sent as `null`: clear the stored value.
+    Null,
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    /// Whether the stored value should be overwritten (with a value or `NULL`).
+    pub fn is_set(&self) -> bool {
+        !matches!(self, Patch::Absent)
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Patch::Null)
+    }
+
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Patch::Value(value) => Some(value),
+            Patch::Absent | Patch::Null => None,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Patch<U> {
+        match self {
+            Patch::Absent => Patch::Absent,
+            Patch::Null => Patch::Null,
+            Patch::Value(value) => Patch::Value(f(value)),
+        }
+    }
+
+    /// The value to write when `is_set`; `None` for both `Null` and `Absent`.
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Patch::Value(value) => Some(value),
+            Patch::Absent | Patch::Null => None,
+        }
+    }
+}
+
+/// `None` means "leave unchanged", for callers that only know which fields
+/// they have a value for.
+impl<T> From<Option<T>> for Patch<T> {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Patch::Absent, Patch::Value)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<T>::deserialize(deserializer).map(|value| value.map_or(Patch::Null, Patch::Value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Patch;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(default)]
+    struct Request {
+        name: Patch<String>,
+        count: Patch<i32>,
+    }
+
+    fn parse(json: &str) -> Request {
+        serde_json::from_str(json).expect("valid request")
+    }
+
+    #[test]
+    fn missing_key_is_absent() {
+        let request = parse("{}");
+
+        assert_eq!(request.name, Patch::Absent);
+        assert_eq!(request.count, Patch::Absent);
+        assert!(!request.name.is_set());
+    }
+
+    #[test]
+    fn explicit_null_is_null() {
+        let request = parse(r#"{"name": null, "count": null}"#);
+
+        assert_eq!(request.name, Patch::Null);
+        assert_eq!(request.count, Patch::Null);
+        assert!(request.name.is_set());
+        assert_eq!(request.name.into_option(), None);
+    }
+
+    #[test]
+    fn value_is_value() {
+        let request = parse(r#"{"name": "Celeste", "count": 3}"#);
+
+        assert_eq!(request.name, Patch::Value("Celeste".to_string()));
+        assert_eq!(request.count.value(), Some(&3));
+    }
+
+    #[test]
+    fn keys_are_independent() {
+        let request = parse(r#"{"name": null}"#);
+
+        assert_eq!(request.name, Patch::Null);
+        assert_eq!(request.count, Patch::Absent);
+    }
+
+    #[test]
+    fn wrong_type_is_rejected() {
+        assert!(serde_json::from_str::<Request>(r#"{"count": "three"}"#).is_err());
+    }
+}
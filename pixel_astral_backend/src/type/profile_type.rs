@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+use crate::type::patch_type::Patch;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct UserProfile {
+    pub user_id: Uuid,
+    pub display_name: Option<String>,
+    pub handle: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    pub theme: String,
+    pub default_view: String,
+    pub locale: String,
+    /// `private`, `unlisted` or `public`; see `VISIBILITIES`.
+    pub visibility: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Partial update: omitted fields are kept. `null` clears the display
+/// name, handle, bio or avatar; the settings below them cannot be cleared
+/// and ignore `null`.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct UpdateProfileRequest {
+    #[schema(value_type = Option<String>)]
+    pub display_name: Patch<String>,
+    #[schema(value_type = Option<String>)]
+    pub handle: Patch<String>,
+    #[schema(value_type = Option<String>)]
+    pub bio: Patch<String>,
+    #[schema(value_type = Option<String>)]
+    pub avatar_url: Patch<String>,
+    pub theme: Option<String>,
+    pub default_view: Option<String>,
+    pub locale: Option<String>,
+    pub visibility: Option<String>,
+}
+
+pub const PROFILE_THEMES: &[&str] = &["dark", "light", "system"];
+pub const PROFILE_VIEWS: &[&str] = &["grid", "list"];
+
+/// Handles that would clash with routes or impersonate the service.
+pub const RESERVED_HANDLES: &[&str] = &[
+    "about", "admin", "administrator", "api", "help", "login", "logout", "me", "moderator",
+    "pixelastral", "pixel_astral", "public", "root", "settings", "shares", "signup", "staff",
+    "support", "system",
+];
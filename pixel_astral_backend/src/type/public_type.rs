@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// `private` shows nothing, `unlisted` only what is reached through a share
+/// link, `public` also the collection under `/api/public/:handle`.
+pub const VISIBILITIES: &[&str] = &["private", "unlisted", "public"];
+
+/// A per-item override. It can only narrow the profile's visibility: a
+/// `public` item in an `unlisted` collection is still unlisted.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ItemVisibility {
+    pub item_type: String,
+    pub item_id: Uuid,
+    pub visibility: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// `null` removes the override so the item follows the profile again.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetItemVisibilityRequest {
+    pub visibility: Option<String>,
+}
+
+/// What anyone may see of a profile.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PublicProfile {
+    pub handle: Option<String>,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+/// A game as shown to other people: no notes and no bookkeeping fields.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PublicGame {
+    pub id: Uuid,
+    pub title: String,
+    pub genre: Option<String>,
+    pub rating: Option<i32>,
+    pub status: String,
+    pub platform: Option<String>,
+    pub hours_played: Option<f64>,
+    pub completion_percent: Option<i32>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub cover_url: Option<String>,
+    pub cover_medium_url: Option<String>,
+    pub cover_small_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicCollection {
+    pub profile: PublicProfile,
+    pub games: Vec<PublicGame>,
+}
+
+/// An item of any kind in a shared list, with the fields every kind has.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PublicItem {
+    pub item_type: String,
+    pub id: Uuid,
+    pub title: String,
+    pub genre: Option<String>,
+    pub rating: Option<i32>,
+    pub status: String,
+    pub cover_url: Option<String>,
+    pub cover_medium_url: Option<String>,
+    pub cover_small_url: Option<String>,
+    pub position: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SharedList {
+    pub name: String,
+    pub description: Option<String>,
+    pub owner: PublicProfile,
+    pub items: Vec<PublicItem>,
+}
+
+/// A read-only link to a list. The token itself is only returned once, on
+/// creation.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ShareLink {
+    pub id: Uuid,
+    pub list_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedShareLink {
+    #[serde(flatten)]
+    pub link: ShareLink,
+    /// Goes in `GET /api/public/shares/:token`; it cannot be read again.
+    pub token: String,
+}
+
+/// The owner and list behind a live share token.
+#[derive(Debug, Clone)]
+pub struct SharedListOwner {
+    pub list_id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub visibility: String,
+    pub handle: Option<String>,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+/// Cache policy for public responses. Shared caches may hold them for a few
+/// minutes, so revoking a link or hiding an item can take that long to show.
+pub const PUBLIC_CACHE_CONTROL: &str = "public, max-age=60, s-maxage=300";
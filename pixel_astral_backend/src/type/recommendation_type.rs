@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// A rated game, as read by the batch job. `shared` is false for games
+/// marked `private`, which only count towards their owner's own tastes.
+#[derive(Debug, Clone, FromRow)]
+pub struct RatedGame {
+    pub user_id: Uuid,
+    pub title: String,
+    pub genre: Option<String>,
+    pub platform: Option<String>,
+    pub rating: i32,
+    pub rated_at: DateTime<Utc>,
+    pub shared: bool,
+}
+
+/// A `Plan to Play` game waiting for a score.
+#[derive(Debug, Clone, FromRow)]
+pub struct BacklogGame {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub title: String,
+    pub genre: Option<String>,
+    pub platform: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NewRecommendation {
+    pub user_id: Uuid,
+    pub game_id: Uuid,
+    pub score: f64,
+    pub genre_score: Option<f64>,
+    pub platform_score: Option<f64>,
+    pub similar_users_score: Option<f64>,
+    pub similar_users: i32,
+}
+
+/// A backlog game with its precomputed score. Games added since the last
+/// batch run have no score yet and come last.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Recommendation {
+    pub game_id: Uuid,
+    pub title: String,
+    pub genre: Option<String>,
+    pub platform: Option<String>,
+    pub cover_small_url: Option<String>,
+    pub added_at: DateTime<Utc>,
+    /// 0-100; 50 is neutral.
+    pub score: Option<f64>,
+    /// How the user rates games of this genre, from -1 (much lower than
+    /// usual) to 1; `null` without a genre or history for it.
+    pub genre_score: Option<f64>,
+    /// Same for the platform.
+    pub platform_score: Option<f64>,
+    /// How users with similar ratings rated the same title, -1 to 1; `null`
+    /// when none of them did.
+    pub similar_users_score: Option<f64>,
+    /// How many similar users the title's score is based on.
+    pub similar_users: Option<i32>,
+    pub computed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RecommendationQuery {
+    /// 1-100 (default 20).
+    pub limit: Option<i64>,
+}
+
+pub const DEFAULT_RECOMMENDATION_LIMIT: i64 = 20;
+pub const MAX_RECOMMENDATION_LIMIT: i64 = 100;
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::type::stats_type::GenreCount;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewFormat {
+    #[default]
+    Json,
+    /// A shareable pixel-art card.
+    Svg,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ReviewQuery {
+    /// `json` (default) or `svg`
+    pub format: Option<ReviewFormat>,
+}
+
+/// An item started or finished during the year. `started_at` and
+/// `finished_at` come from the item where it has them (games), otherwise from
+/// its history.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReviewItem {
+    pub item_type: String,
+    pub id: Uuid,
+    pub title: String,
+    pub genre: Option<String>,
+    pub rating: Option<i32>,
+    pub added_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// The item finished this year that waited longest since it was added.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BacklogItem {
+    pub item: ReviewItem,
+    pub days: i64,
+}
+
+/// History events in one month of the year (1-12).
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MonthActivity {
+    pub month: u32,
+    pub events: i64,
+}
+
+/// A user's games, manga and novels over one calendar year (UTC).
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct YearInReview {
+    pub year: i32,
+    pub started: i64,
+    pub finished: i64,
+    /// Best-rated items finished during the year, up to `REVIEW_TOP_RATED`.
+    pub top_rated: Vec<ReviewItem>,
+    /// Genres of the items started or finished, most common first.
+    pub genres: Vec<GenreCount>,
+    pub longest_backlog: Option<BacklogItem>,
+    /// `null` for a year without activity.
+    pub busiest_month: Option<MonthActivity>,
+    /// All twelve months, January first.
+    pub activity_by_month: Vec<MonthActivity>,
+}
+
+pub const REVIEW_TOP_RATED: usize = 5;
+
+/// Oldest year a review can be asked for.
+pub const REVIEW_MIN_YEAR: i32 = 2000;
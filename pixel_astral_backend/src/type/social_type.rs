@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::type::item_type::ItemKind;
+
+/// Follows of a `private` profile start `pending` until the owner approves
+/// them; other follows are `accepted` straight away.
+pub const FOLLOW_PENDING: &str = "pending";
+pub const FOLLOW_ACCEPTED: &str = "accepted";
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Follow {
+    pub follower_id: Uuid,
+    pub followee_id: Uuid,
+    /// `pending` or `accepted`
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+}
+
+/// The other side of a follow, with enough of the profile to list it.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct FollowUser {
+    pub user_id: Uuid,
+    pub handle: Option<String>,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    /// `pending` or `accepted`
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct BlockedUser {
+    pub user_id: Uuid,
+    pub handle: Option<String>,
+    pub display_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What a feed entry says the user did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityVerb {
+    Added,
+    Started,
+    Completed,
+    Dropped,
+    Rated,
+}
+
+impl ActivityVerb {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActivityVerb::Added => "added",
+            ActivityVerb::Started => "started",
+            ActivityVerb::Completed => "completed",
+            ActivityVerb::Dropped => "dropped",
+            ActivityVerb::Rated => "rated",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NewActivity {
+    pub user_id: Uuid,
+    pub event_id: Uuid,
+    pub item_type: ItemKind,
+    pub item_id: Uuid,
+    pub verb: ActivityVerb,
+    pub title: String,
+    pub rating: Option<i32>,
+}
+
+/// "X completed Hollow Knight, rated 5". `title` and `rating` are as they
+/// were at the time.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Activity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub handle: Option<String>,
+    pub display_name: Option<String>,
+    pub item_type: String,
+    pub item_id: Uuid,
+    /// `added`, `started`, `completed`, `dropped` or `rated`
+    pub verb: String,
+    pub title: String,
+    pub rating: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct FeedQuery {
+    /// `next_cursor` of the previous page; omit for the newest entries.
+    pub cursor: Option<String>,
+    /// Page size, 1-100 (default 20).
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeedPage {
+    pub activities: Vec<Activity>,
+    /// `null` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+pub const DEFAULT_FEED_LIMIT: i64 = 20;
+pub const MAX_FEED_LIMIT: i64 = 100;
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::type::item_type::ItemKind;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct StatsQuery {
+    /// `game` (default), `manga` or `novel`
+    pub item_type: Option<ItemKind>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StatusCount {
+    pub status: String,
+    pub count: i64,
+}
+
+/// `genre` is `null` for items without one.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GenreCount {
+    pub genre: Option<String>,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RatingCount {
+    pub rating: i32,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RatingStats {
+    /// `null` when nothing is rated.
+    pub average: Option<f64>,
+    pub rated: i64,
+    /// Every rating from 1 to 5, including those with no items.
+    pub distribution: Vec<RatingCount>,
+}
+
+/// `period` is `YYYY-MM` or `YYYY` (UTC).
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PeriodCount {
+    pub period: String,
+    pub count: i64,
+}
+
+/// Runs of consecutive UTC days on which the user changed at least one item
+/// of this kind. The current streak counts if it reached today or yesterday.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StreakStats {
+    pub current_days: i64,
+    pub longest_days: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CollectionStats {
+    pub item_type: ItemKind,
+    pub total: i64,
+    /// Every status of the item type, including those with no items.
+    pub by_status: Vec<StatusCount>,
+    /// Most common first.
+    pub by_genre: Vec<GenreCount>,
+    pub ratings: RatingStats,
+    /// Completed items by the month they were completed, oldest first.
+    pub completed_by_month: Vec<PeriodCount>,
+    pub completed_by_year: Vec<PeriodCount>,
+    /// Mean days from the plan status (`Plan to Play` / `Plan to Read`) to
+    /// `Completed`, from the item history. `null` without such a transition.
+    pub average_days_to_complete: Option<f64>,
+    pub streaks: StreakStats,
+}
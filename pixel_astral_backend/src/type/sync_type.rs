@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::type::game_type::{CreateGameRequest, Game};
+use crate::type::manga_type::Manga;
+use crate::type::novel_type::Novel;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SyncQuery {
+    /// `cursor` from the previous sync; omit for a full download.
+    pub since: Option<i64>,
+}
+
+/// An item the client should drop: moved to the trash or purged.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SyncDeletion {
+    pub item_type: String,
+    pub item_id: Uuid,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// Everything that changed between `since` and `cursor`. Items are sent
+/// whole, in their latest state.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncResponse {
+    /// Opaque; pass it back as `since` next time.
+    pub cursor: i64,
+    pub games: Vec<Game>,
+    pub manga: Vec<Manga>,
+    pub novels: Vec<Novel>,
+    pub deleted: Vec<SyncDeletion>,
+}
+
+/// A change made offline. `id` is generated by the client for new games;
+/// `base_version` is the version the change was made against (`None` for a
+/// game the client created).
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum GameMutation {
+    Upsert {
+        id: Uuid,
+        base_version: Option<i32>,
+        game: CreateGameRequest,
+    },
+    Delete {
+        id: Uuid,
+        base_version: Option<i32>,
+    },
+}
+
+impl GameMutation {
+    pub fn id(&self) -> Uuid {
+        match self {
+            GameMutation::Upsert { id, .. } | GameMutation::Delete { id, .. } => *id,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SyncPushRequest {
+    #[serde(default)]
+    pub games: Vec<GameMutation>,
+}
+
+/// `conflict` means the server copy moved on since `base_version`; the
+/// client should merge with the returned `game` and push again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStatus {
+    Applied,
+    Conflict,
+    Rejected,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncMutationResult {
+    pub id: Uuid,
+    pub status: SyncStatus,
+    pub error: Option<String>,
+    /// The server copy after the mutation (or, on conflict, as it stands);
+    /// `None` when it no longer exists.
+    pub game: Option<Game>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncPushResponse {
+    pub results: Vec<SyncMutationResult>,
+}
+
+/// Upper bound on mutations in one push.
+pub const MAX_SYNC_MUTATIONS: usize = 500;
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Tag {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub color: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateTagRequest {
+    pub name: String,
+    /// `#rrggbb`
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateTagRequest {
+    pub name: Option<String>,
+    pub color: Option<String>,
+}
+
+/// Replaces the full set of tags on an item.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetItemTagsRequest {
+    pub tag_ids: Vec<Uuid>,
+}
+
+/// A tag applied to one collection item.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ItemTagLink {
+    pub tag_id: Uuid,
+    pub item_type: String,
+    pub item_id: Uuid,
+}
+
+pub const MAX_TAG_NAME_LENGTH: usize = 40;
@@ -4,6 +4,8 @@ use uuid::Uuid;
 use sqlx::FromRow;
 use utoipa::ToSchema;
 
+use crate::type::profile_type::UserProfile;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct User {
     pub id: Uuid,
@@ -11,6 +13,9 @@ pub struct User {
     #[serde(skip_serializing)]
     #[schema(hidden)]
     pub password_hash: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub pending_email: Option<String>,
+    pub deletion_scheduled_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
@@ -31,12 +36,52 @@ pub struct LoginRequest {
 pub struct UserResponse {
     pub id: Uuid,
     pub email: String,
+    pub email_verified: bool,
+    pub pending_email: Option<String>,
+    pub deletion_scheduled_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VerifyEmailRequest {
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct EmailRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChangeEmailRequest {
+    pub password: String,
+    pub new_email: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DeleteAccountRequest {
+    pub password: String,
+    /// Skip the grace period and delete everything right away.
+    #[serde(default)]
+    pub immediate: bool,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AuthResponse {
     pub user: UserResponse,
+    pub profile: UserProfile,
     pub token: String,
 }
 
@@ -45,6 +90,9 @@ impl From<User> for UserResponse {
         Self {
             id: user.id,
             email: user.email,
+            email_verified: user.email_verified_at.is_some(),
+            pending_email: user.pending_email,
+            deletion_scheduled_at: user.deletion_scheduled_at,
             created_at: user.created_at,
         }
     }
@@ -53,5 +101,38 @@ impl From<User> for UserResponse {
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct Claims {
     pub sub: String,
+    pub sid: String,
     pub exp: usize,
 }
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct LoginAttempt {
+    pub email: String,
+    pub failed_count: i32,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub last_failed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct UserToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub purpose: String,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub const TOKEN_PURPOSE_VERIFY_EMAIL: &str = "verify_email";
+pub const TOKEN_PURPOSE_PASSWORD_RESET: &str = "password_reset";
+pub const TOKEN_PURPOSE_CHANGE_EMAIL: &str = "change_email";